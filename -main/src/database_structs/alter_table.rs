@@ -1,4 +1,4 @@
-use crate::driver::TableField;
+use crate::driver::{TableConstraint, TableField, TableIndex};
 
 /// Single alter-table operation used by the migration procedural macros.
 ///
@@ -20,6 +20,14 @@ pub enum AlterTableSingle {
         old_column_name: &'static str,
         new_column_name: &'static str,
     },
+    /// Create a new index on the table.
+    CreateIndex { index: TableIndex },
+    /// Drop an existing index.
+    DropIndex { name: &'static str },
+    /// Add a new table constraint.
+    AddConstraint { constraint: TableConstraint },
+    /// Drop an existing table constraint.
+    DropConstraint { name: &'static str },
 }
 
 /// Collection of alter-table operations for a single table.
@@ -29,6 +37,8 @@ pub enum AlterTableSingle {
 /// as part of migration compilation, so prefer using the macro instead of
 /// constructing this directly.
 pub struct AlterTable {
+    /// See [`Table::schema`](crate::Table::schema)
+    pub schema: Option<&'static str>,
     pub table_name: &'static str,
     pub alters: Vec<AlterTableSingle>,
 }
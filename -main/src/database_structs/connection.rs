@@ -8,7 +8,7 @@ use easy_macros::always_context;
 use sqlx::{Database, Executor};
 
 use crate::{
-    Driver, EasyExecutor,
+    Driver, EasyExecutor, Transaction,
     traits::{DriverConnection, InternalDriver, SetupSql},
 };
 /// Wrapper around [`sqlx::pool::PoolConnection`](https://docs.rs/sqlx/latest/sqlx/pool/struct.PoolConnection.html)
@@ -49,6 +49,12 @@ where
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         &mut *self.internal
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        Ok(Transaction::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
 }
 
 impl<D: Driver> Deref for Connection<D> {
@@ -0,0 +1,80 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use futures_core::{Stream, stream::BoxStream};
+use sqlx::Executor;
+
+use crate::{Driver, EasyExecutorInto, Output, Table, macro_support, traits::DriverRow};
+
+/// Streams every row of a table as `O` (returned by `copy_out` of the driver modules).
+///
+/// Holds the generated `SELECT` query, use [`fetch`](CopyOut::fetch) to start streaming. Soft
+/// deleted rows are skipped.
+#[derive(Debug)]
+pub struct CopyOut<T, O, D: Driver> {
+    query: String,
+    _data: PhantomData<fn() -> (T, O, D)>,
+}
+
+impl<T: Table<D>, O: Output<T, D, DataToConvert = DriverRow<D>>, D: Driver> CopyOut<T, O, D> {
+    pub fn new() -> Self {
+        let mut query = String::from("SELECT ");
+        O::select(&mut query);
+        query.push_str(&format!(
+            " FROM {}",
+            macro_support::quoted_qualified_table_name::<T, D>()
+        ));
+        T::table_joins(&mut query);
+        let (_, _, soft_delete_where) =
+            macro_support::soft_delete_filter(T::soft_delete_column(), D::identifier_delimiter());
+        query.push_str(&soft_delete_where);
+
+        CopyOut {
+            query,
+            _data: PhantomData,
+        }
+    }
+
+    pub fn fetch<'e, E>(&'e mut self, conn: E) -> impl Stream<Item = anyhow::Result<O>> + 'e
+    where
+        E: EasyExecutorInto<D> + 'e,
+        T: 'e,
+        O: 'e,
+        D: 'e,
+    {
+        OutputStream::<T, O, D> {
+            rows: conn.into_executor().fetch(self.query.as_str()),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T: Table<D>, O: Output<T, D, DataToConvert = DriverRow<D>>, D: Driver> Default
+    for CopyOut<T, O, D>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts fetched rows into `O` one by one
+struct OutputStream<'e, T, O, D: Driver> {
+    rows: BoxStream<'e, Result<DriverRow<D>, sqlx::Error>>,
+    _data: PhantomData<fn() -> (T, O)>,
+}
+
+impl<T: Table<D>, O: Output<T, D, DataToConvert = DriverRow<D>>, D: Driver> Stream
+    for OutputStream<'_, T, O, D>
+{
+    type Item = anyhow::Result<O>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rows
+            .as_mut()
+            .poll_next(cx)
+            .map(|row| row.map(macro_support::query_lazy_row::<T, O, D>))
+    }
+}
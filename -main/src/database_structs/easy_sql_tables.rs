@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use crate::{
     Driver, Output,
-    markers::{HasTable, NotJoinedTable},
+    markers::{HasTable, NotJoinedTable, WritableTable},
     traits::{DriverConnection, InternalDriver},
 };
 use easy_macros::always_context;
@@ -29,6 +29,8 @@ impl HasTable<EasySqlTables> for EasySqlTables {}
 
 impl NotJoinedTable for EasySqlTables {}
 
+impl WritableTable for EasySqlTables {}
+
 #[macro_export]
 #[doc(hidden)]
 /// Used by Table derive macro
@@ -114,11 +116,13 @@ where
     async fn setup(conn: &mut (impl EasyExecutor<D> + Send + Sync)) -> anyhow::Result<()> {
         use anyhow::Context;
 
+        let schema = <EasySqlTables as Table<D>>::schema();
         let table_name = <EasySqlTables as Table<D>>::table_name();
 
         let table_exists = D::table_exists(
             #[context(no)]
             conn,
+            schema,
             table_name,
         )
         .await?;
@@ -127,6 +131,7 @@ where
             D::create_table(
                 #[context(no)]
                 conn,
+                schema,
                 table_name,
                 vec![
                     TableField {
@@ -138,6 +143,7 @@ where
                         is_not_null: true,
                         default: None,
                         is_auto_increment: false,
+                        generated: None,
                     },
                     TableField {
                         name: "version",
@@ -148,11 +154,13 @@ where
                         is_not_null: true,
                         default: None,
                         is_auto_increment: false,
+                        generated: None,
                     },
                 ],
                 vec!["table_id"],
                 #[context(no)]
                 Default::default(),
+                Vec::new(),
             )
             .await?;
         }
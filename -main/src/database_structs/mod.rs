@@ -1,7 +1,21 @@
 mod alter_table;
 mod connection;
+mod copy_out;
 mod easy_sql_tables;
+#[cfg(feature = "json")]
+mod patch;
+mod query_stats;
+mod retry_policy;
+mod table_constraint;
 mod table_field;
+mod table_index;
+mod table_meta;
 mod transaction;
 
-pub use {alter_table::*, connection::*, easy_sql_tables::*, table_field::*, transaction::*};
+#[cfg(feature = "json")]
+pub use patch::*;
+pub use {
+    alter_table::*, connection::*, copy_out::*, easy_sql_tables::*, query_stats::*,
+    retry_policy::*, table_constraint::*, table_field::*, table_index::*, table_meta::*,
+    transaction::*,
+};
@@ -0,0 +1,177 @@
+use std::marker::PhantomData;
+
+use anyhow::Context;
+use easy_macros::always_context;
+
+use crate::{
+    Driver, Table, Update,
+    macro_support::{PatchBind, PatchColumns, PatchValue, invalid_patch},
+    traits::DriverArguments,
+};
+
+/// Update of columns chosen at runtime, for example from a PATCH request body.
+///
+/// Requires `#[sql(patch)]` on the [`Table`](macro@crate::Table) derive. Every key is checked
+/// against the table columns and converted into the column type when it's added, failures return
+/// [`Error::InvalidPatch`](crate::Error::InvalidPatch). Columns filled in by the database
+/// (`#[sql(created_at)]`, `#[sql(updated_at)]`, `#[sql(generated = ...)]`) can't be patched.
+///
+/// Implements [`Update`], use it as `UPDATE TableType SET {patch} WHERE ...`.
+#[derive(Debug, Clone)]
+pub struct Patch<T> {
+    values: Vec<(&'static str, PatchValue)>,
+    _table: PhantomData<fn() -> T>,
+}
+
+impl<T: PatchColumns> Default for Patch<T> {
+    fn default() -> Self {
+        Patch {
+            values: Vec::new(),
+            _table: PhantomData,
+        }
+    }
+}
+
+#[always_context]
+impl<T: PatchColumns> Patch<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Patch from a JSON object, keys are column names
+    pub fn from_json(value: serde_json::Value) -> anyhow::Result<Self> {
+        let serde_json::Value::Object(object) = value else {
+            anyhow::bail!("Patch of table `{}` has to be a JSON object", T::TABLE_NAME);
+        };
+        Self::from_pairs(object)
+    }
+
+    /// Patch from `(column, value)` pairs, for example a `HashMap<String, serde_json::Value>`
+    pub fn from_pairs<K: AsRef<str>, V: serde::Serialize>(
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> anyhow::Result<Self> {
+        let mut patch = Self::new();
+        for (column, value) in pairs {
+            patch.set(column.as_ref(), value)?;
+        }
+        Ok(patch)
+    }
+
+    /// Sets `column` to `value`, replaces the previous value of the column
+    pub fn set(&mut self, column: &str, value: impl serde::Serialize) -> anyhow::Result<&mut Self> {
+        let column = T::patch_column(column)
+            .ok_or_else(|| invalid_patch(T::TABLE_NAME, column, "unknown column"))?;
+        let value = serde_json::to_value(value)
+            .map_err(|error| invalid_patch(T::TABLE_NAME, column, error.to_string()))?;
+        T::check_patch_value(column, &value)?;
+
+        match self.values.iter_mut().find(|(name, _)| *name == column) {
+            Some((_, previous)) => *previous = value,
+            None => self.values.push((column, value)),
+        }
+        Ok(self)
+    }
+
+    /// Fails if the patch sets a column outside of `columns`
+    ///
+    /// Use it when the patch comes from an untrusted source, to keep columns like primary keys
+    /// or ownership unchanged
+    pub fn allow_only(self, columns: &[&str]) -> anyhow::Result<Self> {
+        if let Some((column, _)) = self
+            .values
+            .iter()
+            .find(|(column, _)| !columns.contains(column))
+        {
+            return Err(invalid_patch(
+                T::TABLE_NAME,
+                column,
+                "column is not allowed",
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Columns set by the patch, in the order they were added
+    pub fn columns(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.values.iter().map(|(column, _)| *column)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[always_context]
+impl<'a, T, D: Driver> Update<'a, T, D> for Patch<T>
+where
+    T: Table<D> + PatchBind<'a, D>,
+{
+    fn updates(
+        self,
+        mut args_list: DriverArguments<'a, D>,
+        current_query: &mut String,
+        parameter_n: &mut usize,
+    ) -> anyhow::Result<DriverArguments<'a, D>> {
+        let delimeter = D::identifier_delimiter();
+        let lock_version_column = T::lock_version_column();
+        let current_query_start_len = current_query.len();
+
+        let mut updated_columns = Vec::with_capacity(self.values.len());
+        for (column, value) in self.values {
+            updated_columns.push(column);
+            // Bound by `Update::lock_version` (and incremented below)
+            if lock_version_column == Some(column) {
+                continue;
+            }
+            current_query.push_str(&format!(
+                "{delimeter}{column}{delimeter} = {}, ",
+                D::parameter_placeholder(*parameter_n)
+            ));
+            args_list = T::bind_patch_value(column, value, args_list)
+                .with_context(|| format!("Binding patch column `{column}` to query failed"))?;
+            *parameter_n += 1;
+        }
+
+        if updated_columns.is_empty() {
+            return Err(invalid_patch(
+                T::TABLE_NAME,
+                "",
+                "patch doesn't set any columns",
+            ));
+        }
+
+        for column in T::updated_at_columns() {
+            if !updated_columns.contains(column) {
+                current_query.push_str(&format!(
+                    "{delimeter}{column}{delimeter} = {}, ",
+                    D::current_timestamp()
+                ));
+            }
+        }
+        if let Some(column) = lock_version_column {
+            current_query.push_str(&format!(
+                "{delimeter}{column}{delimeter} = {delimeter}{column}{delimeter} + 1, "
+            ));
+        }
+
+        if current_query.len() >= current_query_start_len + 2 {
+            current_query.pop();
+            current_query.pop();
+        }
+        Ok(args_list)
+    }
+
+    fn lock_version(
+        &self,
+        args_list: DriverArguments<'a, D>,
+        column: &str,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, bool)> {
+        match self.values.iter().find(|(name, _)| *name == column) {
+            Some((column, value)) => {
+                let args_list = T::bind_patch_value(*column, value.clone(), args_list)?;
+                Ok((args_list, true))
+            }
+            None => Ok((args_list, false)),
+        }
+    }
+}
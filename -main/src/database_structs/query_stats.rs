@@ -0,0 +1,44 @@
+use std::future::Future;
+
+use crate::{Driver, traits::DriverQueryResult};
+
+/// Result of `INSERT`, `UPDATE` and `DELETE` queries without `RETURNING`.
+///
+/// Returned by [`query!`](crate::query), statements split into multiple chunks are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueryStats {
+    /// Number of rows changed by the statement
+    pub rows_affected: u64,
+    /// Row id of the last inserted row, for drivers without `RETURNING` support of generated
+    /// keys (SQLite `last_insert_rowid`), `None` for other drivers
+    pub last_insert_id: Option<i64>,
+}
+
+impl QueryStats {
+    pub fn from_result<D: Driver>(result: &DriverQueryResult<D>) -> Self {
+        QueryStats {
+            rows_affected: D::rows_affected(result),
+            last_insert_id: D::last_insert_id(result),
+        }
+    }
+}
+
+/// Shortcuts for reading a single [`QueryStats`] value from a not yet awaited
+/// [`query!`](crate::query) call.
+///
+/// ```rust,ignore
+/// let deleted: u64 = query!(&mut conn, DELETE FROM Users WHERE active = false)
+///     .rows_affected()
+///     .await?;
+/// ```
+pub trait QueryStatsFuture: Future<Output = anyhow::Result<QueryStats>> + Sized {
+    fn rows_affected(self) -> impl Future<Output = anyhow::Result<u64>> {
+        async move { Ok(self.await?.rows_affected) }
+    }
+
+    fn last_insert_id(self) -> impl Future<Output = anyhow::Result<Option<i64>>> {
+        async move { Ok(self.await?.last_insert_id) }
+    }
+}
+
+impl<F: Future<Output = anyhow::Result<QueryStats>>> QueryStatsFuture for F {}
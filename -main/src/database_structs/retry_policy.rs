@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+/// Backoff policy used when retrying transactions that failed with a retryable error.
+///
+/// See `in_transaction_with_retry` of the driver `Database` types and
+/// [`RetryableError::is_retryable`](crate::RetryableError::is_retryable).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: usize,
+    /// Delay before the second attempt
+    pub initial_backoff: Duration,
+    /// Upper limit of the delay between attempts
+    pub max_backoff: Duration,
+    /// Delay multiplier applied after every failed attempt
+    pub multiplier: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after the failed `attempt` (1-based)
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let multiplier = self.multiplier.saturating_pow(exponent);
+        self.initial_backoff
+            .saturating_mul(multiplier)
+            .min(self.max_backoff)
+    }
+}
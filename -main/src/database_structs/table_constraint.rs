@@ -0,0 +1,30 @@
+/// Table level constraint used by drivers to build tables and migrations.
+///
+/// Generated by the [`Table`](macro@crate::Table) macro from `#[sql(check = ...)]` and
+/// `#[sql(unique(...))]` attributes, rendered into
+/// [`Driver::create_table`](crate::Driver::create_table) and changed with
+/// [`AlterTableSingle::AddConstraint`](crate::driver::AlterTableSingle::AddConstraint) /
+/// [`AlterTableSingle::DropConstraint`](crate::driver::AlterTableSingle::DropConstraint).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TableConstraint {
+    /// `CHECK` constraint, `expression` is already rendered for the driver.
+    Check {
+        name: &'static str,
+        expression: String,
+    },
+    /// Composite `UNIQUE` constraint over `columns`.
+    Unique {
+        name: &'static str,
+        columns: Vec<&'static str>,
+    },
+}
+
+impl TableConstraint {
+    /// Constraint name as it should appear in SQL.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableConstraint::Check { name, .. } | TableConstraint::Unique { name, .. } => name,
+        }
+    }
+}
@@ -18,4 +18,16 @@ pub struct TableField {
     pub default: Option<String>,
     /// Whether the column should auto-increment (driver-specific behavior).
     pub is_auto_increment: bool,
+    /// Expression computing the column value (`GENERATED ALWAYS AS`).
+    pub generated: Option<GeneratedColumn>,
+}
+
+/// Generated (computed) column definition, see [`TableField::generated`].
+#[derive(Debug)]
+pub struct GeneratedColumn {
+    /// SQL expression the column value is computed from.
+    pub expression: String,
+    /// `STORED` (computed on write) instead of `VIRTUAL` (computed on read). Postgres columns are
+    /// always `STORED`.
+    pub stored: bool,
 }
@@ -0,0 +1,16 @@
+/// Index definition used by drivers to build tables and migrations.
+///
+/// Generated by the [`Table`](macro@crate::Table) macro from `#[sql(index)]` attributes and
+/// created with [`AlterTableSingle::CreateIndex`](crate::driver::AlterTableSingle::CreateIndex)
+/// after [`Driver::create_table`](crate::Driver::create_table).
+#[derive(Debug)]
+pub struct TableIndex {
+    /// Index name as it should appear in SQL.
+    pub name: &'static str,
+    /// Indexed column names, in order.
+    pub columns: Vec<&'static str>,
+    /// Whether to create an `UNIQUE` index.
+    pub is_unique: bool,
+    /// Optional SQL predicate of a partial index (`WHERE ...`).
+    pub where_clause: Option<String>,
+}
@@ -0,0 +1,62 @@
+/// Runtime description of a table, see [`TableMetadata`](crate::TableMetadata).
+///
+/// Generated by the [`Table`](macro@crate::Table) macro for every supported driver.
+#[derive(Debug, Clone)]
+pub struct TableMeta {
+    /// Table name as it appears in SQL.
+    pub name: &'static str,
+    /// Schema or attached database the table lives in, see [`Table::schema`](crate::Table::schema).
+    pub schema: Option<&'static str>,
+    /// Primary key column names, in order.
+    pub primary_keys: Vec<&'static str>,
+    /// Columns in the order of the struct fields.
+    pub columns: Vec<ColumnMeta>,
+}
+
+impl TableMeta {
+    /// Columns in the order of the struct fields.
+    pub fn columns(&self) -> &[ColumnMeta] {
+        &self.columns
+    }
+
+    /// Column with the given SQL name, `None` if the table has no such column.
+    pub fn column(&self, name: &str) -> Option<&ColumnMeta> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+/// Runtime description of a table column, see [`TableMeta::columns`].
+#[derive(Debug, Clone)]
+pub struct ColumnMeta {
+    /// Column name as it appears in SQL.
+    pub name: &'static str,
+    /// Rust type of the struct field (as returned by [`std::any::type_name`]).
+    pub rust_type: &'static str,
+    /// Database-specific type name (e.g. `TEXT`, `INTEGER`, `UUID`).
+    pub sql_type: String,
+    /// Whether the column accepts `NULL` (`Option<T>` fields).
+    pub nullable: bool,
+    /// Whether the column has its own `UNIQUE` constraint (composite constraints aren't included).
+    pub unique: bool,
+    /// Whether the column is part of the primary key (see [`TableMeta::primary_keys`]).
+    pub primary_key: bool,
+    /// Whether the value is assigned by the database (`#[sql(auto_increment)]`).
+    pub auto_increment: bool,
+    /// SQL literal used as `DEFAULT` value.
+    pub default: Option<String>,
+    /// Whether the value is computed by the database (`#[sql(generated = ...)]`).
+    pub generated: bool,
+    /// Columns referenced by `#[sql(foreign_key = ...)]` attributes of the field.
+    pub foreign_keys: Vec<ForeignKeyMeta>,
+}
+
+/// Column referenced by a foreign key, see [`ColumnMeta::foreign_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyMeta {
+    /// Referenced table name.
+    pub table: &'static str,
+    /// Referenced (primary key) column name.
+    pub column: &'static str,
+    /// Whether `ON DELETE/UPDATE CASCADE` is enabled.
+    pub cascade: bool,
+}
@@ -32,6 +32,23 @@ impl<'a, D: Driver> Transaction<'a, D> {
         self.internal.rollback().await?;
         Ok(())
     }
+
+    /// Creates a savepoint, rolled back when dropped without [`Savepoint::release`]
+    pub async fn savepoint(&mut self) -> anyhow::Result<Savepoint<'_, D>> {
+        Ok(Savepoint::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
+
+    /// Runs `f` inside of a new savepoint, released on `Ok` and rolled back on `Err`
+    #[no_context_inputs]
+    pub async fn in_transaction<R>(
+        &mut self,
+        f: impl AsyncFnOnce(&mut Savepoint<'_, D>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let savepoint = self.savepoint().await?;
+        run_in_transaction(savepoint, f).await
+    }
 }
 
 #[always_context]
@@ -56,6 +73,12 @@ where
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         &mut *self.internal
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        Ok(Transaction::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
 }
 impl<'c, D: Driver> Deref for Transaction<'c, D> {
     type Target = <InternalDriver<D> as Database>::Connection;
@@ -95,6 +118,23 @@ impl<D: Driver> PoolTransaction<D> {
         self.internal.rollback().await?;
         Ok(())
     }
+
+    /// Creates a savepoint, rolled back when dropped without [`Savepoint::release`]
+    pub async fn savepoint(&mut self) -> anyhow::Result<Savepoint<'_, D>> {
+        Ok(Savepoint::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
+
+    /// Runs `f` inside of a new savepoint, released on `Ok` and rolled back on `Err`
+    #[no_context_inputs]
+    pub async fn in_transaction<R>(
+        &mut self,
+        f: impl AsyncFnOnce(&mut Savepoint<'_, D>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let savepoint = self.savepoint().await?;
+        run_in_transaction(savepoint, f).await
+    }
 }
 
 #[always_context]
@@ -119,6 +159,12 @@ where
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         &mut *self.internal
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        Ok(Transaction::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
 }
 impl<D: Driver> Deref for PoolTransaction<D> {
     type Target = <InternalDriver<D> as Database>::Connection;
@@ -133,3 +179,138 @@ impl<D: Driver> DerefMut for PoolTransaction<D> {
         &mut self.internal
     }
 }
+/// Savepoint inside of a [`Transaction`], [`PoolTransaction`] or another savepoint
+///
+/// Rolled back when dropped without calling [`release`](Savepoint::release).
+#[derive(Debug)]
+pub struct Savepoint<'a, D: Driver> {
+    internal: sqlx::Transaction<'a, D::InternalDriver>,
+}
+
+#[always_context]
+impl<'a, D: Driver> Savepoint<'a, D> {
+    pub fn new(internal: sqlx::Transaction<'a, D::InternalDriver>) -> Self {
+        Savepoint { internal }
+    }
+
+    /// Keeps changes made since the savepoint was created (`RELEASE SAVEPOINT`)
+    pub async fn release(self) -> anyhow::Result<()> {
+        self.internal.commit().await?;
+        Ok(())
+    }
+
+    /// Reverts changes made since the savepoint was created (`ROLLBACK TO SAVEPOINT`)
+    pub async fn rollback_to(self) -> anyhow::Result<()> {
+        self.internal.rollback().await?;
+        Ok(())
+    }
+
+    /// Creates a nested savepoint, rolled back when dropped without [`Savepoint::release`]
+    pub async fn savepoint(&mut self) -> anyhow::Result<Savepoint<'_, D>> {
+        Ok(Savepoint::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
+
+    /// Runs `f` inside of a nested savepoint, released on `Ok` and rolled back on `Err`
+    #[no_context_inputs]
+    pub async fn in_transaction<R>(
+        &mut self,
+        f: impl AsyncFnOnce(&mut Savepoint<'_, D>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let savepoint = self.savepoint().await?;
+        run_in_transaction(savepoint, f).await
+    }
+}
+
+/// Transaction or savepoint finished by [`run_in_transaction`]
+pub(crate) trait TransactionScope: Sized {
+    /// Commit (transaction) or release (savepoint)
+    async fn finish(self) -> anyhow::Result<()>;
+
+    async fn abort(self) -> anyhow::Result<()>;
+}
+
+impl<D: Driver> TransactionScope for PoolTransaction<D> {
+    async fn finish(self) -> anyhow::Result<()> {
+        self.commit().await
+    }
+
+    async fn abort(self) -> anyhow::Result<()> {
+        self.rollback().await
+    }
+}
+
+impl<D: Driver> TransactionScope for Savepoint<'_, D> {
+    async fn finish(self) -> anyhow::Result<()> {
+        self.release().await
+    }
+
+    async fn abort(self) -> anyhow::Result<()> {
+        self.rollback_to().await
+    }
+}
+
+/// Runs `f` inside of `scope`, finished on `Ok` and rolled back on `Err`
+///
+/// A failed rollback doesn't replace the error returned by `f`, it's added as its context.
+#[always_context]
+#[no_context_inputs]
+pub(crate) async fn run_in_transaction<T: TransactionScope, R>(
+    mut scope: T,
+    f: impl AsyncFnOnce(&mut T) -> anyhow::Result<R>,
+) -> anyhow::Result<R> {
+    match f(&mut scope).await {
+        Ok(result) => {
+            scope.finish().await?;
+            Ok(result)
+        }
+        Err(err) => match scope.abort().await {
+            Ok(()) => Err(err),
+            Err(rollback_err) => Err(err.context(format!("Rollback failed: {rollback_err:?}"))),
+        },
+    }
+}
+
+#[always_context]
+impl<'c, D: Driver> EasyExecutor<D> for Savepoint<'c, D>
+where
+    for<'b> &'b mut DriverConnection<D>: sqlx::Executor<'b, Database = D::InternalDriver>,
+{
+    type InternalExecutor<'b>
+        = &'b mut DriverConnection<D>
+    where
+        Self: 'b;
+    async fn query_setup<O: SetupSql<D> + Send + Sync>(
+        &mut self,
+        sql: O,
+    ) -> anyhow::Result<O::Output>
+    where
+        DriverConnection<D>: Send + Sync,
+    {
+        sql.query(self).await
+    }
+
+    fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
+        &mut *self.internal
+    }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        Ok(Transaction::new(
+            sqlx::Connection::begin(&mut *self.internal).await?,
+        ))
+    }
+}
+impl<'c, D: Driver> Deref for Savepoint<'c, D> {
+    type Target = <InternalDriver<D> as Database>::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'c, D: Driver> DerefMut for Savepoint<'c, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.internal
+    }
+}
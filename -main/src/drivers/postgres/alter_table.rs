@@ -1,7 +1,7 @@
 use anyhow::Context;
 use easy_macros::{always_context, context};
 
-use super::{Postgres, table_field_definition};
+use super::{Postgres, qualified_name, table_constraint_definition, table_field_definition};
 use crate::{
     EasyExecutor,
     driver::{AlterTable, AlterTableSingle},
@@ -14,14 +14,12 @@ impl SetupSql<Postgres> for AlterTable {
 
     async fn query(self, exec: &mut impl EasyExecutor<Postgres>) -> anyhow::Result<Self::Output> {
         let mut queries_done = Vec::new();
+        let table = qualified_name(self.schema, self.table_name);
 
         for alter in self.alters {
             match alter {
                 AlterTableSingle::RenameTable { new_table_name } => {
-                    let query = format!(
-                        "ALTER TABLE \"{}\" RENAME TO \"{}\"",
-                        self.table_name, new_table_name
-                    );
+                    let query = format!("ALTER TABLE {table} RENAME TO \"{new_table_name}\"");
 
                     #[no_context]
                     sqlx::query(&query)
@@ -39,10 +37,7 @@ impl SetupSql<Postgres> for AlterTable {
                 AlterTableSingle::AddColumn { column } => {
                     let column_def = table_field_definition(column);
                     let column_def = column_def.trim_end_matches(',').trim_end();
-                    let query = format!(
-                        "ALTER TABLE \"{}\" ADD COLUMN {}",
-                        self.table_name, column_def
-                    );
+                    let query = format!("ALTER TABLE {table} ADD COLUMN {column_def}");
 
                     #[no_context]
                     sqlx::query(&query)
@@ -62,10 +57,89 @@ impl SetupSql<Postgres> for AlterTable {
                     new_column_name,
                 } => {
                     let query = format!(
-                        "ALTER TABLE \"{}\" RENAME COLUMN \"{}\" TO \"{}\"",
-                        self.table_name, old_column_name, new_column_name
+                        "ALTER TABLE {table} RENAME COLUMN \"{old_column_name}\" TO \"{new_column_name}\""
                     );
 
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                AlterTableSingle::CreateIndex { index } => {
+                    let unique = if index.is_unique { "UNIQUE " } else { "" };
+                    let columns = index
+                        .columns
+                        .iter()
+                        .map(|column| format!("\"{column}\""))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    // Index is created in the schema of its table
+                    let mut query = format!(
+                        "CREATE {unique}INDEX \"{}\" ON {table} ({columns})",
+                        index.name
+                    );
+                    if let Some(where_clause) = index.where_clause {
+                        query.push_str(&format!(" WHERE {where_clause}"));
+                    }
+
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                AlterTableSingle::DropIndex { name } => {
+                    let query = format!("DROP INDEX {}", qualified_name(self.schema, name));
+
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                AlterTableSingle::AddConstraint { constraint } => {
+                    let constraint_def = table_constraint_definition(constraint);
+                    let constraint_def = constraint_def.trim_end_matches(',');
+                    let query = format!("ALTER TABLE {table} ADD {constraint_def}");
+
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                AlterTableSingle::DropConstraint { name } => {
+                    let query = format!("ALTER TABLE {table} DROP CONSTRAINT \"{name}\"");
+
                     #[no_context]
                     sqlx::query(&query)
                         .execute(exec.executor())
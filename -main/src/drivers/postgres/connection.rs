@@ -3,7 +3,7 @@ use sqlx::{PgConnection, Pool};
 
 use super::Db;
 use crate::{
-    EasyExecutor, EasyExecutorInto,
+    EasyExecutor, EasyExecutorInto, Transaction,
     traits::{DriverConnection, SetupSql},
 };
 
@@ -31,6 +31,10 @@ impl EasyExecutor<CDriver> for &Pool<Db> {
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         self
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, CDriver>> {
+        Ok(Transaction::new(self.begin().await?))
+    }
 }
 
 impl EasyExecutorInto<CDriver> for &Pool<Db> {
@@ -67,6 +71,12 @@ impl EasyExecutor<CDriver> for &mut Connection {
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         self
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, CDriver>> {
+        Ok(Transaction::new(
+            sqlx::Connection::begin(&mut **self).await?,
+        ))
+    }
 }
 
 impl EasyExecutorInto<CDriver> for &mut Connection {
@@ -0,0 +1,137 @@
+use anyhow::Context;
+use easy_macros::always_context;
+use sqlx::{Encode, Type, encode::IsNull, postgres::PgArgumentBuffer};
+
+use super::Postgres;
+use crate::{
+    CopyOut, Driver, EasyExecutor, Insert, Output, Table, macro_support,
+    markers::BindsAllColumns,
+    traits::{DriverRow, InsertVisitor, InternalDriver},
+};
+
+/// Size of buffered `COPY` data sent to the database at once
+const COPY_SEND_SIZE: usize = 1024 * 1024;
+
+const COPY_BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Encodes visited values into the `COPY ... (FORMAT binary)` tuple format
+struct CopyBinaryWriter {
+    data: Vec<u8>,
+    row: Vec<u8>,
+    row_fields: i16,
+}
+
+#[always_context]
+impl CopyBinaryWriter {
+    fn new() -> Self {
+        let mut data = Vec::with_capacity(COPY_SEND_SIZE);
+        data.extend_from_slice(COPY_BINARY_HEADER);
+        // Flags
+        data.extend_from_slice(&0i32.to_be_bytes());
+        // Header extension length
+        data.extend_from_slice(&0i32.to_be_bytes());
+
+        CopyBinaryWriter {
+            data,
+            row: Vec::new(),
+            row_fields: 0,
+        }
+    }
+
+    fn take_data(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.data)
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        // File trailer
+        self.data.extend_from_slice(&(-1i16).to_be_bytes());
+        self.data
+    }
+}
+
+#[always_context]
+impl<'a> InsertVisitor<'a, Postgres> for CopyBinaryWriter {
+    fn value<T>(&mut self, value: T) -> anyhow::Result<()>
+    where
+        T: Encode<'a, InternalDriver<Postgres>> + Type<InternalDriver<Postgres>> + 'a,
+    {
+        let mut buf = PgArgumentBuffer::default();
+        let is_null = value
+            .encode_by_ref(&mut buf)
+            .map_err(anyhow::Error::from_boxed)
+            .context("Failed to encode value for COPY")?;
+
+        match is_null {
+            IsNull::Yes => self.row.extend_from_slice(&(-1i32).to_be_bytes()),
+            IsNull::No => {
+                let len = i32::try_from(buf.len()).context("COPY value is too large")?;
+                self.row.extend_from_slice(&len.to_be_bytes());
+                self.row.extend_from_slice(&buf);
+            }
+        }
+        self.row_fields += 1;
+        Ok(())
+    }
+
+    fn end_row(&mut self) -> anyhow::Result<()> {
+        self.data.extend_from_slice(&self.row_fields.to_be_bytes());
+        self.data.append(&mut self.row);
+        self.row_fields = 0;
+        Ok(())
+    }
+}
+
+/// Bulk loads `rows` into table `T` with `COPY ... FROM STDIN (FORMAT binary)`.
+///
+/// Column list comes from [`Insert::insert_columns`], all rows are loaded inside of a single
+/// transaction (savepoint if `conn` is already inside of one). Returns number of copied rows.
+///
+/// `I` needs [`Insert::visit_values`], implemented by the [`Insert`](macro@crate::Insert) derive
+/// for types binding every column ([`BindsAllColumns`]).
+#[always_context]
+#[no_context_inputs]
+pub async fn copy_in<'a, T: Table<Postgres>, I: Insert<'a, T, Postgres> + BindsAllColumns>(
+    conn: &mut impl EasyExecutor<Postgres>,
+    rows: impl IntoIterator<Item = I>,
+) -> anyhow::Result<u64> {
+    let delimiter = Postgres::identifier_delimiter();
+    let columns = I::insert_columns()
+        .iter()
+        .map(|column| format!("{delimiter}{column}{delimiter}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let statement = format!(
+        "COPY {} ({columns}) FROM STDIN (FORMAT binary)",
+        macro_support::quoted_qualified_table_name::<T, Postgres>()
+    );
+
+    let mut transaction = conn.transaction().await?;
+    let mut copy = transaction.copy_in_raw(&statement).await?;
+
+    let mut writer = CopyBinaryWriter::new();
+    for row in rows {
+        if let Err(err) = row.visit_values(&mut writer) {
+            copy.abort("easy_sql: failed to encode COPY row").await?;
+            return Err(err);
+        }
+        if writer.data.len() >= COPY_SEND_SIZE {
+            copy.send(writer.take_data()).await?;
+        }
+    }
+    copy.send(writer.finish()).await?;
+
+    let copied = copy.finish().await?;
+    transaction.commit().await?;
+
+    Ok(copied)
+}
+
+/// Streams every row of table `T` as `O`, the reading counterpart of [`copy_in`]. Soft deleted
+/// rows are skipped.
+///
+/// Runs a regular `SELECT` instead of `COPY ... TO STDOUT`, as `COPY` output can't be decoded
+/// into sqlx rows.
+pub fn copy_out<T: Table<Postgres>, O: Output<T, Postgres, DataToConvert = DriverRow<Postgres>>>()
+-> CopyOut<T, O, Postgres> {
+    CopyOut::new()
+}
@@ -3,21 +3,34 @@ use std::collections::HashMap;
 use anyhow::Context;
 use easy_macros::{always_context, context};
 
-use super::{Postgres, table_field_definition};
+use super::{
+    Postgres, qualified_name, quoted_identifier, table_constraint_definition,
+    table_field_definition,
+};
 use crate::EasyExecutor;
 use crate::traits::SetupSql;
 
-use crate::driver::TableField;
+use crate::driver::{TableConstraint, TableField};
 
 #[derive(Debug)]
 pub struct CreateTable {
+    pub schema: Option<&'static str>,
     pub table_name: &'static str,
     pub fields: Vec<TableField>,
 
     pub primary_keys: Vec<&'static str>,
     ///Key - table name
-    ///Value - field names, foreign field names, on delete/update cascade
-    pub foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+    ///Value - table schema, field names, foreign field names, on delete/update cascade
+    pub foreign_keys: HashMap<
+        &'static str,
+        (
+            Option<&'static str>,
+            Vec<&'static str>,
+            Vec<&'static str>,
+            bool,
+        ),
+    >,
+    pub constraints: Vec<TableConstraint>,
 }
 
 #[always_context]
@@ -42,7 +55,9 @@ impl SetupSql<Postgres> for CreateTable {
         table_constrains.push_str(&format!("PRIMARY KEY ({}),", formatted_keys.join(", ")));
 
         //Foreign key constraints
-        for (foreign_table, (referenced_fields, foreign_fields, cascade)) in self.foreign_keys {
+        for (foreign_table, (foreign_schema, referenced_fields, foreign_fields, cascade)) in
+            self.foreign_keys
+        {
             let referenced_fields: Vec<String> = referenced_fields
                 .iter()
                 .map(|field| format!("\"{}\"", field))
@@ -55,11 +70,17 @@ impl SetupSql<Postgres> for CreateTable {
             let foreign_fields = foreign_fields.join(", ");
             let on_delete = if cascade { "ON DELETE CASCADE" } else { "" };
             let on_update = if cascade { "ON UPDATE CASCADE" } else { "" };
+            let foreign_table = qualified_name(foreign_schema, foreign_table);
             table_constrains.push_str(&format!(
-                "FOREIGN KEY ({referenced_fields}) REFERENCES \"{foreign_table}\"({foreign_fields}) {on_delete} {on_update},"
+                "FOREIGN KEY ({referenced_fields}) REFERENCES {foreign_table}({foreign_fields}) {on_delete} {on_update},"
             ));
         }
 
+        //Check and other table constraints
+        for constraint in self.constraints {
+            table_constrains.push_str(&table_constraint_definition(constraint));
+        }
+
         if table_constrains.is_empty() && !table_fields.is_empty() {
             //Removes last ,
             table_fields.pop();
@@ -70,9 +91,25 @@ impl SetupSql<Postgres> for CreateTable {
             table_constrains.pop();
         }
 
+        if let Some(schema) = self.schema {
+            let query = format!("CREATE SCHEMA IF NOT EXISTS {}", quoted_identifier(schema));
+
+            #[no_context]
+            sqlx::query(&query)
+                .execute(exec.executor())
+                .await
+                .with_context(context!(
+                    "table_name: {:?} | query: {:?}",
+                    self.table_name,
+                    query
+                ))?;
+        }
+
         let query = format!(
-            "CREATE TABLE \"{}\" (\r\n{}\r\n{})",
-            self.table_name, table_fields, table_constrains
+            "CREATE TABLE {} (\r\n{}\r\n{})",
+            qualified_name(self.schema, self.table_name),
+            table_fields,
+            table_constrains
         );
 
         let sqlx_query = sqlx::query(&query);
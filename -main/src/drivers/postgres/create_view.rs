@@ -0,0 +1,46 @@
+use anyhow::Context;
+use easy_macros::{always_context, context};
+
+use super::{Postgres, qualified_name};
+use crate::traits::{EasyExecutor, SetupSql};
+
+/// Drops the view (if it exists) and creates it again
+#[derive(Debug)]
+pub struct CreateView {
+    pub schema: Option<&'static str>,
+    pub view_name: &'static str,
+    pub columns: Vec<&'static str>,
+    pub query: String,
+}
+
+#[always_context]
+impl SetupSql<Postgres> for CreateView {
+    type Output = ();
+
+    async fn query(self, exec: &mut impl EasyExecutor<Postgres>) -> anyhow::Result<Self::Output> {
+        let view = qualified_name(self.schema, self.view_name);
+        let queries = [
+            format!("DROP VIEW IF EXISTS {}", view),
+            format!(
+                "CREATE VIEW {} ({}) AS {}",
+                view,
+                self.columns.join(", "),
+                self.query
+            ),
+        ];
+
+        for query in queries {
+            #[no_context]
+            sqlx::query(&query)
+                .execute(exec.executor())
+                .await
+                .with_context(context!(
+                    "view_name: {:?} | query: {:?}",
+                    self.view_name,
+                    query
+                ))?;
+        }
+
+        Ok(())
+    }
+}
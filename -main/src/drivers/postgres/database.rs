@@ -1,13 +1,18 @@
+use std::time::Duration;
+
 use anyhow::Context;
-use easy_macros::always_context;
+use easy_macros::{always_context, context};
 
-use crate::{Connection, DatabaseSetup, EasySqlTables, PoolTransaction};
+use crate::{
+    Connection, DatabaseSetup, EasyExecutor, EasySqlTables, PoolTransaction, RetryPolicy,
+    RetryableError, database_structs::run_in_transaction,
+};
 
 use super::Db;
 
 pub use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
-use super::Postgres;
+use super::{Postgres, quoted_identifier};
 
 /// PostgreSQL connection pool wrapper with setup helpers.
 ///
@@ -43,6 +48,33 @@ impl Database {
         Ok(Database { connection_pool })
     }
 
+    /// Like [`Database::setup_with_options`], tables without `#[sql(schema = ...)]` (and the
+    /// `easy_sql_tables` version tracking table) are placed in `schema`, created when missing
+    ///
+    /// `schema` is set as the `search_path` of every connection of the pool, so it also applies to
+    /// [`query_lazy!`](crate::query_lazy) and connections used outside of this crate.
+    pub async fn setup_with_default_schema<T: DatabaseSetup<Postgres>>(
+        options: PgConnectOptions,
+        schema: &str,
+    ) -> anyhow::Result<Self> {
+        let options = options.options([("search_path", schema)]);
+        let connection_pool = sqlx::Pool::<Db>::connect_with(options).await?;
+
+        let mut conn = Connection::new(connection_pool.acquire().await?);
+
+        let query = format!("CREATE SCHEMA IF NOT EXISTS {}", quoted_identifier(schema));
+        #[no_context]
+        sqlx::query(&query)
+            .execute(conn.executor())
+            .await
+            .with_context(context!("schema: {:?} | query: {:?}", schema, query))?;
+
+        EasySqlTables::setup(&mut &mut conn).await?;
+        T::setup(&mut &mut conn).await?;
+
+        Ok(Database { connection_pool })
+    }
+
     pub async fn conn(&self) -> anyhow::Result<Connection<Postgres>> {
         let conn = self.connection_pool.acquire().await?;
         Ok(Connection::new(conn))
@@ -53,8 +85,51 @@ impl Database {
         Ok(PoolTransaction::new(conn))
     }
 
+    /// Runs `f` inside of a new transaction, committed on `Ok` and rolled back on `Err`
+    ///
+    /// Nested calls (`in_transaction` of the provided transaction) are mapped to savepoints.
+    #[no_context_inputs]
+    pub async fn in_transaction<R>(
+        &self,
+        f: impl AsyncFnOnce(&mut PoolTransaction<Postgres>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let transaction = self.transaction().await?;
+        run_in_transaction(transaction, f).await
+    }
+
+    /// Runs `f` inside of a new transaction, retried according to `policy` when it fails with a
+    /// [retryable](crate::RetryableError::is_retryable) error
+    ///
+    /// `sleep` waits between attempts, pass the sleep function of the used async runtime (like
+    /// `tokio::time::sleep`). `f` can be called multiple times, it shouldn't have side effects
+    /// outside of the database.
+    #[no_context_inputs]
+    pub async fn in_transaction_with_retry<R>(
+        &self,
+        policy: &RetryPolicy,
+        sleep: impl AsyncFn(Duration),
+        mut f: impl AsyncFnMut(&mut PoolTransaction<Postgres>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.in_transaction(&mut f).await {
+                Err(err) if attempt < policy.max_attempts && err.is_retryable::<Postgres>() => {
+                    sleep(policy.backoff(attempt)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
     #[cfg(test)]
     pub async fn setup_for_testing<T: DatabaseSetup<Postgres>>() -> anyhow::Result<Self> {
+        Self::setup_with_options::<T>(Self::test_connect_options().await?).await
+    }
+
+    /// Options connecting to a new (empty) test database
+    #[cfg(test)]
+    pub async fn test_connect_options() -> anyhow::Result<PgConnectOptions> {
         use tokio::sync::Mutex;
 
         use crate::tests::init_test_logger;
@@ -113,22 +188,11 @@ impl Database {
 
         maintenance_pool.close().await;
 
-        // Connect to the test database
-        let connection_pool = sqlx::Pool::<Db>::connect_with(
-            PgConnectOptions::new()
-                .host(&host)
-                .port(port)
-                .username(&username)
-                .password(&password)
-                .database(&test_database),
-        )
-        .await?;
-
-        let mut conn = Connection::new(connection_pool.acquire().await?);
-
-        EasySqlTables::setup(&mut &mut conn).await?;
-        T::setup(&mut &mut conn).await?;
-
-        Ok(Database { connection_pool })
+        Ok(PgConnectOptions::new()
+            .host(&host)
+            .port(port)
+            .username(&username)
+            .password(&password)
+            .database(&test_database))
     }
 }
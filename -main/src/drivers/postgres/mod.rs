@@ -1,9 +1,15 @@
 mod alter_table;
+mod copy;
 mod create_table;
+mod create_view;
 mod database;
+mod native_enum;
 mod table_exists;
+pub use copy::{copy_in, copy_out};
 use create_table::CreateTable;
+use create_view::CreateView;
 pub use database::*;
+use native_enum::SetupNativeEnum;
 use table_exists::TableExists;
 
 mod connection;
@@ -17,9 +23,10 @@ use easy_macros::always_context;
 
 use crate::{
     Driver, EasyExecutor,
-    driver::TableField,
+    driver::{DriverQueryResult, DriverTypeInfo, GeneratedColumn, TableConstraint, TableField},
     markers::{
-        AllowsNoPrimaryKey, SupportsAutoIncrementCompositePrimaryKey,
+        AllowsNoPrimaryKey, SupportsAlterConstraint, SupportsAutoIncrementCompositePrimaryKey,
+        SupportsNativeEnum,
         operators::{
             SupportsAdd, SupportsAnd, SupportsBetween, SupportsBitAnd, SupportsBitOr,
             SupportsBitShiftLeft, SupportsBitShiftRight, SupportsConcatOperator, SupportsDiv,
@@ -51,11 +58,28 @@ impl Driver for Postgres {
         format!("${}", index + 1)
     }
 
+    fn max_bind_parameters() -> usize {
+        65_535
+    }
+
+    fn rows_affected(result: &DriverQueryResult<Self>) -> u64 {
+        result.rows_affected()
+    }
+
+    fn is_retryable_error(error: &(dyn sqlx::error::DatabaseError + 'static)) -> bool {
+        let Some(error) = error.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() else {
+            return false;
+        };
+        // serialization_failure, deadlock_detected
+        matches!(error.code(), "40001" | "40P01")
+    }
+
     async fn table_exists(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         name: &'static str,
     ) -> anyhow::Result<bool> {
-        let result = conn.query_setup(TableExists { name }).await?;
+        let result = conn.query_setup(TableExists { schema, name }).await?;
         Ok(result)
     }
 
@@ -63,20 +87,50 @@ impl Driver for Postgres {
     ///
     /// `foreign_keys` - Key - table name
     ///
-    /// `foreign_keys` - Value - field names, foreign field names, on delete/update cascade
+    /// `foreign_keys` - Value - foreign table schema, field names, foreign field names, on delete/update cascade
     #[no_context_inputs]
     async fn create_table(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         table_name: &'static str,
         fields: Vec<TableField>,
         primary_keys: Vec<&'static str>,
-        foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+        foreign_keys: HashMap<
+            &'static str,
+            (
+                Option<&'static str>,
+                Vec<&'static str>,
+                Vec<&'static str>,
+                bool,
+            ),
+        >,
+        constraints: Vec<TableConstraint>,
     ) -> anyhow::Result<()> {
         conn.query_setup(CreateTable {
+            schema,
             table_name,
             fields,
             primary_keys,
             foreign_keys,
+            constraints,
+        })
+        .await?;
+        Ok(())
+    }
+
+    #[no_context_inputs]
+    async fn create_view(
+        conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
+        view_name: &'static str,
+        columns: Vec<&'static str>,
+        query: String,
+    ) -> anyhow::Result<()> {
+        conn.query_setup(CreateView {
+            schema,
+            view_name,
+            columns,
+            query,
         })
         .await?;
         Ok(())
@@ -89,6 +143,29 @@ impl AllowsNoPrimaryKey for Postgres {}
 #[always_context]
 impl SupportsAutoIncrementCompositePrimaryKey for Postgres {}
 
+#[always_context]
+impl SupportsAlterConstraint for Postgres {}
+
+#[always_context]
+impl SupportsNativeEnum for Postgres {
+    fn native_enum_type_info(type_name: &'static str) -> DriverTypeInfo<Self> {
+        sqlx::postgres::PgTypeInfo::with_name(type_name)
+    }
+
+    async fn setup_native_enum(
+        conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        type_name: &'static str,
+        variants: Vec<&'static str>,
+    ) -> anyhow::Result<()> {
+        conn.query_setup(SetupNativeEnum {
+            type_name,
+            variants,
+        })
+        .await?;
+        Ok(())
+    }
+}
+
 impl_supports_fn!(Postgres, SupportsCount, 0, 1);
 impl_supports_fn!(Postgres, SupportsSum, 1);
 impl_supports_fn!(Postgres, SupportsAvg, 1);
@@ -152,6 +229,23 @@ impl SupportsIsNotNull for Postgres {}
 impl SupportsIn for Postgres {}
 impl SupportsBetween for Postgres {}
 
+/// Identifier in delimiters, delimiters inside of it are doubled
+fn quoted_identifier(name: &str) -> String {
+    let delimiter = Postgres::identifier_delimiter();
+    format!(
+        "{delimiter}{}{delimiter}",
+        name.replace(delimiter, &delimiter.repeat(2))
+    )
+}
+
+/// Table (or index) name prefixed with the schema, used in DDL statements
+fn qualified_name(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quoted_identifier(schema), quoted_identifier(name)),
+        None => quoted_identifier(name),
+    }
+}
+
 fn table_field_definition(field: TableField) -> String {
     let TableField {
         name,
@@ -160,6 +254,7 @@ fn table_field_definition(field: TableField) -> String {
         is_not_null,
         default,
         is_auto_increment,
+        generated,
     } = field;
 
     let unique = if is_unique { "UNIQUE" } else { "" };
@@ -193,8 +288,35 @@ fn table_field_definition(field: TableField) -> String {
         data_type
     };
 
+    let generated = generated_definition(generated);
+
     format!(
-        "\"{}\" {} {} {} {},",
-        name, data_type, unique, not_null, default
+        "\"{}\" {} {} {} {} {},",
+        name, data_type, generated, unique, not_null, default
     )
 }
+
+fn generated_definition(generated: Option<GeneratedColumn>) -> String {
+    match generated {
+        // VIRTUAL generated columns require Postgres 18, always STORED
+        Some(GeneratedColumn { expression, .. }) => {
+            format!("GENERATED ALWAYS AS ({expression}) STORED")
+        }
+        None => String::new(),
+    }
+}
+
+fn table_constraint_definition(constraint: TableConstraint) -> String {
+    match constraint {
+        TableConstraint::Check { name, expression } => {
+            format!("CONSTRAINT \"{name}\" CHECK ({expression}),")
+        }
+        TableConstraint::Unique { name, columns } => {
+            let columns: Vec<String> = columns
+                .iter()
+                .map(|column| format!("\"{}\"", column))
+                .collect();
+            format!("CONSTRAINT \"{name}\" UNIQUE ({}),", columns.join(", "))
+        }
+    }
+}
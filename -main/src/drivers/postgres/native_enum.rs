@@ -0,0 +1,83 @@
+use anyhow::Context;
+use easy_macros::{always_context, context};
+use sqlx::Row;
+
+use super::Postgres;
+use crate::{EasyExecutor, traits::SetupSql};
+
+/// Creates `CREATE TYPE ... AS ENUM` type, or adds variants missing in the existing type
+#[derive(Debug)]
+pub struct SetupNativeEnum {
+    pub type_name: &'static str,
+    pub variants: Vec<&'static str>,
+}
+
+fn escape_label(label: &str) -> String {
+    label.replace('\'', "''")
+}
+
+#[always_context]
+impl SetupSql<Postgres> for SetupNativeEnum {
+    type Output = ();
+
+    async fn query(self, exec: &mut impl EasyExecutor<Postgres>) -> anyhow::Result<Self::Output> {
+        let query = "SELECT e.enumlabel FROM pg_enum e JOIN pg_type t ON t.oid = e.enumtypid \
+             WHERE t.typname = $1 AND pg_type_is_visible(t.oid) ORDER BY e.enumsortorder";
+        #[no_context]
+        let existing: Vec<String> = sqlx::query(query)
+            .bind(self.type_name)
+            .fetch_all(exec.executor())
+            .await
+            .with_context(context!(
+                "type_name: {:?} | query: {:?}",
+                self.type_name,
+                query
+            ))?
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+
+        let mut queries = Vec::new();
+        if existing.is_empty() {
+            let labels = self
+                .variants
+                .iter()
+                .map(|variant| format!("'{}'", escape_label(variant)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            queries.push(format!(
+                "CREATE TYPE \"{}\" AS ENUM ({labels})",
+                self.type_name
+            ));
+        } else {
+            // Migration - new variants are added, removed ones are kept (Postgres can't drop them)
+            for variant in self.variants.iter() {
+                if !existing.iter().any(|label| label == variant) {
+                    queries.push(format!(
+                        "ALTER TYPE \"{}\" ADD VALUE '{}'",
+                        self.type_name,
+                        escape_label(variant)
+                    ));
+                }
+            }
+        }
+
+        let mut queries_done = Vec::new();
+        for query in queries {
+            #[no_context]
+            sqlx::query(&query)
+                .execute(exec.executor())
+                .await
+                .with_context(context!(
+                    "type_name: {:?} | query: {:?} | queries_before: {:?}",
+                    self.type_name,
+                    query,
+                    queries_done
+                ))?;
+
+            queries_done.push(query);
+        }
+
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use crate::{EasyExecutor, traits::SetupSql};
 
 #[derive(Debug)]
 pub struct TableExists {
+    pub schema: Option<&'static str>,
     pub name: &'static str,
 }
 
@@ -15,9 +16,13 @@ impl SetupSql<Postgres> for TableExists {
     type Output = bool;
 
     async fn query(self, exec: &mut impl EasyExecutor<Postgres>) -> anyhow::Result<Self::Output> {
+        let table_schema = match self.schema {
+            Some(schema) => format!("'{schema}'"),
+            None => "current_schema()".to_string(),
+        };
         let query = format!(
-            "SELECT EXISTS (SELECT FROM information_schema.tables WHERE table_schema = 'public' AND table_name = '{}')",
-            self.name
+            "SELECT EXISTS (SELECT FROM information_schema.tables WHERE table_schema = {} AND table_name = '{}')",
+            table_schema, self.name
         );
         #[no_context]
         let result: bool = sqlx::query(&query)
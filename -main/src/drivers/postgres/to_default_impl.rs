@@ -214,6 +214,13 @@ impl ToDefault<D> for &uuid::Uuid {
     }
 }
 
+#[cfg(feature = "json")]
+impl ToDefault<D> for sqlx::types::Json<serde_json::Value> {
+    fn to_default(self) -> String {
+        format!("{}::jsonb", quote_literal(&self.0.to_string()))
+    }
+}
+
 impl<T: ToDefault<D>> ToDefault<D> for Option<T> {
     fn to_default(self) -> String {
         match self {
@@ -2,10 +2,12 @@ use super::Sqlite;
 use anyhow::Context;
 use easy_macros::{always_context, context};
 
-use super::table_field_definition;
+use super::{
+    qualified_name, quoted_identifier, table_constraint_definition, table_field_definition,
+};
 use crate::{
     EasyExecutor,
-    driver::{AlterTable, AlterTableSingle},
+    driver::{AlterTable, AlterTableSingle, TableConstraint},
     traits::SetupSql,
 };
 
@@ -15,14 +17,29 @@ impl SetupSql<Sqlite> for AlterTable {
 
     async fn query(self, exec: &mut impl EasyExecutor<Sqlite>) -> anyhow::Result<Self::Output> {
         let mut queries_done = Vec::new();
+        let table = qualified_name(self.schema, self.table_name);
+        // Constraint changes, applied together by recreating the table
+        let mut constraint_changes = Vec::new();
 
         for alter in self.alters {
+            if !matches!(
+                alter,
+                AlterTableSingle::AddConstraint { .. } | AlterTableSingle::DropConstraint { .. }
+            ) && !constraint_changes.is_empty()
+            {
+                rebuild_table(
+                    exec,
+                    self.schema,
+                    self.table_name,
+                    std::mem::take(&mut constraint_changes),
+                    &mut queries_done,
+                )
+                .await?;
+            }
+
             match alter {
                 AlterTableSingle::RenameTable { new_table_name } => {
-                    let query = format!(
-                        "ALTER TABLE {} RENAME TO {}",
-                        self.table_name, new_table_name
-                    );
+                    let query = format!("ALTER TABLE {table} RENAME TO {new_table_name}");
 
                     #[no_context]
                     sqlx::query(&query)
@@ -40,8 +57,7 @@ impl SetupSql<Sqlite> for AlterTable {
                 AlterTableSingle::AddColumn { column } => {
                     let column_def = table_field_definition(column);
                     let column_def = column_def.trim_end_matches(',').trim_end();
-                    let query =
-                        format!("ALTER TABLE {} ADD COLUMN {}", self.table_name, column_def);
+                    let query = format!("ALTER TABLE {table} ADD COLUMN {column_def}");
 
                     let sqlx_query = sqlx::query(&query);
 
@@ -63,9 +79,39 @@ impl SetupSql<Sqlite> for AlterTable {
                     new_column_name,
                 } => {
                     let query = format!(
-                        "ALTER TABLE {} RENAME COLUMN {} TO {}",
-                        self.table_name, old_column_name, new_column_name
+                        "ALTER TABLE {table} RENAME COLUMN {old_column_name} TO {new_column_name}"
+                    );
+
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                AlterTableSingle::CreateIndex { index } => {
+                    let unique = if index.is_unique { "UNIQUE " } else { "" };
+                    let columns = index
+                        .columns
+                        .iter()
+                        .map(|column| format!("`{column}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    // Schema prefixes the index name, the table has to be in the same database
+                    let mut query = format!(
+                        "CREATE {unique}INDEX {} ON `{}` ({columns})",
+                        qualified_name(self.schema, index.name),
+                        self.table_name,
                     );
+                    if let Some(where_clause) = index.where_clause {
+                        query.push_str(&format!(" WHERE {where_clause}"));
+                    }
 
                     #[no_context]
                     sqlx::query(&query)
@@ -80,9 +126,296 @@ impl SetupSql<Sqlite> for AlterTable {
 
                     queries_done.push(query);
                 }
+                AlterTableSingle::DropIndex { name } => {
+                    let query = format!("DROP INDEX {}", qualified_name(self.schema, name));
+
+                    #[no_context]
+                    sqlx::query(&query)
+                        .execute(exec.executor())
+                        .await
+                        .with_context(context!(
+                            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+                            self.table_name,
+                            query,
+                            queries_done
+                        ))?;
+
+                    queries_done.push(query);
+                }
+                // SQLite can't alter constraints of an existing table
+                AlterTableSingle::AddConstraint { constraint } => {
+                    constraint_changes.push(ConstraintChange::Add(constraint));
+                }
+                AlterTableSingle::DropConstraint { name } => {
+                    constraint_changes.push(ConstraintChange::Drop(name));
+                }
             }
         }
 
+        if !constraint_changes.is_empty() {
+            rebuild_table(
+                exec,
+                self.schema,
+                self.table_name,
+                constraint_changes,
+                &mut queries_done,
+            )
+            .await?;
+        }
+
         Ok(())
     }
 }
+
+enum ConstraintChange {
+    Add(TableConstraint),
+    Drop(&'static str),
+}
+
+/// Recreates the table with changed constraints: creates the new table, copies the rows, drops
+/// the old table and renames the new one (see <https://www.sqlite.org/lang_altertable.html>)
+///
+/// The new table definition is the `CREATE TABLE` statement stored in `sqlite_master` with
+/// `changes` applied. Indexes are created again, foreign keys are disabled while rows are copied.
+#[always_context]
+async fn rebuild_table(
+    exec: &mut impl EasyExecutor<Sqlite>,
+    schema: Option<&'static str>,
+    table_name: &'static str,
+    changes: Vec<ConstraintChange>,
+    queries_done: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    let sqlite_master = qualified_name(schema, "sqlite_master");
+
+    let query = format!("SELECT sql FROM {sqlite_master} WHERE type = 'table' AND name = ?");
+    #[no_context]
+    let mut create_sql: String = sqlx::query_scalar(&query)
+        .bind(table_name)
+        .fetch_one(exec.executor())
+        .await
+        .with_context(context!(
+            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+            table_name,
+            query,
+            queries_done
+        ))?;
+
+    for change in changes {
+        match change {
+            ConstraintChange::Add(constraint) => {
+                let definition = table_constraint_definition(constraint);
+                let end = table_definition_end(&create_sql)
+                    .with_context(context!("Unexpected table definition: {:?}", create_sql))?;
+                create_sql.insert_str(end, &format!(",{}", definition.trim_end_matches(',')));
+            }
+            ConstraintChange::Drop(name) => {
+                let clause = format!("CONSTRAINT {}", quoted_identifier(name));
+                let start = create_sql.find(&clause).with_context(context!(
+                    "Constraint {:?} not found in {:?}",
+                    name,
+                    create_sql
+                ))?;
+                let end = definition_item_end(&create_sql, start)
+                    .with_context(context!("Unexpected table definition: {:?}", create_sql))?;
+                if create_sql[end..].starts_with(',') {
+                    create_sql.replace_range(start..=end, "");
+                } else {
+                    // Last item of the definition, the comma before it is removed
+                    let comma = create_sql[..start]
+                        .rfind(',')
+                        .with_context(context!("Unexpected table definition: {:?}", create_sql))?;
+                    create_sql.replace_range(comma..end, "");
+                }
+            }
+        }
+    }
+
+    let new_table_name = format!("__easy_sql_new_{table_name}");
+    // Schema name isn't stored in `sqlite_master`
+    let definition_start = create_sql
+        .find('(')
+        .with_context(context!("Unexpected table definition: {:?}", create_sql))?;
+    let create_new_table = format!(
+        "CREATE TABLE {} {}",
+        qualified_name(schema, &new_table_name),
+        &create_sql[definition_start..]
+    );
+
+    let query = format!(
+        "SELECT sql FROM {sqlite_master} WHERE type = 'index' AND tbl_name = ? AND sql IS NOT NULL"
+    );
+    #[no_context]
+    let indexes: Vec<String> = sqlx::query_scalar(&query)
+        .bind(table_name)
+        .fetch_all(exec.executor())
+        .await
+        .with_context(context!(
+            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+            table_name,
+            query,
+            queries_done
+        ))?;
+
+    // Generated columns can't be inserted
+    let query = "SELECT name FROM pragma_table_xinfo(?, ?) WHERE hidden = 0";
+    #[no_context]
+    let columns: Vec<String> = sqlx::query_scalar(query)
+        .bind(table_name)
+        .bind(schema.unwrap_or("main"))
+        .fetch_all(exec.executor())
+        .await
+        .with_context(context!(
+            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+            table_name,
+            query,
+            queries_done
+        ))?;
+    let columns = columns
+        .iter()
+        .map(|column| quoted_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let table = qualified_name(schema, table_name);
+    let mut queries = vec![
+        create_new_table,
+        format!(
+            "INSERT INTO {} ({columns}) SELECT {columns} FROM {table}",
+            qualified_name(schema, &new_table_name)
+        ),
+        format!("DROP TABLE {table}"),
+        format!(
+            "ALTER TABLE {} RENAME TO {}",
+            qualified_name(schema, &new_table_name),
+            quoted_identifier(table_name)
+        ),
+    ];
+    for index in indexes {
+        let index = match schema {
+            Some(schema) => index.replacen(
+                "INDEX ",
+                &format!("INDEX {}.", quoted_identifier(schema)),
+                1,
+            ),
+            None => index,
+        };
+        queries.push(index);
+    }
+
+    // Only changed outside of a transaction, while enabled dropping the old table would run
+    // `ON DELETE` actions of the referencing tables
+    if foreign_keys_enabled(exec, table_name, queries_done).await? {
+        execute(exec, "PRAGMA foreign_keys = OFF", table_name, queries_done).await?;
+        if foreign_keys_enabled(exec, table_name, queries_done).await? {
+            anyhow::bail!(
+                "Recreating table {:?} to change its constraints requires disabling foreign keys, which isn't possible inside of a transaction | queries_before: {:?}",
+                table_name,
+                queries_done
+            );
+        }
+
+        let result = rebuild_table_queries(exec, &queries, table_name, queries_done).await;
+        execute(exec, "PRAGMA foreign_keys = ON", table_name, queries_done).await?;
+        result
+    } else {
+        rebuild_table_queries(exec, &queries, table_name, queries_done).await
+    }
+}
+
+/// Runs `queries` inside of a savepoint, rolled back when any of them fails
+#[always_context]
+async fn rebuild_table_queries(
+    exec: &mut impl EasyExecutor<Sqlite>,
+    queries: &[String],
+    table_name: &'static str,
+    queries_done: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    execute(exec, "SAVEPOINT easy_sql_rebuild", table_name, queries_done).await?;
+    for query in queries {
+        if let Err(err) = execute(exec, query, table_name, queries_done).await {
+            execute(
+                exec,
+                "ROLLBACK TO easy_sql_rebuild",
+                table_name,
+                queries_done,
+            )
+            .await?;
+            execute(exec, "RELEASE easy_sql_rebuild", table_name, queries_done).await?;
+            return Err(err);
+        }
+    }
+    execute(exec, "RELEASE easy_sql_rebuild", table_name, queries_done).await
+}
+
+#[always_context]
+async fn foreign_keys_enabled(
+    exec: &mut impl EasyExecutor<Sqlite>,
+    table_name: &'static str,
+    queries_done: &[String],
+) -> anyhow::Result<bool> {
+    #[no_context]
+    let enabled: bool = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(exec.executor())
+        .await
+        .with_context(context!(
+            "table_name: {:?} | queries_before: {:?}",
+            table_name,
+            queries_done
+        ))?;
+    Ok(enabled)
+}
+
+#[always_context]
+async fn execute(
+    exec: &mut impl EasyExecutor<Sqlite>,
+    query: &str,
+    table_name: &'static str,
+    queries_done: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    #[no_context]
+    sqlx::query(query)
+        .execute(exec.executor())
+        .await
+        .with_context(context!(
+            "table_name: {:?} | query: {:?} | queries_before: {:?}",
+            table_name,
+            query,
+            queries_done
+        ))?;
+
+    queries_done.push(query.to_string());
+    Ok(())
+}
+
+/// Position of the `,` or `)` ending the item of the table definition starting at `start`
+///
+/// Quoted strings and identifiers are skipped.
+fn definition_item_end(sql: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in sql[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(start + i),
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => return Some(start + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Position of the `)` closing the column and constraint list of a `CREATE TABLE` statement
+fn table_definition_end(sql: &str) -> Option<usize> {
+    let mut position = sql.find('(')? + 1;
+    loop {
+        let end = definition_item_end(sql, position)?;
+        if sql[end..].starts_with(')') {
+            return Some(end);
+        }
+        position = end + 1;
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Context;
+use easy_macros::always_context;
+
+use super::Sqlite;
+use crate::{
+    CopyOut, Driver, EasyExecutor, Insert, Output, Table, macro_support,
+    markers::BindsAllColumns,
+    traits::{DriverArguments, DriverRow},
+};
+
+/// Bulk loads `rows` into table `T`.
+///
+/// SQLite has no `COPY`, rows are inserted one by one with a single prepared statement inside of
+/// one transaction (savepoint if `conn` is already inside of one). Returns number of inserted
+/// rows.
+///
+/// Every item of `rows` has to insert a single row, binding every column ([`BindsAllColumns`]).
+#[always_context]
+#[no_context_inputs]
+pub async fn copy_in<'a, T: Table<Sqlite>, I: Insert<'a, T, Sqlite> + BindsAllColumns>(
+    conn: &mut impl EasyExecutor<Sqlite>,
+    rows: impl IntoIterator<Item = I>,
+) -> anyhow::Result<u64> {
+    let delimiter = Sqlite::identifier_delimiter();
+    let columns = I::insert_columns();
+    let placeholders = (0..columns.len())
+        .map(Sqlite::parameter_placeholder)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let columns = columns
+        .iter()
+        .map(|column| format!("{delimiter}{column}{delimiter}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let statement = format!(
+        "INSERT INTO {} ({columns}) VALUES ({placeholders})",
+        macro_support::quoted_qualified_table_name::<T, Sqlite>()
+    );
+
+    let mut transaction = conn.transaction().await?;
+
+    let mut inserted = 0;
+    for row in rows {
+        let (args, count) = row
+            .insert_values(DriverArguments::<Sqlite>::default())
+            .context("Insert::insert_values failed")?;
+        if count != 1 {
+            anyhow::bail!("copy_in expects every item to insert a single row, got {count} rows");
+        }
+        inserted += sqlx::query_with(&statement, args)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+    }
+
+    transaction.commit().await?;
+
+    Ok(inserted)
+}
+
+/// Streams every row of table `T` as `O` with a regular `SELECT`, the reading counterpart of
+/// [`copy_in`]. Soft deleted rows are skipped.
+pub fn copy_out<T: Table<Sqlite>, O: Output<T, Sqlite, DataToConvert = DriverRow<Sqlite>>>()
+-> CopyOut<T, O, Sqlite> {
+    CopyOut::new()
+}
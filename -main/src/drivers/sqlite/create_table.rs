@@ -3,14 +3,15 @@ use std::collections::HashMap;
 use anyhow::Context;
 use easy_macros::{always_context, context};
 
-use super::{Sqlite, table_field_definition};
+use super::{Sqlite, qualified_name, table_constraint_definition, table_field_definition};
 use crate::EasyExecutor;
 use crate::traits::SetupSql;
 
-use crate::driver::TableField;
+use crate::driver::{TableConstraint, TableField};
 
 #[derive(Debug)]
 pub struct CreateTable {
+    pub schema: Option<&'static str>,
     pub table_name: &'static str,
     pub fields: Vec<TableField>,
 
@@ -18,8 +19,17 @@ pub struct CreateTable {
     ///Can only be used when with single primary key
     pub auto_increment: bool,
     ///Key - table name
-    ///Value - field names, foreign field names, on delete/update cascade
-    pub foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+    ///Value - table schema, field names, foreign field names, on delete/update cascade
+    pub foreign_keys: HashMap<
+        &'static str,
+        (
+            Option<&'static str>,
+            Vec<&'static str>,
+            Vec<&'static str>,
+            bool,
+        ),
+    >,
+    pub constraints: Vec<TableConstraint>,
 }
 
 #[always_context]
@@ -49,7 +59,19 @@ impl SetupSql<Sqlite> for CreateTable {
         }
 
         //Foreign key constraints
-        for (foreign_table, (referenced_fields, foreign_fields, cascade)) in self.foreign_keys {
+        for (foreign_table, (foreign_schema, referenced_fields, foreign_fields, cascade)) in
+            self.foreign_keys
+        {
+            // SQLite foreign keys can only reference tables of the same database
+            if foreign_schema != self.schema {
+                anyhow::bail!(
+                    "Foreign key of table {:?} (schema {:?}) references table {:?} from other schema ({:?}), SQLite doesn't support it",
+                    self.table_name,
+                    self.schema,
+                    foreign_table,
+                    foreign_schema
+                );
+            }
             let referenced_fields = referenced_fields.join(", ");
             let foreign_fields = foreign_fields.join(", ");
             let on_delete = if cascade { "ON DELETE CASCADE" } else { "" };
@@ -59,6 +81,11 @@ impl SetupSql<Sqlite> for CreateTable {
             ));
         }
 
+        //Check and other table constraints
+        for constraint in self.constraints {
+            table_constrains.push_str(&table_constraint_definition(constraint));
+        }
+
         if table_constrains.is_empty() && !table_fields.is_empty() {
             //Removes last ,
             table_fields.pop();
@@ -71,7 +98,9 @@ impl SetupSql<Sqlite> for CreateTable {
 
         let query = format!(
             "CREATE TABLE {} (\r\n{}\r\n{})",
-            self.table_name, table_fields, table_constrains
+            qualified_name(self.schema, self.table_name),
+            table_fields,
+            table_constrains
         );
 
         let sqlx_query = sqlx::query(&query);
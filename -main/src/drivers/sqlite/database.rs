@@ -1,17 +1,47 @@
 use anyhow::Context;
 use easy_macros::always_context;
 
-use std::path::Path;
-#[cfg(test)]
+use futures_core::future::BoxFuture;
+use sqlx::pool::PoolConnectionMetadata;
+use sqlx::sqlite::{SqliteConnection, SqlitePoolOptions};
 use std::path::PathBuf;
+use std::{path::Path, time::Duration};
 
-use crate::{Connection, DatabaseSetup, EasySqlTables, PoolTransaction};
+use crate::{
+    Connection, DatabaseSetup, EasySqlTables, PoolTransaction, RetryPolicy, RetryableError,
+    database_structs::run_in_transaction,
+};
 
 use super::Db;
 
 pub use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
 
-use super::Sqlite;
+use super::{Sqlite, quoted_identifier};
+
+/// `after_connect` hook of the pool, attaches the `(alias, path)` databases to a new connection
+fn attach_databases(
+    attached: Vec<(String, PathBuf)>,
+) -> impl for<'c> Fn(
+    &'c mut SqliteConnection,
+    PoolConnectionMetadata,
+) -> BoxFuture<'c, Result<(), sqlx::Error>>
++ Send
++ Sync
++ 'static {
+    move |conn, _| {
+        let attached = attached.clone();
+        Box::pin(async move {
+            for (alias, path) in attached {
+                let query = format!("ATTACH DATABASE ? AS {}", quoted_identifier(&alias));
+                sqlx::query(&query)
+                    .bind(path.to_string_lossy().into_owned())
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            Ok(())
+        })
+    }
+}
 
 /// SQLite connection pool wrapper with setup helpers.
 ///
@@ -73,6 +103,36 @@ impl Database {
         })
     }
 
+    /// Like [`Database::setup_with_options`], every `(alias, path)` database is attached to each
+    /// connection of the pool (`ATTACH DATABASE path AS alias`)
+    ///
+    /// Tables with `#[sql(schema = "alias")]` are placed in the attached database, missing
+    /// database files are created.
+    pub async fn setup_with_attached<T: DatabaseSetup<Sqlite>>(
+        options: SqliteConnectOptions,
+        attached: &[(&str, &Path)],
+    ) -> anyhow::Result<Self> {
+        let attached = attached
+            .iter()
+            .map(|(alias, path)| (alias.to_string(), path.to_path_buf()))
+            .collect::<Vec<_>>();
+        let connection_pool = SqlitePoolOptions::new()
+            .after_connect(attach_databases(attached))
+            .connect_with(options.clone())
+            .await?;
+
+        let mut conn = Connection::new(connection_pool.acquire().await?);
+
+        EasySqlTables::setup(&mut &mut conn).await?;
+        T::setup(&mut &mut conn).await?;
+
+        Ok(Database {
+            connection_pool,
+            #[cfg(test)]
+            test_db_file_path: Some(options.get_filename().to_owned()),
+        })
+    }
+
     // Broken - database will be lost after connection is closed
     /* pub async fn setup_in_memory<T: DatabaseSetup<Sqlite>>() -> anyhow::Result<Self> {
         let connection_pool =
@@ -99,6 +159,43 @@ impl Database {
         let conn = self.connection_pool.begin().await?;
         Ok(PoolTransaction::new(conn))
     }
+
+    /// Runs `f` inside of a new transaction, committed on `Ok` and rolled back on `Err`
+    ///
+    /// Nested calls (`in_transaction` of the provided transaction) are mapped to savepoints.
+    #[no_context_inputs]
+    pub async fn in_transaction<R>(
+        &self,
+        f: impl AsyncFnOnce(&mut PoolTransaction<Sqlite>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let transaction = self.transaction().await?;
+        run_in_transaction(transaction, f).await
+    }
+
+    /// Runs `f` inside of a new transaction, retried according to `policy` when it fails with a
+    /// [retryable](crate::RetryableError::is_retryable) error
+    ///
+    /// `sleep` waits between attempts, pass the sleep function of the used async runtime (like
+    /// `tokio::time::sleep`). `f` can be called multiple times, it shouldn't have side effects
+    /// outside of the database.
+    #[no_context_inputs]
+    pub async fn in_transaction_with_retry<R>(
+        &self,
+        policy: &RetryPolicy,
+        sleep: impl AsyncFn(Duration),
+        mut f: impl AsyncFnMut(&mut PoolTransaction<Sqlite>) -> anyhow::Result<R>,
+    ) -> anyhow::Result<R> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.in_transaction(&mut f).await {
+                Err(err) if attempt < policy.max_attempts && err.is_retryable::<Sqlite>() => {
+                    sleep(policy.backoff(attempt)).await;
+                }
+                result => return result,
+            }
+        }
+    }
     #[cfg(test)]
     pub async fn setup_for_testing<T: DatabaseSetup<Sqlite>>() -> anyhow::Result<Self> {
         use tokio::sync::Mutex;
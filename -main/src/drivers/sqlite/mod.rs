@@ -2,9 +2,11 @@ use std::collections::HashMap;
 
 use anyhow::Context;
 use create_table::CreateTable;
+use create_view::CreateView;
 use easy_macros::always_context;
 
 mod database;
+pub use copy::{copy_in, copy_out};
 pub use database::*;
 use table_exists::TableExists;
 
@@ -13,21 +15,23 @@ mod to_convert_impl;
 mod to_default_impl;
 
 mod alter_table;
+mod copy;
 mod create_table;
+mod create_view;
 mod table_exists;
 
 use crate::{
     Driver, EasyExecutor,
-    driver::TableField,
+    driver::{DriverQueryResult, GeneratedColumn, TableConstraint, TableField},
     markers::{
-        AllowsNoPrimaryKey,
+        AllowsNoPrimaryKey, SupportsAlterConstraint,
         operators::{
             SupportsAdd, SupportsAnd, SupportsBetween, SupportsBitAnd, SupportsBitOr,
             SupportsBitShiftLeft, SupportsBitShiftRight, SupportsConcatOperator, SupportsDiv,
             SupportsEqual, SupportsGreaterThan, SupportsGreaterThanOrEqual, SupportsIn,
-            SupportsIsNotNull, SupportsIsNull, SupportsLessThan, SupportsLessThanOrEqual,
-            SupportsLike, SupportsModOperator, SupportsMul, SupportsNotEqual, SupportsOr,
-            SupportsSub,
+            SupportsIsNotNull, SupportsIsNull, SupportsJsonExtract, SupportsJsonExtractText,
+            SupportsLessThan, SupportsLessThanOrEqual, SupportsLike, SupportsModOperator,
+            SupportsMul, SupportsNotEqual, SupportsOr, SupportsSub,
         },
     },
 };
@@ -52,11 +56,53 @@ impl Driver for Sqlite {
         format!("?{}", index + 1)
     }
 
+    fn max_bind_parameters() -> usize {
+        32_766
+    }
+
+    fn rows_affected(result: &DriverQueryResult<Self>) -> u64 {
+        result.rows_affected()
+    }
+
+    fn last_insert_id(result: &DriverQueryResult<Self>) -> Option<i64> {
+        Some(result.last_insert_rowid())
+    }
+
+    fn current_timestamp() -> &'static str {
+        // CURRENT_TIMESTAMP has only second precision
+        "(strftime('%Y-%m-%d %H:%M:%f', 'now'))"
+    }
+
+    fn insert_default_value(column_default: Option<&str>) -> String {
+        // `DEFAULT` can't be used inside of `VALUES`, `NULL` also fills `INTEGER PRIMARY KEY`
+        match column_default {
+            Some(default) => format!("({default})"),
+            None => "NULL".to_string(),
+        }
+    }
+
+    fn is_retryable_error(error: &(dyn sqlx::error::DatabaseError + 'static)) -> bool {
+        if error
+            .try_downcast_ref::<sqlx::sqlite::SqliteError>()
+            .is_none()
+        {
+            return false;
+        }
+        // Extended result codes keep the primary code in the lowest byte
+        let primary_code = error
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .map(|code| code & 0xff);
+        // SQLITE_BUSY, SQLITE_LOCKED
+        matches!(primary_code, Some(5) | Some(6))
+    }
+
     async fn table_exists(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         name: &'static str,
     ) -> anyhow::Result<bool> {
-        let result = conn.query_setup(TableExists { name }).await?;
+        let result = conn.query_setup(TableExists { schema, name }).await?;
         Ok(result)
     }
 
@@ -64,21 +110,51 @@ impl Driver for Sqlite {
     ///
     /// `foreign_keys` - Key - table name
     ///
-    /// `foreign_keys` - Value - field names, foreign field names, on delete/update cascade
+    /// `foreign_keys` - Value - foreign table schema, field names, foreign field names, on delete/update cascade
     #[no_context_inputs]
     async fn create_table(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         table_name: &'static str,
         fields: Vec<TableField>,
         primary_keys: Vec<&'static str>,
-        foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+        foreign_keys: HashMap<
+            &'static str,
+            (
+                Option<&'static str>,
+                Vec<&'static str>,
+                Vec<&'static str>,
+                bool,
+            ),
+        >,
+        constraints: Vec<TableConstraint>,
     ) -> anyhow::Result<()> {
         conn.query_setup(CreateTable {
+            schema,
             table_name,
             auto_increment: fields.iter().any(|f| f.is_auto_increment),
             fields,
             primary_keys,
             foreign_keys,
+            constraints,
+        })
+        .await?;
+        Ok(())
+    }
+
+    #[no_context_inputs]
+    async fn create_view(
+        conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
+        view_name: &'static str,
+        columns: Vec<&'static str>,
+        query: String,
+    ) -> anyhow::Result<()> {
+        conn.query_setup(CreateView {
+            schema,
+            view_name,
+            columns,
+            query,
         })
         .await?;
         Ok(())
@@ -88,6 +164,10 @@ impl Driver for Sqlite {
 #[always_context]
 impl AllowsNoPrimaryKey for Sqlite {}
 
+/// Constraints are changed by recreating the table
+#[always_context]
+impl SupportsAlterConstraint for Sqlite {}
+
 impl_supports_fn!(Sqlite, SupportsCount, 0, 1);
 impl_supports_fn!(Sqlite, SupportsSum, 1);
 impl_supports_fn!(Sqlite, SupportsAvg, 1);
@@ -142,6 +222,9 @@ impl SupportsMul for Sqlite {}
 impl SupportsDiv for Sqlite {}
 impl SupportsModOperator for Sqlite {}
 impl SupportsConcatOperator for Sqlite {}
+// Native since SQLite 3.38, `->>` returns the same value as `json_extract`
+impl SupportsJsonExtract for Sqlite {}
+impl SupportsJsonExtractText for Sqlite {}
 impl SupportsBitAnd for Sqlite {}
 impl SupportsBitOr for Sqlite {}
 impl SupportsBitShiftLeft for Sqlite {}
@@ -158,6 +241,23 @@ impl SupportsIsNotNull for Sqlite {}
 impl SupportsIn for Sqlite {}
 impl SupportsBetween for Sqlite {}
 
+/// Identifier in delimiters, delimiters inside of it are doubled
+fn quoted_identifier(name: &str) -> String {
+    let delimiter = Sqlite::identifier_delimiter();
+    format!(
+        "{delimiter}{}{delimiter}",
+        name.replace(delimiter, &delimiter.repeat(2))
+    )
+}
+
+/// Table (or index) name prefixed with the schema, used in DDL statements
+fn qualified_name(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quoted_identifier(schema), quoted_identifier(name)),
+        None => quoted_identifier(name),
+    }
+}
+
 #[always_context]
 fn table_field_definition(field: TableField) -> String {
     let TableField {
@@ -167,6 +267,7 @@ fn table_field_definition(field: TableField) -> String {
         is_not_null,
         default,
         is_auto_increment: _, // SQLite handles auto_increment in PRIMARY KEY constraint
+        generated,
     } = field;
 
     let unique = if is_unique { "UNIQUE" } else { "" };
@@ -176,9 +277,37 @@ fn table_field_definition(field: TableField) -> String {
     } else {
         String::new()
     };
+    let generated = generated_definition(generated);
 
     format!(
-        "{} {} {} {} {},",
-        name, data_type, unique, not_null, default
+        "{} {} {} {} {} {},",
+        name, data_type, generated, unique, not_null, default
     )
 }
+
+fn generated_definition(generated: Option<GeneratedColumn>) -> String {
+    match generated {
+        Some(GeneratedColumn { expression, stored }) => {
+            let storage = if stored { "STORED" } else { "VIRTUAL" };
+            format!("GENERATED ALWAYS AS ({expression}) {storage}")
+        }
+        None => String::new(),
+    }
+}
+
+fn table_constraint_definition(constraint: TableConstraint) -> String {
+    let delimiter = Sqlite::identifier_delimiter();
+    match constraint {
+        TableConstraint::Check { name, expression } => {
+            format!("CONSTRAINT {delimiter}{name}{delimiter} CHECK ({expression}),")
+        }
+        TableConstraint::Unique { name, columns } => {
+            let columns = columns
+                .iter()
+                .map(|column| format!("{delimiter}{column}{delimiter}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("CONSTRAINT {delimiter}{name}{delimiter} UNIQUE ({columns}),")
+        }
+    }
+}
@@ -2,11 +2,12 @@ use anyhow::Context;
 use easy_macros::{always_context, context};
 use sqlx::Row;
 
-use super::Sqlite;
+use super::{Sqlite, qualified_name};
 use crate::traits::{EasyExecutor, SetupSql};
 
 #[derive(Debug)]
 pub struct TableExists {
+    pub schema: Option<&'static str>,
     pub name: &'static str,
 }
 
@@ -16,7 +17,8 @@ impl SetupSql<Sqlite> for TableExists {
 
     async fn query(self, exec: &mut impl EasyExecutor<Sqlite>) -> anyhow::Result<Self::Output> {
         let query = format!(
-            "SELECT EXISTS (SELECT * FROM sqlite_master WHERE type='table' AND name='{}')",
+            "SELECT EXISTS (SELECT * FROM {} WHERE type='table' AND name='{}')",
+            qualified_name(self.schema, "sqlite_master"),
             self.name
         );
         #[no_context]
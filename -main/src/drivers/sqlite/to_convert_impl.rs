@@ -45,6 +45,14 @@ impl ToConvert<CDriver> for Option<Row> {
             .await
             .with_context(context!("Failed to fetch optional row from SQL query"))
     }
+
+    fn merge(self, next: Self) -> Self {
+        self.or(next)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_none()
+    }
 }
 
 #[always_context]
@@ -79,6 +87,15 @@ impl ToConvert<CDriver> for Vec<Row> {
             .await
             .with_context(context!("Failed to fetch all rows from SQL query"))
     }
+
+    fn merge(mut self, next: Self) -> Self {
+        self.extend(next);
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
 }
 
 #[always_context]
@@ -80,6 +80,13 @@ impl ToDefault<D> for &chrono::NaiveTime {
     }
 }
 
+#[cfg(feature = "json")]
+impl ToDefault<D> for sqlx::types::Json<serde_json::Value> {
+    fn to_default(self) -> String {
+        format!("'{}'", escape_sql(&self.0.to_string()))
+    }
+}
+
 impl<T: ToDefault<D>> ToDefault<D> for Option<T> {
     fn to_default(self) -> String {
         match self {
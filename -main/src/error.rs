@@ -0,0 +1,299 @@
+use std::fmt;
+
+use sqlx::error::{DatabaseError, ErrorKind};
+
+/// Typed classification of a failed operation.
+///
+/// Query macros and table setup keep returning `anyhow::Result`, this error is attached to the
+/// returned `anyhow::Error` (next to the original `sqlx` error), so it can be matched on without
+/// parsing error messages. Use [`Error::from_anyhow`] (or `anyhow::Error::downcast_ref`) to
+/// retrieve it.
+///
+/// Errors are classified for:
+/// - statements executed by [`query!`](crate::query), including the commit of multi statement
+///   inserts
+/// - every row streamed by [`query_lazy!`](crate::query_lazy)
+/// - table creation in [`DatabaseSetup::setup`](crate::DatabaseSetup::setup), migrations fail
+///   with [`Error::Migration`]
+///
+/// Failures that don't fit any variant (connection errors, custom `Output` errors, ...) are
+/// returned without it, `from_anyhow` returns `None` for them.
+///
+/// Table names come from the database error when available, otherwise from the table used in
+/// the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// `UNIQUE` or primary key constraint failed
+    ///
+    /// `columns` is empty when the database doesn't report them
+    UniqueViolation { table: String, columns: Vec<String> },
+    /// `FOREIGN KEY` constraint failed
+    ///
+    /// SQLite doesn't report the violated constraint
+    ForeignKeyViolation {
+        table: String,
+        constraint: Option<String>,
+    },
+    /// `CHECK` constraint failed
+    CheckViolation {
+        table: String,
+        constraint: Option<String>,
+    },
+    /// `NOT NULL` constraint failed
+    NotNullViolation {
+        table: String,
+        column: Option<String>,
+    },
+    /// Single row `SELECT` (or `RETURNING`) returned no rows
+    NotFound { table: String },
+    /// Column requested by the output type is missing from the returned row
+    ColumnNotFound { table: String, column: String },
+    /// Column type isn't compatible with the Rust type of the output field
+    TypeMismatch { table: String, column: String },
+    /// Returned value couldn't be decoded into the output type
+    Decode { table: String },
+    /// Update checking the `#[sql(lock_version)]` column didn't change any rows, the row was
+    /// changed (or removed) since it was read
+    StaleObject { table: String },
+    /// [`Patch`](crate::Patch) column is unknown, not allowed or its value has a wrong type
+    InvalidPatch {
+        table: String,
+        column: String,
+        reason: String,
+    },
+    /// Migration of an existing table to the current version failed
+    Migration {
+        table: String,
+        from_version: i64,
+        to_version: i64,
+    },
+}
+
+impl Error {
+    /// Returns typed error attached to `error`, if there is one
+    pub fn from_anyhow(error: &anyhow::Error) -> Option<&Error> {
+        error.downcast_ref::<Error>()
+    }
+
+    /// Classifies `error`, `table` is used when the database doesn't report the table name
+    pub fn from_sqlx(error: &sqlx::Error, table: &str) -> Option<Error> {
+        match error {
+            sqlx::Error::Database(error) => Error::from_database_error(error.as_ref(), table),
+            sqlx::Error::RowNotFound => Some(Error::NotFound {
+                table: table.to_owned(),
+            }),
+            sqlx::Error::ColumnNotFound(column) => Some(Error::ColumnNotFound {
+                table: table.to_owned(),
+                column: column.clone(),
+            }),
+            sqlx::Error::ColumnIndexOutOfBounds { index, .. } => Some(Error::ColumnNotFound {
+                table: table.to_owned(),
+                column: index.to_string(),
+            }),
+            // `Row::try_get` reports incompatible types with a `mismatched types; ...` source
+            sqlx::Error::ColumnDecode { index, source }
+                if source.to_string().starts_with("mismatched types") =>
+            {
+                Some(Error::TypeMismatch {
+                    table: table.to_owned(),
+                    column: index.trim_matches('"').to_owned(),
+                })
+            }
+            sqlx::Error::ColumnDecode { .. } | sqlx::Error::Decode(_) => Some(Error::Decode {
+                table: table.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Classifies constraint violations, returns `None` for other database errors
+    pub fn from_database_error(
+        error: &(dyn DatabaseError + 'static),
+        table: &str,
+    ) -> Option<Error> {
+        let details = ViolationDetails::new(error);
+        let table = details.table.unwrap_or_else(|| table.to_owned());
+
+        match error.kind() {
+            ErrorKind::UniqueViolation => Some(Error::UniqueViolation {
+                table,
+                columns: details.columns,
+            }),
+            ErrorKind::ForeignKeyViolation => Some(Error::ForeignKeyViolation {
+                table,
+                constraint: error.constraint().map(str::to_owned),
+            }),
+            ErrorKind::CheckViolation => Some(Error::CheckViolation {
+                table,
+                // SQLite reports the constraint name in place of columns
+                constraint: error
+                    .constraint()
+                    .map(str::to_owned)
+                    .or_else(|| details.columns.into_iter().next()),
+            }),
+            ErrorKind::NotNullViolation => Some(Error::NotNullViolation {
+                table,
+                column: details.columns.into_iter().next(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Table the error is related to
+    pub fn table(&self) -> &str {
+        match self {
+            Error::UniqueViolation { table, .. }
+            | Error::ForeignKeyViolation { table, .. }
+            | Error::CheckViolation { table, .. }
+            | Error::NotNullViolation { table, .. }
+            | Error::NotFound { table }
+            | Error::ColumnNotFound { table, .. }
+            | Error::TypeMismatch { table, .. }
+            | Error::Decode { table }
+            | Error::StaleObject { table }
+            | Error::InvalidPatch { table, .. }
+            | Error::Migration { table, .. } => table,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UniqueViolation { table, columns } => {
+                write!(f, "Unique constraint violated on table `{table}`")?;
+                if !columns.is_empty() {
+                    write!(f, " (columns: {})", columns.join(", "))?;
+                }
+                Ok(())
+            }
+            Error::ForeignKeyViolation { table, constraint } => {
+                write!(f, "Foreign key constraint violated on table `{table}`")?;
+                if let Some(constraint) = constraint {
+                    write!(f, " (constraint: {constraint})")?;
+                }
+                Ok(())
+            }
+            Error::CheckViolation { table, constraint } => {
+                write!(f, "Check constraint violated on table `{table}`")?;
+                if let Some(constraint) = constraint {
+                    write!(f, " (constraint: {constraint})")?;
+                }
+                Ok(())
+            }
+            Error::NotNullViolation { table, column } => {
+                write!(f, "Not null constraint violated on table `{table}`")?;
+                if let Some(column) = column {
+                    write!(f, " (column: {column})")?;
+                }
+                Ok(())
+            }
+            Error::NotFound { table } => write!(f, "No rows found in table `{table}`"),
+            Error::ColumnNotFound { table, column } => write!(
+                f,
+                "Column `{column}` is missing from row returned from table `{table}`"
+            ),
+            Error::TypeMismatch { table, column } => write!(
+                f,
+                "Type of column `{column}` in table `{table}` doesn't match the output type"
+            ),
+            Error::Decode { table } => {
+                write!(f, "Failed to decode row returned from table `{table}`")
+            }
+            Error::StaleObject { table } => write!(
+                f,
+                "Row in table `{table}` was changed since it was read (lock version mismatch)"
+            ),
+            Error::InvalidPatch {
+                table,
+                column,
+                reason,
+            } => write!(
+                f,
+                "Invalid patch of column `{column}` in table `{table}`: {reason}"
+            ),
+            Error::Migration {
+                table,
+                from_version,
+                to_version,
+            } => write!(
+                f,
+                "Failed to migrate table `{table}` from version {from_version} to {to_version}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Table and columns reported by the database for a constraint violation
+#[derive(Default)]
+struct ViolationDetails {
+    table: Option<String>,
+    columns: Vec<String>,
+}
+
+impl ViolationDetails {
+    fn new(error: &(dyn DatabaseError + 'static)) -> Self {
+        #[cfg(feature = "postgres")]
+        if let Some(error) = error.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
+            return Self::postgres(error);
+        }
+        #[cfg(feature = "sqlite")]
+        if error
+            .try_downcast_ref::<sqlx::sqlite::SqliteError>()
+            .is_some()
+        {
+            return Self::sqlite(error.message());
+        }
+        let _ = error;
+        Self::default()
+    }
+
+    /// `Key (column1, column2)=(value1, value2) already exists.` detail or reported column
+    #[cfg(feature = "postgres")]
+    fn postgres(error: &sqlx::postgres::PgDatabaseError) -> Self {
+        let columns = if let Some(column) = error.column() {
+            vec![column.to_owned()]
+        } else {
+            error
+                .detail()
+                .and_then(|detail| detail.strip_prefix("Key ("))
+                .and_then(|detail| detail.split_once(")=("))
+                .map(|(columns, _)| {
+                    columns
+                        .split(',')
+                        .map(|column| column.trim().trim_matches('"').to_owned())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        ViolationDetails {
+            table: error.table().map(str::to_owned),
+            columns,
+        }
+    }
+
+    /// `UNIQUE constraint failed: table.column1, table.column2` message
+    #[cfg(feature = "sqlite")]
+    fn sqlite(message: &str) -> Self {
+        let Some((_, columns)) = message.split_once("constraint failed: ") else {
+            return Self::default();
+        };
+
+        let mut result = Self::default();
+        for column in columns.split(',') {
+            let column = column.trim();
+            match column.split_once('.') {
+                Some((table, column)) => {
+                    result.table.get_or_insert_with(|| table.to_owned());
+                    result.columns.push(column.to_owned());
+                }
+                None => result.columns.push(column.to_owned()),
+            }
+        }
+        result
+    }
+}
@@ -5,17 +5,24 @@
 docify::compile_markdown!("README.docify.md", "README.md");
 
 mod database_structs;
+mod error;
 pub mod markers;
 mod traits;
 
 mod drivers;
 pub use drivers::*;
 
+#[cfg(feature = "json")]
+pub use database_structs::Patch;
 pub use {
-    database_structs::{Connection, EasySqlTables, PoolTransaction, Transaction},
+    database_structs::{
+        ColumnMeta, Connection, CopyOut, EasySqlTables, ForeignKeyMeta, PoolTransaction,
+        QueryStats, QueryStatsFuture, RetryPolicy, Savepoint, TableMeta, Transaction,
+    },
+    error::Error,
     traits::{
-        DatabaseSetup, Driver, EasyExecutor, EasyExecutorInto, Insert, Output, Table, ToDefault,
-        Update,
+        DatabaseSetup, Driver, EasyExecutor, EasyExecutorInto, Insert, LoadRelated, Output,
+        Related, RetryableError, SqlType, Table, TableMetadata, ToDefault, Update, load_related,
     },
 };
 #[allow(rustdoc::broken_intra_doc_links)]
@@ -25,11 +32,13 @@ pub use {
 /// implementing a custom backend. Most application code will use the concrete drivers (like
 /// [`Postgres`] or [`Sqlite`]) rather than these internals.
 pub mod driver {
-    pub use crate::database_structs::{AlterTable, AlterTableSingle, TableField};
+    pub use crate::database_structs::{
+        AlterTable, AlterTableSingle, GeneratedColumn, TableConstraint, TableField, TableIndex,
+    };
     pub use crate::markers::driver::*;
     pub use crate::traits::{
         DriverArguments, DriverConnection, DriverQueryResult, DriverRow, DriverTypeInfo,
-        InternalDriver, SetupSql,
+        InsertChunk, InsertVisitor, InternalDriver, SetupSql,
     };
     /// Implement a built-in SQL function support marker for specific argument counts.
     ///
@@ -87,8 +96,10 @@ pub mod macro_support;
 /// Validates table/column names, binds arguments, and executes immediately, returns awaitable [anyhow](https://crates.io/crates/anyhow)::Result.
 ///
 /// Notes:
-/// - For INSERT/UPDATE/DELETE without `RETURNING`, the output is driver-specific (generally the
-/// number of rows affected).
+/// - For INSERT/UPDATE/DELETE without `RETURNING`, the output is [`QueryStats`] (number of rows
+/// affected and the SQLite `last_insert_rowid`). [`QueryStatsFuture`] reads a single value
+/// without awaiting the whole struct, for example
+/// `query!(&mut conn, DELETE FROM T WHERE ...).rows_affected().await?`.
 /// - Input syntax highlighting is applied in IDE's, but on documentation page it is not.
 ///
 /// ## Syntax
@@ -121,6 +132,24 @@ pub mod macro_support;
 ///
 /// Accepted Clauses: `WHERE`, `GROUP BY`, `HAVING`, `ORDER BY`, `LIMIT`, `DISTINCT`
 ///
+/// #### Scalar and tuple outputs
+/// `SELECT Type = expr FROM TableType ...` selects a single expression without an [`Output`]
+/// struct, `SELECT (A, B) = (expr1, expr2) FROM TableType ...` selects one column per tuple
+/// element. Values are decoded by position and have to be supported by the driver.
+/// - `SELECT i64 = COUNT(*) FROM TableType WHERE true`
+/// - `SELECT Vec<(i32, String)> = (id, name) FROM TableType WHERE active = true`
+/// - An outer `Vec<...>` / `Option<...>` collects multiple or optional rows (like with `Output`
+///   types), so a nullable value of a single row is written as `Option<Option<T>>`.
+/// - Output arguments and `OutputType.column` references are not available.
+///
+/// ### Soft delete tables
+/// For tables with `#[sql(soft_delete)]` (see [`Table`](macro@crate::Table)), `SELECT`, `EXISTS`
+/// and `UPDATE` skip rows with the soft delete column (`deleted_at` by default) set, and `DELETE`
+/// sets it to the current timestamp instead of removing rows.
+/// - `SELECT OutputType FROM TableType WITH DELETED ...` (same for `EXISTS TableType WITH DELETED`
+///   and `UPDATE TableType WITH DELETED SET ...`) includes soft deleted rows.
+/// - `HARD DELETE FROM TableType ...` removes rows (soft deleted ones included).
+///
 /// ### INSERT
 /// `INSERT INTO TableType VALUES {data}` inserts one value or a collection. `{data}` must implement [`Insert`].
 #[doc = docify::embed!("src/tests/general/documentation/query_macro.rs", insert_example)]
@@ -171,6 +200,20 @@ pub mod macro_support;
 /// `len()` (e.g., `Vec<T>`, `&[T]`). Use `IN {&vec}` if you need to reuse the collection.
 #[doc = docify::embed!("src/tests/general/documentation/query_macro.rs", in_vec_example)]
 ///
+/// ### Optional predicates
+/// `{?value}` binds an `Option<T>` inside of `WHERE` and `HAVING` clauses. When it's `None`, the
+/// `AND` / `OR` operand containing it is left out of the query (together with its operator), so
+/// filters can be built from optional inputs:
+/// - `WHERE name = {?name} AND age > {?min_age}` becomes `WHERE age > $1` for `name: None`
+/// - A parenthesized group with every operand left out is left out as well, an always true
+///   condition is used when nothing is left.
+/// - Parameter numbering and compile time column checks are not affected.
+/// - Not accepted as `IN` lists (`IN {?vec}`).
+///
+/// ## Errors
+/// Returns `anyhow::Result`, classified failures carry an [`Error`] retrieved with
+/// [`Error::from_anyhow`] (constraint violations, missing rows, column and decode errors, ...).
+///
 /// ## Generic connection
 /// `*conn` syntax might be needed when using `&mut EasyExecutor<D>` as connection
 #[doc = docify::embed!("src/tests/general/documentation/query_macro.rs", generic_connection_example)]
@@ -204,7 +247,8 @@ pub use easy_sql_macros::query;
 ///     - otherwise pass a connection or transaction directly (e.g., `fetch(conn)` or `fetch(&mut transaction)`)
 ///
 /// Both return `futures::Stream<Item = anyhow::Result<Output>>`. The stream borrows the
-/// connection; drop or fully consume it before reusing the connection.
+/// connection; drop or fully consume it before reusing the connection. Failed rows carry an
+/// [`Error`] the same way [`query!`] errors do.
 ///
 /// ### Output and query forms
 /// - Output must be a **single-row type** implementing [`Output`]. To return multiple rows,
@@ -232,13 +276,36 @@ pub use easy_sql_macros::query_lazy;
 /// - `#[sql(primary_key)]` marks a column as part of the primary key.
 /// - `#[sql(auto_increment)]` enables auto-increment for the column (driver-dependent).
 /// - `#[sql(unique)]` adds a `UNIQUE` constraint.
+/// - `#[sql(index)]` creates an index on the column (named `{table}_{column}_idx`).
+/// - `#[sql(check = expr)]` adds a `CHECK` constraint (named `{table}_{column}_check`), `expr` uses
+///   the [`query!`](crate::query) expression syntax, columns are validated at compile time.
 /// - `#[sql(default = expr)]` sets a column default (the expression is type-checked).
 /// - `#[sql(bytes)]` stores the field as a binary blob using [`bincode`](https://crates.io/crates/bincode) + [`serde`](https://crates.io/crates/serde).
+/// - `#[sql(json)]` stores the field as JSON using [`serde_json`](https://crates.io/crates/serde_json)
+///   (`JSONB` on Postgres, `TEXT` on SQLite, feature `json`). Unlike `#[sql(bytes)]`, the value
+///   can be queried with `->`/`->>` (for example `settings ->> "theme"`).
+/// - `#[sql(created_at)]` fills the column with [`Driver::current_timestamp`] on insert.
+/// - `#[sql(updated_at)]` fills the column with [`Driver::current_timestamp`] on insert and on
+///   every update, made with [`Update`] types or inline `SET column = value` (unless the update
+///   sets the column itself). Updates made with [`Patch`] are covered too.
+/// - `#[sql(lock_version)]` on an integer field enables optimistic locking: updates made with
+///   [`Update`] types increment the column, and when the update type contains the field, its old
+///   value is added to the `WHERE` clause. A stale write returns [`Error::StaleObject`] (with
+///   `RETURNING` when no rows are returned, `query_lazy!` streams end with it). Update fields of
+///   the column have to be plain integers, checked at compile time.
+/// - `#[sql(generated = expr)]` / `#[sql(generated = expr, stored)]` makes the column computed by
+///   the database (`GENERATED ALWAYS AS (expr) VIRTUAL|STORED`), `expr` uses the
+///   [`query!`](crate::query) expression syntax. Postgres columns are always `STORED`.
 /// - `#[sql(foreign_key = TableStruct)]` creates a foreign key to another table.
 /// - `#[sql(foreign_key = TableStruct, cascade)]` enables `ON DELETE/UPDATE CASCADE`.
+/// - Single column foreign keys implement [`Related`], used by [`load_related`] and
+///   `#[sql(related)]` fields of [`Output`](macro@crate::Output) structs.
 ///
 /// `Option<T>` fields are treated as nullable; all other fields are `NOT NULL` by default.
 ///
+/// [`TableMetadata`] is implemented for every supported driver, `table_meta()` describes the
+/// columns at runtime (Rust and SQL types, nullability, uniqueness, defaults and foreign keys).
+///
 /// ### Foreign keys
 #[doc = docify::embed!("src/tests/general/documentation/table_macro.rs", table_foreign_key_example)]
 ///
@@ -250,6 +317,14 @@ pub use easy_sql_macros::query_lazy;
 ///
 /// ## Table attributes
 /// - `#[sql(table_name = "...")]` overrides the generated `snake_case` name.
+/// - `#[sql(schema = "...")]` places the table in a PostgreSQL schema (created by the setup when
+///   missing) or in a SQLite attached database (the alias of `ATTACH DATABASE ... AS alias`,
+///   attached to every connection by `sqlite::Database::setup_with_attached`). Without it, the
+///   table name is left unqualified and the connection default is used: the `search_path` of
+///   PostgreSQL connections (set by `postgres::Database::setup_with_default_schema`), the main
+///   database for SQLite. The same applies to the `easy_sql_tables` version tracking table.
+///   SQLite foreign keys can't reference tables from another database. Changing the schema
+///   doesn't move existing tables.
 /// - `#[sql(drivers = Driver1, Driver2)]` sets the supported drivers when no default driver is
 ///   configured in the build script via
 ///   [`sql_build::build`](https://docs.rs/sql-build/latest/sql_build/fn.build.html).
@@ -257,11 +332,35 @@ pub use easy_sql_macros::query_lazy;
 /// - `#[sql(version = 1)]` enables migrations and sets the table version (feature `migrations`).
 /// - `#[sql(unique_id = "...")]` is auto generated and used by the build script for migration tracking.
 /// - `#[sql(version_test = 1)]` sets a temporary version for migration testing, requires `unique_id`.
+/// - `#[sql(index(name = "...", columns = [a, b], unique, where = expr))]` creates a (composite)
+///   index. `name` defaults to `{table}_{columns}_idx`, `unique` and `where` (predicate of a
+///   partial index, same syntax as `#[sql(check = expr)]`) are optional. With migrations, added or removed indexes are created or
+///   dropped when the table version changes.
+/// - `#[sql(check = expr)]` adds a table level `CHECK` constraint (named `{table}_check{n}`),
+///   usable for conditions over multiple columns. Outside variables are not allowed. With
+///   migrations, changed checks are dropped and added again when the table version changes
+///   (compile error for drivers without [`SupportsAlterConstraint`](markers::SupportsAlterConstraint)).
+///   SQLite can't alter constraints, the table is recreated with the new constraints and its rows
+///   and indexes are copied over.
+/// - `#[sql(unique(a, b))]` / `#[sql(unique(a, b, name = "..."))]` adds a composite `UNIQUE`
+///   constraint. `name` defaults to `{table}_{columns}_key`. With migrations, changed constraints
+///   are dropped and added again when the table version changes (same driver requirement).
+/// - `#[sql(soft_delete)]` requires a `deleted_at: Option<...>` field (another field with
+///   `#[sql(soft_delete = column)]`), `DELETE` only sets it and other queries skip rows where it is
+///   set (see [`query!`](crate::query)). The field is left out of
+///   [`Insert`](macro@crate::Insert) structs. Joined tables are not filtered.
+/// - `#[sql(patch)]` allows updates with [`Patch`] (feature `json`), columns chosen at runtime
+///   from a JSON object or key/value pairs. Field types have to implement `serde::Deserialize`.
+///   The soft delete column can't be patched.
 ///
 /// ## Notes
 /// - Some drivers require at least one primary key; if none is specified, compilation will fail.
 /// - Auto-increment may be restricted when using composite primary keys, depending on the driver.
-/// - `#[sql(bytes)]` requires the field type to implement [`serde::Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html)/[`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html).
+/// - `#[sql(created_at)]`, `#[sql(updated_at)]` and `#[sql(generated = ...)]` columns are left out
+///   of the derived [`Insert`] and [`Update`] implementations, and [`Insert`](macro@crate::Insert)
+///   structs must not contain them (timestamps work with any timestamp type, like the `chrono`
+///   ones). Generated columns can still be selected with [`Output`].
+/// - `#[sql(bytes)]` and `#[sql(json)]` require the field type to implement [`serde::Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html)/[`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html).
 pub use easy_sql_macros::Table;
 
 /// Defines insertable data for a table.
@@ -281,9 +380,25 @@ pub use easy_sql_macros::Table;
 ///
 /// ## Field attributes
 /// - `#[sql(bytes)]` must match `#[sql(bytes)]` on table struct, stores the field as a binary blob using [`bincode`](https://crates.io/crates/bincode) + [`serde`](https://crates.io/crates/serde).
+/// - `#[sql(json)]` must match `#[sql(json)]` on table struct, stores the field as JSON.
+/// - `#[sql(skip)]` excludes the field, it isn't a column.
+/// - `#[sql(field = column)]` (or `#[sql(field = TableStruct.column)]`) inserts the field into
+///   a column with a different name.
+/// - `#[sql(default_if_none)]` on an `Option<T>` field uses the column default when the value is
+///   `None` (`DEFAULT` on Postgres; the `#[sql(default = ...)]` value or `NULL` on SQLite, which
+///   also fills auto increment keys).
+/// - `#[sql(value = expr)]` inserts a SQL expression instead of the plain value, `{field}`
+///   references the bound field value, e.g. `#[sql(value = LOWER({email}))] email: String`.
+///   Other variables can't be used.
+///
+/// Types with `#[sql(default_if_none)]` or `#[sql(value = ...)]` fields can only be inserted with
+/// `INSERT` statements, passing them to `copy_in` is a compile error (they don't implement
+/// [`markers::BindsAllColumns`]).
 ///
 /// ## Notes
 /// - `#[sql(table = TableStruct)]` is required and must point to a [`Table`] type.
+/// - `#[sql(created_at)]` / `#[sql(updated_at)]` table columns are filled in automatically, they
+///   don't need to be listed in `#[sql(default = ...)]`.
 /// - You can insert a single value, borrow or a collection (`&T`, `Vec<T>`, `&Vec<T>`, `&[T]`).
 pub use easy_sql_macros::Insert;
 /// Defines a SQL output mapping.
@@ -309,10 +424,32 @@ pub use easy_sql_macros::Insert;
 /// For [`table_join!`](crate::table_join) results, map fields to joined columns with `#[sql(field = Table.column)]` or `#[sql(select = ...)]`.
 #[doc = docify::embed!("src/tests/general/documentation/output_macro.rs", output_joined_fields_example)]
 ///
+/// ## Flattened fields
+/// `#[sql(flatten)]` reads a field from columns of another `Output` type with the same
+/// `#[sql(table = ...)]`, for example `#[sql(flatten)] address: Address`. Its columns are appended
+/// to the `SELECT` list with prefixed aliases, so names may repeat between the structs. With
+/// `#[sql(flatten)] address: Option<Address>` (columns of a `LEFT JOIN`ed table) the field is
+/// `None` when every column selected by `Address` is `NULL`.
+///
+/// ## Related rows
+/// `#[sql(related)] children: Vec<ChildOutput>` is not selected, it starts empty and is filled in
+/// by [`LoadRelated::load_related`] for a whole batch of rows with a single `IN (...)` query.
+/// The child table needs a `#[sql(foreign_key = ...)]` field referencing this struct's table, the
+/// parent key is read from the `id` field (`#[sql(related = key_field)]` uses another field). The
+/// key field has to be the single primary key of the table, checked at compile time.
+/// Use [`load_related`] directly to get a `HashMap` of children grouped by the foreign key.
+///
 /// ## Field attributes
 /// - `#[sql(select = ...)]` maps the field to a custom SQL expression.
 /// - `#[sql(field = Table.column)]` maps the field to a joined table column.
+/// - `#[sql(flatten)]` reads the field from columns of another `Output` type (see above). Types
+///   using custom select arguments can't be flattened.
+/// - `#[sql(related)]` / `#[sql(related = key_field)]` marks a `Vec` of related rows (see above).
 /// - `#[sql(bytes)]` decodes the field from binary data (matches table field settings).
+/// - `#[sql(json)]` decodes the field from JSON (matches table field settings). Combined with
+///   `#[sql(select = ...)]` it decodes JSON path results, for example
+///   `#[sql(select = settings -> "theme")] #[sql(json)] theme: Theme`. Text results of `->>` are
+///   read directly, e.g. `#[sql(select = settings ->> "theme")] theme: String`.
 ///
 /// ## Table attributes
 /// - `#[sql(table = TableStruct)]` is required and sets the base table for validation.
@@ -342,6 +479,7 @@ pub use easy_sql_macros::Output;
 /// ## Field attributes
 /// - `#[sql(bytes)]` must match `#[sql(bytes)]` on the table struct, stores the field as a binary
 ///   blob using [`bincode`](https://crates.io/crates/bincode) + [`serde`](https://crates.io/crates/serde).
+/// - `#[sql(json)]` must match `#[sql(json)]` on the table struct, stores the field as JSON.
 /// - `#[sql(maybe_update)]` / `#[sql(maybe)]` marks an `Option<T>` field as optional: `None` skips the update while
 ///   `Some(value)` updates the column. For nullable columns you can also use `Option<Option<T>>`
 ///   to allow `Some(None)` to set `NULL`.
@@ -378,6 +516,118 @@ pub use easy_sql_macros::Update;
 /// - Setup order follows field order;
 pub use easy_sql_macros::DatabaseSetup;
 
+/// Maps a fieldless enum to a column.
+///
+/// Implements `sqlx` [`Type`](sqlx::Type)/[`Encode`](sqlx::Encode)/[`Decode`](sqlx::Decode),
+/// [`ToDefault`] and [`DatabaseSetup`], so the enum can be used as a [`Table`] field (also as
+/// `#[sql(default = ...)]`) and in queries.
+///
+/// ```rust,ignore
+/// #[derive(SqlEnum, Debug, Clone, Copy, PartialEq)]
+/// enum OrderStatus {
+///     Pending,
+///     #[sql(rename = "done")]
+///     Completed,
+/// }
+/// ```
+///
+/// ## Enum attributes
+/// - `#[sql(repr = "text")]` (default) stores variants as text, `#[sql(repr = "i32")]` stores
+///   enum discriminants as integers.
+/// - `#[sql(native)]` uses a native enum type (`CREATE TYPE ... AS ENUM`, Postgres), created by
+///   [`DatabaseSetup::setup`]. Variants missing in an existing type are added during setup.
+/// - `#[sql(type_name = "...")]` overrides the native type name (defaults to `snake_case` enum
+///   name).
+/// - `#[sql(drivers = Driver1, Driver2)]` sets the supported drivers of a `native` enum, every
+///   driver has to implement [`SupportsNativeEnum`](crate::markers::SupportsNativeEnum).
+///
+/// ## Variant attributes
+/// - `#[sql(rename = "...")]` overrides the stored text value (defaults to `snake_case` variant
+///   name).
+///
+/// ## Notes
+/// - `native` enums have to be set up before tables using them, list them first in
+///   [`DatabaseSetup`](macro@crate::DatabaseSetup) structs.
+/// - Decoding an unknown value returns an error.
+pub use easy_sql_macros::SqlEnum;
+
+/// Maps a domain type (usually a newtype) to a column of another SQL type.
+///
+/// Implements [`SqlType`], `sqlx` [`Type`](sqlx::Type)/[`Encode`](sqlx::Encode)/
+/// [`Decode`](sqlx::Decode) and [`ToDefault`] for every driver supporting the stored type, so the
+/// type can be used as a [`Table`] field (migration column types use the stored type),
+/// in `{value}` binds, `IN {vec}` and as `#[sql(default = ...)]`.
+///
+/// ```rust,ignore
+/// #[derive(SqlType, Debug, Clone, Copy, PartialEq)]
+/// struct UserId(i64);
+///
+/// #[derive(SqlType, Debug, Clone, PartialEq)]
+/// #[sql(via = String, from = parse, into = to_string)]
+/// struct Email(EmailAddress);
+/// ```
+///
+/// Without attributes the struct has to have a single field, which is stored directly (and has to
+/// implement `Clone`).
+///
+/// ## Struct attributes
+/// - `#[sql(via = Type)]` stores the value as `Type`, requires `from` and `into`.
+/// - `#[sql(from = ...)]` converts the stored value back, has to return
+///   `Result<Self, E>` where `E` converts into [`BoxDynError`](sqlx::error::BoxDynError) (for
+///   example `std::error::Error` types or `String`).
+/// - `#[sql(into = ...)]` converts `&self` into the stored type.
+///
+/// A single identifier in `from`/`into` is called as a method (`value.parse()`,
+/// `self.to_string()`), paths are called as functions (`Email::try_new(value)`,
+/// `Email::as_string(self)`).
+///
+/// ## Notes
+/// - Decoding failures of `from` are returned as decode errors.
+pub use easy_sql_macros::SqlType;
+
+/// Defines a SQL view.
+///
+/// The view is defined by a `SELECT` query written in the [`query!`](crate::query) syntax,
+/// implements [`Table`], [`DatabaseSetup`] and [`Output`] for the struct, so views can be
+/// selected from like any other table (`SELECT Out FROM MyView ...`), with the same column checks.
+/// View names default to the struct name converted to `snake_case`.
+///
+/// ```rust,ignore
+/// #[derive(View, Debug)]
+/// #[sql(query = SELECT ActiveUserOutput FROM UserTable WHERE active = true)]
+/// struct ActiveUsers {
+///     id: i32,
+///     name: String,
+/// }
+/// ```
+///
+/// Rows selected by the query become the view columns, named after the view struct fields (in
+/// field order).
+///
+/// ## Field attributes
+/// - `#[sql(bytes)]` / `#[sql(json)]` decode the column like the matching table field settings.
+///
+/// ## View attributes
+/// - `#[sql(query = SELECT OutputType FROM TableType [WITH DELETED] [WHERE ...] [GROUP BY ...] [HAVING ...])]`
+///   is required. `OutputType` must be an [`Output`] of `TableType` without custom select
+///   arguments, literals are inlined and outside variables are not allowed.
+/// - `#[sql(table_name = "...")]` overrides the generated `snake_case` name.
+/// - `#[sql(drivers = Driver1, Driver2)]` sets the supported drivers when no default driver is
+///   configured in the build script via
+///   [`sql_build::build`](https://docs.rs/sql-build/latest/sql_build/fn.build.html).
+/// - `#[sql(no_version)]` recreates the view on every setup instead of tracking its version.
+///
+/// ## Notes
+/// - The query definition is versioned in `easy_sql.ron` by the build script (feature
+///   `migrations`), [`DatabaseSetup::setup`] recreates the view only when the definition changes.
+///   Without migrations the view is recreated on every setup.
+/// - Views are read only, `INSERT`, `UPDATE` and `DELETE` queries (and [`Insert`](macro@crate::Insert)
+///   structs) fail to compile.
+/// - Soft deleted rows of the selected table are skipped, unless `WITH DELETED` is used.
+/// - Views have to be set up after the tables they select from, list them last in
+///   [`DatabaseSetup`](macro@crate::DatabaseSetup) structs.
+pub use easy_sql_macros::View;
+
 /// Defines a joined table type for use in [`query!`](crate::query) and [`query_lazy!`](crate::query_lazy).
 ///
 /// `table_join!` creates a lightweight type that implements [`Table`] with a generated join clause.
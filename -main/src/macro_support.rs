@@ -7,21 +7,132 @@ pub use futures_core::Stream;
 use serde::de::DeserializeOwned;
 use sqlx::IntoArguments;
 pub use sqlx::{
-    Arguments, ColumnIndex, Decode, Encode, Executor, QueryBuilder, Type, TypeInfo, query::Query,
-    query_with,
+    Arguments, ColumnIndex, Database, Decode, Encode, Executor, QueryBuilder, Type, TypeInfo,
+    ValueRef, encode::IsNull, error::BoxDynError, query::Query, query_with,
 };
 
 pub use crate::traits::{
-    DriverArguments, DriverConnection, DriverQueryResult, DriverRow, DriverTypeInfo,
-    InternalDriver, ToConvert,
+    DriverArguments, DriverConnection, DriverQueryResult, DriverRow, DriverTypeInfo, InsertChunk,
+    InternalDriver, ToConvert, push_insert_row,
 };
 
 pub use crate::markers::OutputData;
 
+use crate::QueryStats;
 use crate::traits::{Driver, EasyExecutor, Insert, Output, Table, Update};
 
 pub use sqlx::Row as SqlxRow;
 
+/// Columns every Insert type has to provide, checked at compile time by the Insert derive
+///
+/// The Table derive generates an inherent `__EASY_SQL_INSERT_COLUMNS` constant (without columns
+/// filled in by the database, like `#[sql(created_at)]`), which takes precedence over this one.
+/// Manual [`Table`] implementations fall back to `None`, the check is skipped for them (missing
+/// columns are reported by the database).
+pub trait InsertColumnsFallback {
+    const __EASY_SQL_INSERT_COLUMNS: Option<&'static [&'static str]> = None;
+}
+
+impl<T: ?Sized> InsertColumnsFallback for T {}
+
+/// Used by the Insert derive, whether every column of `table_columns` is in `provided`
+pub const fn insert_columns_provided(table_columns: Option<&[&str]>, provided: &[&str]) -> bool {
+    let Some(table_columns) = table_columns else {
+        return true;
+    };
+    let mut i = 0;
+    while i < table_columns.len() {
+        let mut found = false;
+        let mut j = 0;
+        while j < provided.len() {
+            if const_str_eq(table_columns[i], provided[j]) {
+                found = true;
+                break;
+            }
+            j += 1;
+        }
+        if !found {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Primary key columns of the table, used by `#[sql(related)]` fields of the Output derive
+///
+/// The Table derive generates an inherent `__EASY_SQL_PRIMARY_KEYS` constant, which takes
+/// precedence over this one. The check is skipped for manual [`Table`] implementations.
+pub trait PrimaryKeysFallback {
+    const __EASY_SQL_PRIMARY_KEYS: Option<&'static [&'static str]> = None;
+}
+
+impl<T: ?Sized> PrimaryKeysFallback for T {}
+
+/// Used by the Output derive, whether `column` is the only primary key of the table
+pub const fn is_single_primary_key(primary_keys: Option<&[&str]>, column: &str) -> bool {
+    match primary_keys {
+        Some([primary_key]) => const_str_eq(primary_key, column),
+        Some(_) => false,
+        None => true,
+    }
+}
+
+/// `#[sql(lock_version)]` column of the table, used by the Update derive
+///
+/// The Table derive generates an inherent `__EASY_SQL_LOCK_VERSION_COLUMN` constant, which takes
+/// precedence over this one. The check is skipped for manual [`Table`] implementations.
+pub trait LockVersionColumnFallback {
+    const __EASY_SQL_LOCK_VERSION_COLUMN: Option<&'static str> = None;
+}
+
+impl<T: ?Sized> LockVersionColumnFallback for T {}
+
+/// Used by the Update derive, whether `column` is the `#[sql(lock_version)]` column
+pub const fn is_lock_version_column(lock_version_column: Option<&str>, column: &str) -> bool {
+    match lock_version_column {
+        Some(lock_version_column) => const_str_eq(lock_version_column, column),
+        None => false,
+    }
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Implemented by the Output derive, used by `#[sql(flatten)]` fields of other Output types
+///
+/// Every column is selected with an `{prefix}{field name}` alias, nested flattened fields extend
+/// the prefix with `{field name}__`
+#[diagnostic::on_unimplemented(
+    message = "Type `{Self}` can't be flattened into Output for `{Table}`. Flattened types need to derive Output for the same table (or join), without #[sql(select = ...)] arguments."
+)]
+pub trait OutputFlatten<Table, D: Driver>: Sized {
+    fn select_prefixed(current_query: &mut String, prefix: &str);
+    fn convert_prefixed(data: &DriverRow<D>, prefix: &str) -> Result<Self>;
+    /// Used by `Option<T>` flattened fields (LEFT JOIN), `None` is returned when every column is
+    /// NULL
+    fn all_null_prefixed(data: &DriverRow<D>, prefix: &str) -> Result<bool>;
+}
+
+/// Implemented by the Output derive, `Table` is the type from `#[sql(table = ...)]`
+///
+/// Used by `#[sql(related)]` fields to find the table of related rows
+pub trait OutputTable {
+    type Table;
+}
+
 /// Used for compiler checks, quickly creates a value of any type
 ///
 /// Panics if called
@@ -76,15 +187,15 @@ pub fn query_add_selected<T, O: Output<T, D>, D: Driver>(
 #[always_context(skip(!))]
 #[inline(always)]
 ///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
+///
+/// `reserved_parameters` - bind parameters used outside of inserted values (`RETURNING`), `None`
+/// disables splitting into multiple statements
 pub fn query_insert_data<'a, Table, D: Driver, T: Insert<'a, Table, D>>(
     to_insert: T,
-    args: DriverArguments<'a, D>,
+    reserved_parameters: Option<usize>,
     _exec: &impl crate::EasyExecutor<D>,
-) -> anyhow::Result<(Vec<String>, DriverArguments<'a, D>, usize)> {
-    to_insert
-        .insert_values(args)
-        .map(|(new_args, count)| (T::insert_columns(), new_args, count))
-        .context("Insert::insert_values failed")
+) -> anyhow::Result<(Vec<String>, Vec<InsertChunk<'a, D>>)> {
+    query_insert_data_selected_driver::<Table, D, T>(to_insert, reserved_parameters)
 }
 
 #[always_context(skip(!))]
@@ -94,12 +205,19 @@ pub fn query_insert_data<'a, Table, D: Driver, T: Insert<'a, Table, D>>(
 /// Driver is already known
 pub fn query_insert_data_selected_driver<'a, Table, D: Driver, T: Insert<'a, Table, D>>(
     to_insert: T,
-    args: DriverArguments<'a, D>,
-) -> anyhow::Result<(Vec<String>, DriverArguments<'a, D>, usize)> {
-    to_insert
-        .insert_values(args)
-        .map(|(new_args, count)| (T::insert_columns(), new_args, count))
-        .context("Insert::insert_values failed")
+    reserved_parameters: Option<usize>,
+) -> anyhow::Result<(Vec<String>, Vec<InsertChunk<'a, D>>)> {
+    let columns = T::insert_columns();
+    let max_rows = match reserved_parameters {
+        Some(reserved) => {
+            D::max_bind_parameters().saturating_sub(reserved) / T::insert_binds_per_row().max(1)
+        }
+        None => usize::MAX,
+    };
+    let chunks = to_insert
+        .insert_values_chunked(max_rows)
+        .context("Insert::insert_values_chunked failed")?;
+    Ok((columns, chunks))
 }
 
 #[always_context(skip(!))]
@@ -111,25 +229,49 @@ pub fn query_update_data<'a, Table, D: Driver, T: Update<'a, Table, D>>(
     current_query: &mut String,
     parameter_n: &mut usize,
     _exec: &impl crate::EasyExecutor<D>,
-) -> anyhow::Result<DriverArguments<'a, D>> {
-    update_data
-        .updates(args, current_query, parameter_n)
-        .context("Update::updates failed")
+) -> anyhow::Result<(DriverArguments<'a, D>, Option<String>)>
+where
+    Table: crate::Table<D>,
+{
+    query_update_data_selected_driver::<Table, D, T>(update_data, args, current_query, parameter_n)
 }
 #[always_context(skip(!))]
 #[inline(always)]
 /// This function extracts Update type (that's the only reason why it exists instead of direct call)
 ///
 /// Driver is already known
+///
+/// Also returns the `#[sql(lock_version)]` condition, added to the WHERE clause (see
+/// [`Update::lock_version`])
 pub fn query_update_data_selected_driver<'a, Table, D: Driver, T: Update<'a, Table, D>>(
     update_data: T,
     args: DriverArguments<'a, D>,
     current_query: &mut String,
     parameter_n: &mut usize,
-) -> anyhow::Result<DriverArguments<'a, D>> {
-    update_data
+) -> anyhow::Result<(DriverArguments<'a, D>, Option<String>)>
+where
+    Table: crate::Table<D>,
+{
+    let (args, lock_version_condition) = match Table::lock_version_column() {
+        Some(column) => {
+            let (args, bound) = update_data
+                .lock_version(args, column)
+                .context("Update::lock_version failed")?;
+            let delimiter = D::identifier_delimiter();
+            let condition = bound.then(|| {
+                let placeholder = D::parameter_placeholder(*parameter_n);
+                *parameter_n += 1;
+                format!("{delimiter}{column}{delimiter} = {placeholder}")
+            });
+            (args, condition)
+        }
+        None => (args, None),
+    };
+
+    let args = update_data
         .updates(args, current_query, parameter_n)
-        .context("Update::updates failed")
+        .context("Update::updates failed")?;
+    Ok((args, lock_version_condition))
 }
 
 ///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
@@ -140,6 +282,106 @@ pub fn driver_related_table_name<T: Table<D>, D: Driver>(
     T::table_name()
 }
 
+/// Table name prefixed with [`Table::schema`] (`schema.table`), used wherever the table itself is
+/// referenced (`FROM`, `INSERT INTO`, `UPDATE`, joins), columns keep using the bare table name
+pub fn qualified_table_name<T: Table<D>, D: Driver>() -> String {
+    match T::schema() {
+        Some(schema) => format!("{}.{}", schema, T::table_name()),
+        None => T::table_name().to_string(),
+    }
+}
+
+/// [`qualified_table_name`] with every identifier wrapped in [`Driver::identifier_delimiter`]
+pub fn quoted_qualified_table_name<T: Table<D>, D: Driver>() -> String {
+    let delimiter = D::identifier_delimiter();
+    match T::schema() {
+        Some(schema) => format!(
+            "{delimiter}{schema}{delimiter}.{delimiter}{}{delimiter}",
+            T::table_name()
+        ),
+        None => format!("{delimiter}{}{delimiter}", T::table_name()),
+    }
+}
+
+///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
+pub fn driver_qualified_table_name<T: Table<D>, D: Driver>(
+    _exec: &impl crate::EasyExecutor<D>,
+) -> String {
+    qualified_table_name::<T, D>()
+}
+
+///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
+pub fn driver_soft_delete_column<T: Table<D>, D: Driver>(
+    _exec: &impl crate::EasyExecutor<D>,
+) -> Option<&'static str> {
+    T::soft_delete_column()
+}
+
+///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
+pub fn driver_updated_at_columns<T: Table<D>, D: Driver>(
+    _exec: &impl crate::EasyExecutor<D>,
+) -> &'static [&'static str] {
+    T::updated_at_columns()
+}
+
+///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
+pub fn driver_current_timestamp<D: Driver>(_exec: &impl crate::EasyExecutor<D>) -> &'static str {
+    D::current_timestamp()
+}
+
+/// Format parts skipping soft deleted rows: `(before WHERE expression, after it, WHERE clause
+/// used when there is no expression)`, all empty when the table has no soft delete column
+pub fn soft_delete_filter(column: Option<&str>, delimiter: &str) -> (String, &'static str, String) {
+    match column {
+        Some(column) => (
+            format!("{delimiter}{column}{delimiter} IS NULL AND ("),
+            ")",
+            format!(" WHERE {delimiter}{column}{delimiter} IS NULL"),
+        ),
+        None => (String::new(), "", String::new()),
+    }
+}
+
+/// `, column = timestamp` SET parts of `#[sql(updated_at)]` columns not in `set_columns` (inline
+/// `SET column = value` updates)
+pub fn updated_at_set(
+    updated_at_columns: &[&str],
+    set_columns: &[&str],
+    current_timestamp: &str,
+    delimiter: &str,
+) -> String {
+    updated_at_columns
+        .iter()
+        .filter(|column| !set_columns.contains(column))
+        .map(|column| format!(", {delimiter}{column}{delimiter} = {current_timestamp}"))
+        .collect()
+}
+
+/// Joins WHERE / HAVING operands left after skipping the ones with `None` optional variables
+/// (`{?var}`), `(OR group, operand)` pairs
+///
+/// Operands of the same group are joined with AND, groups with OR. Groups without operands are
+/// left out together with their OR. Returns an always true condition when nothing is left
+pub fn join_optional_predicates(predicates: Vec<(usize, String)>) -> String {
+    let mut groups: Vec<(usize, Vec<String>)> = Vec::new();
+    for (group, predicate) in predicates {
+        match groups.last_mut() {
+            Some((last_group, operands)) if *last_group == group => operands.push(predicate),
+            _ => groups.push((group, vec![predicate])),
+        }
+    }
+
+    if groups.is_empty() {
+        return "1 = 1".to_string();
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, operands)| operands.join(" AND "))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
 ///This function extracts Driver from connection (that's the only reason why it exists instead of direct call)
 #[inline(always)]
 pub fn driver_table_joins<T: Table<D>, D: Driver>(
@@ -148,8 +390,21 @@ pub fn driver_table_joins<T: Table<D>, D: Driver>(
 ) {
     T::table_joins(query);
 }
+/// Attaches [`crate::Error`] to `error` when the failure can be classified
+fn classify_error<T: Table<D>, D: Driver>(error: anyhow::Error) -> anyhow::Error {
+    let typed = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<sqlx::Error>())
+        .and_then(|cause| crate::Error::from_sqlx(cause, T::table_name()));
+
+    match typed {
+        Some(typed) => error.context(typed),
+        None => error,
+    }
+}
+
 /// Used by UPDATE, DELETE modes of query! and query_lazy! macros
-pub async fn query_execute<'a, T, O: Output<T, D>, D: Driver>(
+pub async fn query_execute<'a, T: Table<D>, O: Output<T, D>, D: Driver>(
     exec: &mut impl EasyExecutor<D>,
     query: Query<'a, InternalDriver<D>, DriverArguments<'a, D>>,
 ) -> Result<O>
@@ -158,25 +413,259 @@ where
 {
     let raw_data = O::DataToConvert::get(exec.executor(), query)
         .await
+        .map_err(classify_error::<T, D>)
         .context("Output::DataToConvert::get failed")?;
 
-    O::convert(raw_data).context("Output::convert failed")
+    convert_output::<T, O, D>(raw_data)
+}
+
+fn convert_output<T: Table<D>, O: Output<T, D>, D: Driver>(
+    raw_data: O::DataToConvert,
+) -> Result<O> {
+    O::convert(raw_data)
+        .map_err(classify_convert_error::<T, D>)
+        .context("Output::convert failed")
+}
+
+/// Like [`classify_error`], additionally failed `#[sql(bytes)]` and `#[sql(json)]` field
+/// deserialization is classified as [`crate::Error::Decode`]
+fn classify_convert_error<T: Table<D>, D: Driver>(error: anyhow::Error) -> anyhow::Error {
+    if crate::Error::from_anyhow(&error).is_some() {
+        return error;
+    }
+    let error = classify_error::<T, D>(error);
+    if crate::Error::from_anyhow(&error).is_some() {
+        return error;
+    }
+
+    let deserialization_failed = error.chain().any(|cause| {
+        #[cfg(feature = "json")]
+        if cause.is::<serde_json::Error>() {
+            return true;
+        }
+        cause.is::<bincode::error::DecodeError>()
+    });
+    if deserialization_failed {
+        error.context(crate::Error::Decode {
+            table: T::table_name().to_owned(),
+        })
+    } else {
+        error
+    }
+}
+
+/// Used by query_lazy! macro and [`crate::CopyOut`] for every streamed row
+pub fn query_lazy_row<T: Table<D>, O: Output<T, D>, D: Driver>(
+    row: std::result::Result<O::DataToConvert, sqlx::Error>,
+) -> Result<O> {
+    let row = row
+        .map_err(|error| classify_error::<T, D>(error.into()))
+        .context("sqlx::Query::fetch failed")?;
+    convert_output::<T, O, D>(row)
+}
+
+/// Used by UPDATE mode of query_lazy! macro checking `#[sql(lock_version)]`, the stream ends
+/// with [`crate::Error::StaleObject`] when no rows were returned
+pub fn locked_rows_stream<T: Table<D>, D: Driver, S>(
+    rows: S,
+    lock_version_checked: bool,
+) -> LockedRowsStream<S> {
+    LockedRowsStream {
+        rows,
+        stale_table: lock_version_checked.then(T::table_name),
+    }
+}
+
+/// Stream returned by [`locked_rows_stream`]
+pub struct LockedRowsStream<S> {
+    rows: S,
+    /// Set until the first row is returned
+    stale_table: Option<&'static str>,
+}
+
+impl<O, S: Stream<Item = Result<O>> + Unpin> Stream for LockedRowsStream<S> {
+    type Item = Result<O>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match std::pin::Pin::new(&mut this.rows).poll_next(cx) {
+            std::task::Poll::Ready(Some(row)) => {
+                this.stale_table = None;
+                std::task::Poll::Ready(Some(row))
+            }
+            std::task::Poll::Ready(None) => {
+                std::task::Poll::Ready(this.stale_table.take().map(|table| {
+                    Err(anyhow::Error::new(crate::Error::StaleObject {
+                        table: table.to_owned(),
+                    }))
+                }))
+            }
+            std::task::Poll::Pending => std::task::Poll::Pending,
+        }
+    }
 }
+
+/// Used by table setup generated by the Table derive
+pub fn setup_error<T: Table<D>, D: Driver>(error: anyhow::Error) -> anyhow::Error {
+    classify_error::<T, D>(error)
+}
+
 /// Used by UPDATE, DELETE modes of query! and query_lazy! macros
-pub async fn query_execute_no_output<'a, D: Driver>(
+pub async fn query_execute_no_output<'a, T: Table<D>, D: Driver>(
     exec: &mut impl EasyExecutor<D>,
     query: Query<'a, InternalDriver<D>, DriverArguments<'a, D>>,
-) -> Result<DriverQueryResult<D>>
+) -> Result<QueryStats>
 where
     DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
 {
-    query
+    let result = query
         .execute(exec.executor())
         .await
-        .context("QueryBuilder::build.execute failed")
+        .map_err(|error| classify_error::<T, D>(error.into()))
+        .context("QueryBuilder::build.execute failed")?;
+    Ok(QueryStats::from_result::<D>(&result))
+}
+
+/// Used by UPDATE mode of query! macro checking `#[sql(lock_version)]`, fails with
+/// [`crate::Error::StaleObject`] when no rows were changed
+pub async fn query_execute_locked<'a, T: Table<D>, D: Driver>(
+    exec: &mut impl EasyExecutor<D>,
+    query: Query<'a, InternalDriver<D>, DriverArguments<'a, D>>,
+    lock_version_checked: bool,
+) -> Result<QueryStats>
+where
+    DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
+{
+    let result = query_execute_no_output::<T, D>(exec, query).await?;
+    if lock_version_checked && result.rows_affected == 0 {
+        return Err(anyhow::Error::new(crate::Error::StaleObject {
+            table: T::table_name().to_owned(),
+        }));
+    }
+    Ok(result)
+}
+
+/// Used by UPDATE mode of query! macro with `RETURNING` checking `#[sql(lock_version)]`, fails
+/// with [`crate::Error::StaleObject`] when no rows were returned
+pub async fn query_execute_locked_returning<'a, T: Table<D>, O: Output<T, D>, D: Driver>(
+    exec: &mut impl EasyExecutor<D>,
+    query: Query<'a, InternalDriver<D>, DriverArguments<'a, D>>,
+    lock_version_checked: bool,
+) -> Result<O>
+where
+    DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
+{
+    let stale_object = || crate::Error::StaleObject {
+        table: T::table_name().to_owned(),
+    };
+    let raw_data = match O::DataToConvert::get(exec.executor(), query).await {
+        Ok(raw_data) if lock_version_checked && raw_data.is_empty() => {
+            return Err(anyhow::Error::new(stale_object()));
+        }
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            let error = classify_error::<T, D>(error);
+            // Single row outputs fail when nothing is returned
+            let not_found = matches!(
+                crate::Error::from_anyhow(&error),
+                Some(crate::Error::NotFound { .. })
+            );
+            if lock_version_checked && not_found {
+                return Err(error.context(stale_object()));
+            }
+            return Err(error.context("Output::DataToConvert::get failed"));
+        }
+    };
+
+    convert_output::<T, O, D>(raw_data)
+}
+
+/// Used by INSERT mode of query! macro, values can be split into multiple statements
+///
+/// Multiple statements are executed inside of a single transaction, `RETURNING` data is merged in
+/// execution order
+pub async fn query_execute_chunks<'a, T: Table<D>, O: Output<T, D>, D: Driver>(
+    exec: &mut impl EasyExecutor<D>,
+    queries: Vec<Query<'a, InternalDriver<D>, DriverArguments<'a, D>>>,
+) -> Result<O>
+where
+    DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
+    for<'b> &'b mut DriverConnection<D>: Executor<'b, Database = InternalDriver<D>>,
+{
+    let mut queries = queries.into_iter();
+    let first = queries
+        .next()
+        .context("No statements were generated for the query")?;
+    if queries.len() == 0 {
+        return query_execute::<T, O, D>(exec, first).await;
+    }
+
+    let mut transaction = exec.transaction().await?;
+    let mut raw_data = O::DataToConvert::get(transaction.executor(), first)
+        .await
+        .map_err(classify_error::<T, D>)
+        .context("Output::DataToConvert::get failed")?;
+    for query in queries {
+        let next = O::DataToConvert::get(transaction.executor(), query)
+            .await
+            .map_err(classify_error::<T, D>)
+            .context("Output::DataToConvert::get failed")?;
+        raw_data = raw_data.merge(next);
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(classify_error::<T, D>)
+        .context("Transaction::commit failed")?;
+
+    convert_output::<T, O, D>(raw_data)
 }
+/// Used by INSERT mode of query! macro without `RETURNING`, values can be split into multiple statements
+///
+/// Multiple statements are executed inside of a single transaction, query results are combined
+pub async fn query_execute_chunks_no_output<'a, T: Table<D>, D: Driver>(
+    exec: &mut impl EasyExecutor<D>,
+    queries: Vec<Query<'a, InternalDriver<D>, DriverArguments<'a, D>>>,
+) -> Result<QueryStats>
+where
+    DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
+    for<'b> &'b mut DriverConnection<D>: Executor<'b, Database = InternalDriver<D>>,
+{
+    let mut queries = queries.into_iter();
+    let first = queries
+        .next()
+        .context("No statements were generated for the query")?;
+    if queries.len() == 0 {
+        return query_execute_no_output::<T, D>(exec, first).await;
+    }
+
+    let mut transaction = exec.transaction().await?;
+    let mut result = first
+        .execute(transaction.executor())
+        .await
+        .map_err(|error| classify_error::<T, D>(error.into()))
+        .context("QueryBuilder::build.execute failed")?;
+    for query in queries {
+        let next = query
+            .execute(transaction.executor())
+            .await
+            .map_err(|error| classify_error::<T, D>(error.into()))
+            .context("QueryBuilder::build.execute failed")?;
+        result.extend(std::iter::once(next));
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(classify_error::<T, D>)
+        .context("Transaction::commit failed")?;
 
-pub async fn query_exists_execute<'a, D: Driver>(
+    Ok(QueryStats::from_result::<D>(&result))
+}
+
+pub async fn query_exists_execute<'a, T: Table<D>, D: Driver>(
     exec: &mut impl EasyExecutor<D>,
     query: Query<'a, InternalDriver<D>, DriverArguments<'a, D>>,
 ) -> Result<bool>
@@ -189,6 +678,7 @@ where
     let row = query
         .fetch_one(exec.executor())
         .await
+        .map_err(|error| classify_error::<T, D>(error.into()))
         .context("sqlx::Query::fetch_one failed")?;
     let exists: bool =
         <DriverRow<D> as sqlx::Row>::try_get(&row, 0).context("SqlxRow::try_get failed")?;
@@ -196,6 +686,20 @@ where
     Ok(exists)
 }
 
+/// Used by migrations generated by the Table derive
+pub fn migration_error(
+    error: anyhow::Error,
+    table: &str,
+    from_version: i64,
+    to_version: i64,
+) -> anyhow::Error {
+    error.context(crate::Error::Migration {
+        table: table.to_owned(),
+        from_version,
+        to_version,
+    })
+}
+
 #[always_context]
 /// Used by #[sql(bytes)]
 pub fn from_binary<T: DeserializeOwned>(slice: &[u8]) -> anyhow::Result<T> {
@@ -223,6 +727,112 @@ pub fn to_binary<T: serde::Serialize>(value: T) -> anyhow::Result<Vec<u8>> {
     Ok(result)
 }
 
+#[cfg(feature = "json")]
+#[macro_export]
+#[doc(hidden)]
+/// Used by derive macros for `#[sql(json)]` fields and `#[sql(patch)]` tables
+macro_rules! easy_sql_require_json_feature {
+    ($attribute:literal) => {};
+}
+
+#[cfg(not(feature = "json"))]
+#[macro_export]
+#[doc(hidden)]
+/// Used by derive macros for `#[sql(json)]` fields and `#[sql(patch)]` tables
+macro_rules! easy_sql_require_json_feature {
+    ($attribute:literal) => {
+        ::core::compile_error!(::core::concat!(
+            $attribute,
+            " requires the `json` feature of easy-sql"
+        ));
+    };
+}
+
+/// Column type used by #[sql(json)]
+#[cfg(feature = "json")]
+pub type JsonValue = sqlx::types::Json<serde_json::Value>;
+
+#[cfg(feature = "json")]
+#[always_context]
+/// Used by #[sql(json)]
+pub fn from_json<T: DeserializeOwned>(value: JsonValue) -> anyhow::Result<T> {
+    #[no_context]
+    let result = serde_json::from_value(value.0)?;
+
+    Ok(result)
+}
+
+#[cfg(feature = "json")]
+#[always_context]
+/// Used by #[sql(json)]
+pub fn to_json<T: serde::Serialize>(value: T) -> anyhow::Result<JsonValue> {
+    #[no_context]
+    let result = serde_json::to_value(value)?;
+
+    Ok(sqlx::types::Json(result))
+}
+
+/// Value of a single [`crate::Patch`] column
+#[cfg(feature = "json")]
+pub type PatchValue = serde_json::Value;
+
+/// Implemented by the Table derive for tables with `#[sql(patch)]`, used by [`crate::Patch`]
+#[cfg(feature = "json")]
+#[diagnostic::on_unimplemented(
+    message = "Table `{Self}` can't be patched, add #[sql(patch)] to its Table derive (requires the `json` feature)."
+)]
+pub trait PatchColumns {
+    const TABLE_NAME: &'static str;
+
+    /// Column name, `None` for unknown columns and columns filled in by the database
+    fn patch_column(column: &str) -> Option<&'static str>;
+
+    /// Checks that `value` can be converted into the type of `column`
+    fn check_patch_value(column: &'static str, value: &PatchValue) -> Result<()>;
+}
+
+/// Implemented by the Table derive for tables with `#[sql(patch)]`, used by [`crate::Patch`]
+#[cfg(feature = "json")]
+#[diagnostic::on_unimplemented(
+    message = "Table `{Self}` can't be patched, add #[sql(patch)] to its Table derive (requires the `json` feature)."
+)]
+pub trait PatchBind<'a, D: Driver>: PatchColumns {
+    /// Binds `value` converted into the type of `column`
+    fn bind_patch_value(
+        column: &'static str,
+        value: PatchValue,
+        args_list: DriverArguments<'a, D>,
+    ) -> Result<DriverArguments<'a, D>>;
+}
+
+/// Error returned for patch columns which can't be set
+#[cfg(feature = "json")]
+pub fn invalid_patch(table: &str, column: &str, reason: impl Into<String>) -> Error {
+    Error::new(crate::Error::InvalidPatch {
+        table: table.to_owned(),
+        column: column.to_owned(),
+        reason: reason.into(),
+    })
+}
+
+/// Used by #[sql(patch)]
+#[cfg(feature = "json")]
+pub fn check_patch_value<T: DeserializeOwned>(
+    table: &str,
+    column: &str,
+    value: &PatchValue,
+) -> Result<()> {
+    T::deserialize(value)
+        .map(|_| ())
+        .map_err(|error| invalid_patch(table, column, error.to_string()))
+}
+
+/// Used by #[sql(patch)]
+#[cfg(feature = "json")]
+pub fn patch_value<T: DeserializeOwned>(table: &str, column: &str, value: PatchValue) -> Result<T> {
+    serde_json::from_value(value).map_err(|error| invalid_patch(table, column, error.to_string()))
+}
+
 /// Const hash for SQL function names to use in compile-time capability checks.
 ///
 /// Uses a simple FNV-1a 64-bit hash for stable, reproducible IDs.
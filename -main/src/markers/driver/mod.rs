@@ -1,6 +1,6 @@
 use easy_macros::always_context;
 
-use crate::Driver;
+use crate::{Driver, EasyExecutor, traits::DriverTypeInfo};
 
 pub mod functions;
 pub mod operators;
@@ -31,3 +31,32 @@ pub trait AllowsNoPrimaryKey: Driver {}
     message = "Driver `{Self}` does not support multiple auto-increment columns in the same table. Remove #[sql(auto_increment)] from all but one column."
 )]
 pub trait SupportsMultipleAutoIncrementColumns: Driver {}
+
+#[always_context]
+/// Marker for drivers that can add and drop constraints of an existing table (natively or by
+/// recreating the table).
+///
+/// Required by migrations changing `#[sql(check = ...)]` or `#[sql(unique = ...)]` constraints.
+#[diagnostic::on_unimplemented(
+    message = "Driver `{Self}` can't add or drop constraints of an existing table. Changing check or unique constraints of a versioned table requires recreating the table for this driver."
+)]
+pub trait SupportsAlterConstraint: Driver {}
+
+#[always_context]
+/// Drivers with native enum types (`CREATE TYPE ... AS ENUM`).
+///
+/// Required by [`SqlEnum`](macro@crate::SqlEnum) with `#[sql(native)]`.
+#[diagnostic::on_unimplemented(
+    message = "Driver `{Self}` does not support native enum types. Remove #[sql(native)] to store the enum as text."
+)]
+pub trait SupportsNativeEnum: Driver {
+    /// Type info of the native enum type named `type_name`
+    fn native_enum_type_info(type_name: &'static str) -> DriverTypeInfo<Self>;
+
+    /// Creates the enum type, or adds `variants` missing in the existing one.
+    async fn setup_native_enum(
+        conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        type_name: &'static str,
+        variants: Vec<&'static str>,
+    ) -> anyhow::Result<()>;
+}
@@ -52,6 +52,27 @@ pub trait WithArgsSelect {}
 #[diagnostic::on_unimplemented(message = "UPDATE and DELETE queries do not support joined tables.")]
 pub trait NotJoinedTable {}
 
+/// Marker trait for tables that can be modified by `INSERT`, `UPDATE` and `DELETE` queries.
+///
+/// Implemented by the [`Table`](macro@crate::Table) derive macro, views are read only.
+#[diagnostic::on_unimplemented(
+    message = "Type `{Self}` is read only (views can't be modified). INSERT, UPDATE and DELETE queries are not supported."
+)]
+pub trait WritableTable {}
+
+/// Marker for [`Insert`](crate::Insert) types binding a value for every inserted column.
+///
+/// Implemented by the [`Insert`](macro@crate::Insert) derive macro for types without
+/// `#[sql(default_if_none)]` and `#[sql(value = ...)]` fields, required by `copy_in`.
+#[diagnostic::on_unimplemented(
+    message = "Type `{Self}` has #[sql(default_if_none)] or #[sql(value = ...)] fields, it can only be inserted with INSERT statements (not with copy_in)"
+)]
+pub trait BindsAllColumns {}
+
+impl<T: BindsAllColumns> BindsAllColumns for Vec<T> {}
+impl<T: BindsAllColumns> BindsAllColumns for &Vec<T> {}
+impl<T: BindsAllColumns> BindsAllColumns for &[T] {}
+
 /// Support trait providing fields information for query validation.
 ///
 /// Implemented by the [`Output`](macro@crate::Output) derive macro and used internally by the query
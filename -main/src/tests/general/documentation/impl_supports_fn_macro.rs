@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::{Driver, EasyExecutor, driver::TableField};
+use crate::{
+    Driver, EasyExecutor,
+    driver::{DriverQueryResult, TableConstraint, TableField},
+};
 use anyhow::Result;
 use easy_macros::always_context;
 use easy_sql_macros::{impl_supports_fn, impl_supports_fn_any};
@@ -26,8 +29,13 @@ impl Driver for DocDriver {
         format!("?{}", index + 1)
     }
 
+    fn rows_affected(result: &DriverQueryResult<Self>) -> u64 {
+        result.rows_affected()
+    }
+
     async fn table_exists(
         _conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        _schema: Option<&'static str>,
         _name: &'static str,
     ) -> Result<bool> {
         todo!()
@@ -35,10 +43,20 @@ impl Driver for DocDriver {
 
     async fn create_table(
         _conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        _schema: Option<&'static str>,
         _table_name: &'static str,
         _fields: Vec<TableField>,
         _primary_keys: Vec<&'static str>,
-        _foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+        _foreign_keys: HashMap<
+            &'static str,
+            (
+                Option<&'static str>,
+                Vec<&'static str>,
+                Vec<&'static str>,
+                bool,
+            ),
+        >,
+        _constraints: Vec<TableConstraint>,
     ) -> Result<()> {
         todo!()
     }
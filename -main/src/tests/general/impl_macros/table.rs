@@ -57,12 +57,16 @@ impl sql_crate::DatabaseSetup<TestDriver> for ExampleTable {
     ) -> anyhow::Result<()> {
         use ::anyhow::Context;
 
-        let table_exists = <TestDriver as Driver>::table_exists(conn, "example_table")
-            .await
-            .with_context(easy_macros::context!(
-                "Checking if table exists: {:?}",
-                "example_table"
-            ))?;
+        let table_exists = <TestDriver as Driver>::table_exists(
+            conn,
+            <ExampleTable as sql_crate::Table<TestDriver>>::schema(),
+            "example_table",
+        )
+        .await
+        .with_context(easy_macros::context!(
+            "Checking if table exists: {:?}",
+            "example_table"
+        ))?;
 
         if table_exists {
             // Get Table Version and migrate (alter table + update version) if neccessary
@@ -70,6 +74,7 @@ impl sql_crate::DatabaseSetup<TestDriver> for ExampleTable {
             // Create table and create version in EasySqlTables
             <TestDriver as Driver>::create_table(
                 conn,
+                <ExampleTable as sql_crate::Table<TestDriver>>::schema(),
                 "example_table2",
                 vec![sql_crate::driver::TableField {
                     name: "id",
@@ -84,12 +89,14 @@ impl sql_crate::DatabaseSetup<TestDriver> for ExampleTable {
                     is_not_null: false,
                     default: None,
                     is_auto_increment: true,
+                    generated: None,
                 }],
                 vec!["id"],
                 {
                     vec![(
                         <ExampleReferencedTable as sql_crate::Table<TestDriver>>::table_name(),
                         (
+                            <ExampleReferencedTable as sql_crate::Table<TestDriver>>::schema(),
                             vec!["field3"],
                             <ExampleReferencedTable as sql_crate::Table<TestDriver>>::primary_keys(
                             ),
@@ -99,6 +106,7 @@ impl sql_crate::DatabaseSetup<TestDriver> for ExampleTable {
                     .into_iter()
                     .collect()
                 },
+                Vec::new(),
             )
             .await?;
             EasySqlTables_create!(TestDriver, *conn, "table_name".to_string(), 5);
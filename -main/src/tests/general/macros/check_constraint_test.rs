@@ -0,0 +1,86 @@
+use super::*;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(check = min_quantity <= max_quantity)]
+struct CheckConstraintTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    #[sql(check = price >= 0)]
+    price: i64,
+    #[sql(check = status IN ("draft", "published"))]
+    status: String,
+    min_quantity: i32,
+    max_quantity: i32,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = CheckConstraintTestTable)]
+#[sql(default = id)]
+struct CheckConstraintTestData {
+    price: i64,
+    status: String,
+    min_quantity: i32,
+    max_quantity: i32,
+}
+
+fn check_test_data(price: i64, status: &str, min_quantity: i32) -> CheckConstraintTestData {
+    CheckConstraintTestData {
+        price,
+        status: status.to_string(),
+        min_quantity,
+        max_quantity: 10,
+    }
+}
+
+#[always_context(skip(!))]
+async fn assert_check_violation(
+    conn: &mut impl crate::EasyExecutor<TestDriver>,
+    data: CheckConstraintTestData,
+) -> anyhow::Result<()> {
+    let err = query!(*conn, INSERT INTO CheckConstraintTestTable VALUES {data})
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::CheckViolation { .. })
+    ));
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_check_constraints_accept_valid_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<CheckConstraintTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        check_test_data(0, "draft", 1),
+        check_test_data(100, "published", 10),
+    ];
+    query!(&mut conn, INSERT INTO CheckConstraintTestTable VALUES {data}).await?;
+
+    let rows: Vec<CheckConstraintTestData> = query!(&mut conn,
+        SELECT Vec<CheckConstraintTestData> FROM CheckConstraintTestTable WHERE true
+    )
+    .await?;
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_check_constraints_reject_invalid_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<CheckConstraintTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    // Field level checks
+    assert_check_violation(&mut conn, check_test_data(-1, "draft", 1)).await?;
+    assert_check_violation(&mut conn, check_test_data(5, "deleted", 1)).await?;
+    // Struct level check
+    assert_check_violation(&mut conn, check_test_data(5, "draft", 11)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use super::*;
+use crate::Driver;
+
+/// Enough rows to need at least three statements for every driver
+fn rows_over_bind_limit() -> Vec<ExprTestData> {
+    let columns = <ExprTestData as Insert<ExprTestTable, TestDriver>>::insert_columns().len();
+    let rows = TestDriver::max_bind_parameters() / columns * 2 + 10;
+
+    (0..rows)
+        .map(|i| expr_test_data(i as i32, &format!("row-{i}"), i % 2 == 0, None))
+        .collect()
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_chunked_insert_over_bind_limit() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = rows_over_bind_limit();
+    let expected_count = data.len();
+
+    query!(&mut conn, INSERT INTO ExprTestTable VALUES {data}).await?;
+
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true ORDER BY ExprTestTable.id
+    )
+    .await?;
+
+    assert_eq!(results.len(), expected_count);
+    assert_eq!(results[0].str_field, "row-0");
+    assert_eq!(
+        results[expected_count - 1].str_field,
+        format!("row-{}", expected_count - 1)
+    );
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_chunked_insert_returning_keeps_order() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = rows_over_bind_limit();
+
+    let returned: Vec<ExprTestData> = query!(&mut conn,
+        INSERT INTO ExprTestTable VALUES {&data} RETURNING Vec<ExprTestData>
+    )
+    .await?;
+
+    assert_eq!(returned, data);
+
+    conn.rollback().await?;
+    Ok(())
+}
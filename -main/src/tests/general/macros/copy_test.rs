@@ -0,0 +1,141 @@
+use super::*;
+use futures::StreamExt;
+
+#[cfg(all(feature = "postgres", not(feature = "sqlite")))]
+use crate::postgres::{copy_in, copy_out};
+#[cfg(all(feature = "sqlite", not(feature = "postgres")))]
+use crate::sqlite::{copy_in, copy_out};
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(soft_delete)]
+struct CopyOutSoftDeleteTable {
+    #[sql(primary_key)]
+    id: i32,
+    name: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = CopyOutSoftDeleteTable)]
+struct CopyOutSoftDeleteData {
+    id: i32,
+    name: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_copy_in() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = vec![
+        expr_test_data(1, "first", true, None),
+        expr_test_data(2, "second", false, Some("n")),
+        expr_test_data(3, "third", true, None),
+    ];
+
+    let copied = copy_in::<ExprTestTable, _>(&mut conn, data.clone()).await?;
+    assert_eq!(copied, 3);
+
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true ORDER BY ExprTestTable.id
+    )
+    .await?;
+    assert_eq!(results, data);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_copy_in_borrowed_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = (0..100)
+        .map(|i| expr_test_data(i, &format!("row-{i}"), i % 2 == 0, None))
+        .collect::<Vec<_>>();
+
+    let copied = copy_in::<ExprTestTable, _>(&mut conn, &data).await?;
+    assert_eq!(copied, 100);
+
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true ORDER BY ExprTestTable.id
+    )
+    .await?;
+    assert_eq!(results, data);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_copy_out() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(10, "a", true, None),
+            expr_test_data(20, "b", false, None),
+        ],
+    )
+    .await?;
+
+    let mut all = copy_out::<ExprTestTable, ExprTestData>();
+    let mut stream = all.fetch(&mut conn);
+    let mut results = Vec::new();
+    while let Some(row) = stream.next().await {
+        results.push(row?);
+    }
+    drop(stream);
+    results.sort_by_key(|row| row.int_field);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].str_field, "a");
+    assert_eq!(results[1].str_field, "b");
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_copy_out_skips_soft_deleted_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<CopyOutSoftDeleteTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        CopyOutSoftDeleteData {
+            id: 1,
+            name: "deleted".to_string(),
+        },
+        CopyOutSoftDeleteData {
+            id: 2,
+            name: "kept".to_string(),
+        },
+    ];
+    query!(&mut conn, INSERT INTO CopyOutSoftDeleteTable VALUES {data}).await?;
+    query!(&mut conn, DELETE FROM CopyOutSoftDeleteTable WHERE id = 1).await?;
+
+    let mut all = copy_out::<CopyOutSoftDeleteTable, CopyOutSoftDeleteData>();
+    let mut stream = all.fetch(&mut conn);
+    let mut results = Vec::new();
+    while let Some(row) = stream.next().await {
+        results.push(row?);
+    }
+
+    assert_eq!(
+        results,
+        vec![CopyOutSoftDeleteData {
+            id: 2,
+            name: "kept".to_string(),
+        }]
+    );
+
+    Ok(())
+}
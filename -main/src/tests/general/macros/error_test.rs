@@ -0,0 +1,182 @@
+use super::*;
+use crate::DatabaseSetup;
+use easy_sql_macros::query_lazy;
+use futures::StreamExt;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct ErrorTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    #[sql(unique)]
+    email: String,
+    name: String,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = ErrorTestTable)]
+#[sql(default = id)]
+struct ErrorTestData {
+    email: String,
+    name: String,
+}
+
+#[derive(DatabaseSetup)]
+struct ErrorTestSchema {
+    parents: ExprTestTable,
+    related: RelatedTestTable,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_unique_violation_error() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ErrorTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = ErrorTestData {
+        email: "user@example.com".to_string(),
+        name: "first".to_string(),
+    };
+    query!(&mut conn, INSERT INTO ErrorTestTable VALUES {&data}).await?;
+
+    let err = query!(&mut conn, INSERT INTO ErrorTestTable VALUES {&data})
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::UniqueViolation {
+            table: "error_test_table".to_string(),
+            columns: vec!["email".to_string()],
+        })
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_foreign_key_violation_error() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ErrorTestSchema>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = RelatedTestData {
+        parent_id: 404,
+        data: "orphan".to_string(),
+    };
+    let err = query!(&mut conn, INSERT INTO RelatedTestTable VALUES {data})
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::ForeignKeyViolation { table, .. }) if table == "related_test_table"
+    ));
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_not_found_error() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let result: anyhow::Result<ExprTestData> = query!(&mut conn,
+        SELECT ExprTestData FROM ExprTestTable WHERE ExprTestTable.id = 1
+    )
+    .await;
+    let err = result.unwrap_err();
+
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::NotFound {
+            table: "expr_test_table".to_string(),
+        })
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_query_lazy_unique_violation_error() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ErrorTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = ErrorTestData {
+        email: "lazy@example.com".to_string(),
+        name: "first".to_string(),
+    };
+    query!(&mut conn, INSERT INTO ErrorTestTable VALUES {&data}).await?;
+
+    let mut lazy_insert =
+        query_lazy!(INSERT INTO ErrorTestTable VALUES {&data} RETURNING ErrorTestData)?;
+    let result = {
+        let mut stream = lazy_insert.fetch(&mut conn);
+        stream
+            .next()
+            .await
+            .context("Expected INSERT to return result")?
+    };
+    let err = result.unwrap_err();
+
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::UniqueViolation {
+            table: "error_test_table".to_string(),
+            columns: vec!["email".to_string()],
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_errors_are_distinguished() {
+    let missing = sqlx::Error::ColumnNotFound("email".to_string());
+    assert_eq!(
+        crate::Error::from_sqlx(&missing, "error_test_table"),
+        Some(crate::Error::ColumnNotFound {
+            table: "error_test_table".to_string(),
+            column: "email".to_string(),
+        })
+    );
+
+    let mismatched = sqlx::Error::ColumnDecode {
+        index: "\"email\"".to_string(),
+        source: "mismatched types; Rust type `i64` (as SQL type `INTEGER`) is not compatible with SQL type `TEXT`".into(),
+    };
+    assert_eq!(
+        crate::Error::from_sqlx(&mismatched, "error_test_table"),
+        Some(crate::Error::TypeMismatch {
+            table: "error_test_table".to_string(),
+            column: "email".to_string(),
+        })
+    );
+
+    let invalid = sqlx::Error::ColumnDecode {
+        index: "\"email\"".to_string(),
+        source: "invalid utf-8".into(),
+    };
+    assert_eq!(
+        crate::Error::from_sqlx(&invalid, "error_test_table"),
+        Some(crate::Error::Decode {
+            table: "error_test_table".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_error_converts_into_anyhow() {
+    let err = anyhow::Error::from(crate::Error::NotFound {
+        table: "expr_test_table".to_string(),
+    });
+
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::NotFound { .. })
+    ));
+    assert_eq!(err.to_string(), "No rows found in table `expr_test_table`");
+}
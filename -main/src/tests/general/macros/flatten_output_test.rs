@@ -0,0 +1,145 @@
+use super::*;
+use crate::{DatabaseSetup, table_join};
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = ExprTestTable)]
+struct FlattenFlags {
+    bool_field: bool,
+    nullable_field: Option<String>,
+}
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = ExprTestTable)]
+struct FlattenDetails {
+    str_field: String,
+    #[sql(select = int_field * 2)]
+    doubled: i32,
+    #[sql(flatten)]
+    flags: FlattenFlags,
+}
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = ExprTestTable)]
+struct FlattenRow {
+    int_field: i32,
+    #[sql(flatten)]
+    details: FlattenDetails,
+}
+
+table_join!(FlattenParentsWithChildren | ExprTestTable LEFT JOIN RelatedTestTable ON ExprTestTable.id = RelatedTestTable.parent_id);
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = FlattenParentsWithChildren)]
+struct FlattenChild {
+    #[sql(field = RelatedTestTable.data)]
+    data: String,
+}
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = FlattenParentsWithChildren)]
+struct FlattenParentRow {
+    #[sql(field = ExprTestTable.str_field)]
+    name: String,
+    #[sql(flatten)]
+    child: Option<FlattenChild>,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_flatten_output() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(10, "first", true, None),
+            expr_test_data(20, "second", false, Some("note")),
+        ],
+    )
+    .await?;
+
+    let rows: Vec<FlattenRow> = query!(&mut conn,
+        SELECT Vec<FlattenRow> FROM ExprTestTable WHERE true ORDER BY int_field
+    )
+    .await?;
+
+    assert_eq!(
+        rows,
+        vec![
+            FlattenRow {
+                int_field: 10,
+                details: FlattenDetails {
+                    str_field: "first".to_string(),
+                    doubled: 20,
+                    flags: FlattenFlags {
+                        bool_field: true,
+                        nullable_field: None,
+                    },
+                },
+            },
+            FlattenRow {
+                int_field: 20,
+                details: FlattenDetails {
+                    str_field: "second".to_string(),
+                    doubled: 40,
+                    flags: FlattenFlags {
+                        bool_field: false,
+                        nullable_field: Some("note".to_string()),
+                    },
+                },
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_flatten_optional_left_join() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    RelatedTestTable::setup(&mut &mut conn).await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(1, "with_child", true, None),
+            expr_test_data(2, "without_child", true, None),
+        ],
+    )
+    .await?;
+
+    let child = RelatedTestData {
+        parent_id: 1,
+        data: "child".to_string(),
+    };
+    query!(&mut conn, INSERT INTO RelatedTestTable VALUES {child}).await?;
+
+    let rows: Vec<FlattenParentRow> = query!(&mut conn,
+        SELECT Vec<FlattenParentRow> FROM FlattenParentsWithChildren
+        WHERE true
+        ORDER BY ExprTestTable.int_field
+    )
+    .await?;
+
+    assert_eq!(
+        rows,
+        vec![
+            FlattenParentRow {
+                name: "with_child".to_string(),
+                child: Some(FlattenChild {
+                    data: "child".to_string(),
+                }),
+            },
+            FlattenParentRow {
+                name: "without_child".to_string(),
+                child: None,
+            },
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[sql(no_version)]
+struct GeneratedColumnTestTable {
+    #[sql(primary_key)]
+    id: i32,
+    price: i64,
+    quantity: i64,
+    #[sql(generated = price * quantity, stored)]
+    total: i64,
+}
+
+#[derive(Insert, Debug, Clone)]
+#[sql(table = GeneratedColumnTestTable)]
+struct GeneratedColumnTestInsert {
+    id: i32,
+    price: i64,
+    quantity: i64,
+}
+
+#[derive(Output, Debug, Clone, PartialEq)]
+#[sql(table = GeneratedColumnTestTable)]
+struct GeneratedColumnTestTotal {
+    id: i32,
+    total: i64,
+}
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[sql(no_version)]
+struct VirtualColumnTestTable {
+    #[sql(primary_key)]
+    id: i32,
+    first_name: String,
+    last_name: String,
+    #[sql(generated = first_name || " " || last_name)]
+    full_name: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_generated_column_computed() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<GeneratedColumnTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = GeneratedColumnTestInsert {
+        id: 1,
+        price: 5,
+        quantity: 3,
+    };
+    query!(&mut conn, INSERT INTO GeneratedColumnTestTable VALUES {data}).await?;
+
+    let result: GeneratedColumnTestTotal = query!(&mut conn,
+        SELECT GeneratedColumnTestTotal FROM GeneratedColumnTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(result.total, 15);
+
+    query!(&mut conn, UPDATE GeneratedColumnTestTable SET quantity = 4 WHERE id = 1).await?;
+
+    let result: GeneratedColumnTestTotal = query!(&mut conn,
+        SELECT GeneratedColumnTestTotal FROM GeneratedColumnTestTable WHERE total > 10
+    )
+    .await?;
+    assert_eq!(result.total, 20);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_generated_column_skipped_by_table_insert_and_update() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<GeneratedColumnTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    // `total` is ignored, the database computes it
+    let mut row = GeneratedColumnTestTable {
+        id: 1,
+        price: 2,
+        quantity: 2,
+        total: 0,
+    };
+    query!(&mut conn, INSERT INTO GeneratedColumnTestTable VALUES {&row}).await?;
+
+    row.price = 10;
+    let row_ref = &row;
+    query!(&mut conn, UPDATE GeneratedColumnTestTable SET {row_ref} WHERE id = 1).await?;
+
+    let result: GeneratedColumnTestTable = query!(&mut conn,
+        SELECT GeneratedColumnTestTable FROM GeneratedColumnTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(result.total, 20);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "sqlite", not(feature = "postgres")))]
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_virtual_generated_column() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<VirtualColumnTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let row = VirtualColumnTestTable {
+        id: 1,
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+        full_name: String::new(),
+    };
+    query!(&mut conn, INSERT INTO VirtualColumnTestTable VALUES {row}).await?;
+
+    let result: VirtualColumnTestTable = query!(&mut conn,
+        SELECT VirtualColumnTestTable FROM VirtualColumnTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(result.full_name, "Ada Lovelace");
+
+    Ok(())
+}
+
+#[cfg(all(feature = "postgres", not(feature = "sqlite")))]
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_generated_column_stored_by_default_on_postgres() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<VirtualColumnTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let row = VirtualColumnTestTable {
+        id: 1,
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+        full_name: String::new(),
+    };
+    query!(&mut conn, INSERT INTO VirtualColumnTestTable VALUES {row}).await?;
+
+    let result: VirtualColumnTestTable = query!(&mut conn,
+        SELECT VirtualColumnTestTable FROM VirtualColumnTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(result.full_name, "Ada Lovelace");
+
+    // `s` - stored, `v` - virtual
+    let generated: String = sqlx::query_scalar(
+        "SELECT attgenerated::text FROM pg_attribute WHERE attrelid = 'virtual_column_test_table'::regclass AND attname = 'full_name'",
+    )
+    .fetch_one(conn.executor())
+    .await?;
+    assert_eq!(generated, "s");
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(index(name = "index_test_owner_slug", columns = [owner, slug], unique, where = archived = false))]
+struct IndexTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    #[sql(index)]
+    owner: String,
+    slug: String,
+    archived: bool,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = IndexTestTable)]
+#[sql(default = id)]
+struct IndexTestData {
+    owner: String,
+    slug: String,
+    archived: bool,
+}
+
+fn index_test_data(owner: &str, slug: &str, archived: bool) -> IndexTestData {
+    IndexTestData {
+        owner: owner.to_string(),
+        slug: slug.to_string(),
+        archived,
+    }
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_unique_partial_index() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<IndexTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        index_test_data("alice", "post", false),
+        // Archived rows are not covered by the partial index
+        index_test_data("alice", "post", true),
+        index_test_data("alice", "post", true),
+        index_test_data("bob", "post", false),
+    ];
+    query!(&mut conn, INSERT INTO IndexTestTable VALUES {data}).await?;
+
+    let duplicate = index_test_data("alice", "post", false);
+    let err = query!(&mut conn, INSERT INTO IndexTestTable VALUES {duplicate})
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::UniqueViolation { .. })
+    ));
+
+    let rows: Vec<IndexTestData> = query!(&mut conn,
+        SELECT Vec<IndexTestData> FROM IndexTestTable WHERE IndexTestTable.owner = "alice"
+    )
+    .await?;
+    assert_eq!(rows.len(), 3);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_field_index_is_not_unique() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<IndexTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        index_test_data("carol", "first", false),
+        index_test_data("carol", "second", false),
+    ];
+    query!(&mut conn, INSERT INTO IndexTestTable VALUES {data}).await?;
+
+    let rows: Vec<IndexTestData> = query!(&mut conn,
+        SELECT Vec<IndexTestData> FROM IndexTestTable WHERE IndexTestTable.owner = "carol"
+    )
+    .await?;
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
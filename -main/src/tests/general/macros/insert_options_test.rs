@@ -0,0 +1,179 @@
+use super::*;
+use crate::Driver;
+use crate::markers::BindsAllColumns;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct InsertOptionsTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    email: String,
+    #[sql(default = 5)]
+    score: i64,
+    nickname: Option<String>,
+}
+
+#[derive(Insert, Debug, Clone)]
+#[sql(table = InsertOptionsTestTable)]
+struct InsertOptionsTestData {
+    #[sql(default_if_none)]
+    id: Option<i32>,
+    #[sql(value = LOWER({email}))]
+    email: String,
+    #[sql(default_if_none)]
+    score: Option<i64>,
+    #[sql(field = nickname)]
+    display_name: Option<String>,
+    /// Not a column
+    #[sql(skip)]
+    #[allow(dead_code)]
+    source: Vec<u8>,
+}
+
+#[derive(Output, Debug, Clone, PartialEq)]
+#[sql(table = InsertOptionsTestTable)]
+struct InsertOptionsTestOutput {
+    id: i32,
+    email: String,
+    score: i64,
+    nickname: Option<String>,
+}
+
+fn data(id: Option<i32>, email: &str, score: Option<i64>) -> InsertOptionsTestData {
+    InsertOptionsTestData {
+        id,
+        email: email.to_string(),
+        score,
+        display_name: Some(format!("{email} nickname")),
+        source: vec![1, 2, 3],
+    }
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_insert_options_columns() -> anyhow::Result<()> {
+    let columns =
+        <InsertOptionsTestData as Insert<InsertOptionsTestTable, TestDriver>>::insert_columns();
+    assert_eq!(columns, vec!["id", "email", "score", "nickname"]);
+    Ok(())
+}
+
+/// `BINDS_ALL_COLUMNS` is `true` only when `T` implements [`BindsAllColumns`]
+struct BindsAllColumnsCheck<T>(std::marker::PhantomData<T>);
+
+trait NotBindsAllColumns {
+    const BINDS_ALL_COLUMNS: bool = false;
+}
+
+impl<T> NotBindsAllColumns for BindsAllColumnsCheck<T> {}
+
+impl<T: BindsAllColumns> BindsAllColumnsCheck<T> {
+    const BINDS_ALL_COLUMNS: bool = true;
+}
+
+#[test]
+fn test_insert_options_not_accepted_by_copy_in() {
+    // `copy_in` requires `BindsAllColumns`
+    assert!(!BindsAllColumnsCheck::<InsertOptionsTestData>::BINDS_ALL_COLUMNS);
+    assert!(!BindsAllColumnsCheck::<&InsertOptionsTestData>::BINDS_ALL_COLUMNS);
+    assert!(BindsAllColumnsCheck::<ExprTestData>::BINDS_ALL_COLUMNS);
+    assert!(BindsAllColumnsCheck::<&Vec<ExprTestData>>::BINDS_ALL_COLUMNS);
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_insert_options_single() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<InsertOptionsTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let first = data(None, "First@Example.COM", None);
+    query!(&mut conn, INSERT INTO InsertOptionsTestTable VALUES {&first}).await?;
+    let second = data(Some(10), "Second@Example.com", Some(7));
+    query!(&mut conn, INSERT INTO InsertOptionsTestTable VALUES {second}).await?;
+
+    let rows: Vec<InsertOptionsTestOutput> = query!(&mut conn,
+        SELECT Vec<InsertOptionsTestOutput> FROM InsertOptionsTestTable ORDER BY id
+    )
+    .await?;
+    assert_eq!(
+        rows,
+        vec![
+            InsertOptionsTestOutput {
+                id: 1,
+                email: "first@example.com".to_string(),
+                score: 5,
+                nickname: Some("First@Example.COM nickname".to_string()),
+            },
+            InsertOptionsTestOutput {
+                id: 10,
+                email: "second@example.com".to_string(),
+                score: 7,
+                nickname: Some("Second@Example.com nickname".to_string()),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_insert_options_multiple_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<InsertOptionsTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    // Rows with and without defaults bind a different number of parameters
+    let items = vec![
+        data(Some(1), "A@A.COM", None),
+        data(Some(2), "B@B.COM", Some(2)),
+        data(Some(3), "C@C.COM", None),
+    ];
+    let inserted: Vec<InsertOptionsTestOutput> = query!(&mut conn,
+        INSERT INTO InsertOptionsTestTable VALUES {items} RETURNING Vec<InsertOptionsTestOutput>
+    )
+    .await?;
+
+    let emails_and_scores = inserted
+        .iter()
+        .map(|row| (row.email.as_str(), row.score))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        emails_and_scores,
+        vec![("a@a.com", 5), ("b@b.com", 2), ("c@c.com", 5)]
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_insert_options_chunked_over_bind_limit() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<InsertOptionsTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let binds_per_row = <InsertOptionsTestData as Insert<_, TestDriver>>::insert_binds_per_row();
+    assert_eq!(binds_per_row, 4);
+
+    // Enough rows to need at least three statements
+    let rows = TestDriver::max_bind_parameters() / binds_per_row * 2 + 10;
+    let items = (0..rows)
+        .map(|i| data(Some(i as i32 + 1), &format!("Row-{i}@Example.COM"), None))
+        .collect::<Vec<_>>();
+    query!(&mut conn, INSERT INTO InsertOptionsTestTable VALUES {items}).await?;
+
+    let results: Vec<InsertOptionsTestOutput> = query!(&mut conn,
+        SELECT Vec<InsertOptionsTestOutput> FROM InsertOptionsTestTable ORDER BY id
+    )
+    .await?;
+    assert_eq!(results.len(), rows);
+    assert_eq!(results[0].email, "row-0@example.com");
+    assert_eq!(
+        results[rows - 1].email,
+        format!("row-{}@example.com", rows - 1)
+    );
+    assert!(results.iter().all(|row| row.score == 5));
+
+    conn.rollback().await?;
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct JsonTestSettings {
+    theme: String,
+    font_size: i32,
+    tags: Vec<String>,
+}
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct JsonTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    name: String,
+    #[sql(json)]
+    settings: JsonTestSettings,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = JsonTestTable)]
+#[sql(default = id)]
+struct JsonTestData {
+    name: String,
+    #[sql(json)]
+    settings: JsonTestSettings,
+}
+
+#[derive(Update, Debug, Clone)]
+#[sql(table = JsonTestTable)]
+struct JsonTestSettingsUpdate {
+    #[sql(json)]
+    settings: JsonTestSettings,
+}
+
+#[derive(Output, Debug, Clone, PartialEq)]
+#[sql(table = JsonTestTable)]
+struct JsonTestPaths {
+    name: String,
+    #[sql(select = settings ->> "theme")]
+    theme: String,
+    #[sql(select = settings -> "tags")]
+    #[sql(json)]
+    tags: Vec<String>,
+}
+
+fn json_test_data(name: &str, theme: &str, tags: &[&str]) -> JsonTestData {
+    JsonTestData {
+        name: name.to_string(),
+        settings: JsonTestSettings {
+            theme: theme.to_string(),
+            font_size: 14,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        },
+    }
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_json_field_roundtrip() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<JsonTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = json_test_data("alice", "dark", &["admin", "beta"]);
+    query!(&mut conn, INSERT INTO JsonTestTable VALUES {&data}).await?;
+
+    let result: JsonTestData = query!(&mut conn,
+        SELECT JsonTestData FROM JsonTestTable WHERE name = "alice"
+    )
+    .await?;
+    assert_eq!(result, data);
+
+    let update = JsonTestSettingsUpdate {
+        settings: JsonTestSettings {
+            theme: "light".to_string(),
+            font_size: 16,
+            tags: Vec::new(),
+        },
+    };
+    let update_ref = &update;
+    query!(&mut conn, UPDATE JsonTestTable SET {update_ref} WHERE name = "alice").await?;
+
+    let result: JsonTestData = query!(&mut conn,
+        SELECT JsonTestData FROM JsonTestTable WHERE name = "alice"
+    )
+    .await?;
+    assert_eq!(result.settings, update.settings);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_json_path_in_where() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<JsonTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        json_test_data("alice", "dark", &["admin"]),
+        json_test_data("bob", "light", &[]),
+        json_test_data("carol", "dark", &[]),
+    ];
+    query!(&mut conn, INSERT INTO JsonTestTable VALUES {data}).await?;
+
+    let theme = "dark";
+    let results: Vec<JsonTestData> = query!(&mut conn,
+        SELECT Vec<JsonTestData> FROM JsonTestTable
+        WHERE settings ->> "theme" = {theme}
+        ORDER BY name
+    )
+    .await?;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].name, "alice");
+    assert_eq!(results[1].name, "carol");
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_json_path_output_fields() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<JsonTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = json_test_data("alice", "dark", &["admin", "beta"]);
+    query!(&mut conn, INSERT INTO JsonTestTable VALUES {data}).await?;
+
+    let result: JsonTestPaths = query!(&mut conn,
+        SELECT JsonTestPaths FROM JsonTestTable WHERE name = "alice"
+    )
+    .await?;
+    assert_eq!(
+        result,
+        JsonTestPaths {
+            name: "alice".to_string(),
+            theme: "dark".to_string(),
+            tags: vec!["admin".to_string(), "beta".to_string()],
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use super::*;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct LockVersionTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    title: String,
+    #[sql(lock_version)]
+    lock_version: i64,
+}
+
+#[derive(Insert, Update, Output, Debug, Clone, PartialEq)]
+#[sql(table = LockVersionTestTable)]
+#[sql(default = id)]
+struct LockVersionTestData {
+    title: String,
+    lock_version: i64,
+}
+
+/// Update without the lock version, only increments it
+#[derive(Update, Debug, Clone)]
+#[sql(table = LockVersionTestTable)]
+struct LockVersionTitleUpdate {
+    title: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_lock_version_update_increments() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<LockVersionTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = LockVersionTestData {
+        title: "first".to_string(),
+        lock_version: 0,
+    };
+    query!(&mut conn, INSERT INTO LockVersionTestTable VALUES {data}).await?;
+
+    let mut read: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    read.title = "second".to_string();
+    query!(&mut conn, UPDATE LockVersionTestTable SET {&read} WHERE id = 1).await?;
+
+    let updated: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(
+        updated,
+        LockVersionTestData {
+            title: "second".to_string(),
+            lock_version: 1,
+        }
+    );
+
+    let title_update = LockVersionTitleUpdate {
+        title: "third".to_string(),
+    };
+    query!(&mut conn, UPDATE LockVersionTestTable SET {title_update} WHERE id = 1).await?;
+
+    let updated: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(updated.title, "third");
+    assert_eq!(updated.lock_version, 2);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_lock_version_stale_update() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<LockVersionTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = LockVersionTestData {
+        title: "first".to_string(),
+        lock_version: 0,
+    };
+    query!(&mut conn, INSERT INTO LockVersionTestTable VALUES {data}).await?;
+
+    let first: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    let second = first.clone();
+
+    let first = LockVersionTestData {
+        title: "first writer".to_string(),
+        ..first
+    };
+    query!(&mut conn, UPDATE LockVersionTestTable SET {first} WHERE id = 1).await?;
+
+    // Read before the first update, condition without parentheses checks the user condition is
+    // kept together
+    let second = LockVersionTestData {
+        title: "second writer".to_string(),
+        ..second
+    };
+    let err = query!(&mut conn,
+        UPDATE LockVersionTestTable SET {second} WHERE id = 1 OR id = 2
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::StaleObject {
+            table: "lock_version_test_table".to_string(),
+        })
+    );
+
+    let current: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(current.title, "first writer");
+    assert_eq!(current.lock_version, 1);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_lock_version_stale_update_returning() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<LockVersionTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = LockVersionTestData {
+        title: "first".to_string(),
+        lock_version: 0,
+    };
+    query!(&mut conn, INSERT INTO LockVersionTestTable VALUES {data}).await?;
+
+    let stale = LockVersionTestData {
+        title: "first".to_string(),
+        lock_version: 0,
+    };
+    let updated: LockVersionTestData = query!(&mut conn,
+        UPDATE LockVersionTestTable SET {&stale} WHERE id = 1 RETURNING LockVersionTestData
+    )
+    .await?;
+    assert_eq!(updated.lock_version, 1);
+
+    let stale_object = crate::Error::StaleObject {
+        table: "lock_version_test_table".to_string(),
+    };
+
+    // Single row output
+    let err = query!(&mut conn,
+        UPDATE LockVersionTestTable SET {&stale} WHERE id = 1 RETURNING LockVersionTestData
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(crate::Error::from_anyhow(&err), Some(&stale_object));
+
+    // Multiple rows output
+    let err = query!(&mut conn,
+        UPDATE LockVersionTestTable SET {&stale} WHERE id = 1 RETURNING Vec<LockVersionTestData>
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(crate::Error::from_anyhow(&err), Some(&stale_object));
+
+    let current: LockVersionTestData = query!(&mut conn,
+        SELECT LockVersionTestData FROM LockVersionTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(current.lock_version, 1);
+
+    Ok(())
+}
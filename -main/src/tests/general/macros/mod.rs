@@ -361,16 +361,47 @@ pub async fn insert_multiple_test_data(
 // Sub-modules
 // ====================
 
+mod check_constraint_test;
+mod chunked_insert_test;
+mod copy_test;
 mod custom_select;
 mod custom_select_compile_fail;
+mod error_test;
+mod flatten_output_test;
+mod generated_column_test;
+mod index_test;
+mod insert_options_test;
+#[cfg(feature = "json")]
+mod json_test;
+mod lock_version_test;
+mod optional_predicate_test;
 mod order_by_container_test;
 mod order_by_output_columns_test;
 mod output_columns_comprehensive_test;
 mod output_columns_in_custom_select_test;
+#[cfg(feature = "json")]
+mod patch_test;
 mod pool_argument_test;
 mod query_lazy_macro;
 mod query_macro;
+mod query_stats_test;
+mod related_test;
+mod retry_test;
+mod savepoint_test;
+mod scalar_output_test;
+#[cfg(feature = "postgres")]
+mod schema_test;
+mod soft_delete_test;
+mod sql_enum_test;
 mod sql_expressions;
+mod sql_type_test;
+#[cfg(feature = "sqlite")]
+mod sqlite_schema_test;
+mod table_meta_test;
+#[cfg(feature = "chrono")]
+mod timestamp_test;
+mod unique_constraint_test;
+mod view_test;
 
 mod custom_select_validation_test;
 mod custom_sql_functions;
@@ -0,0 +1,259 @@
+// Tests for optional predicates ({?var}) in WHERE and HAVING clauses
+
+use super::*;
+
+#[always_context(skip(!))]
+async fn insert_optional_test_data(
+    conn: impl crate::EasyExecutor<TestDriver> + Send + Sync,
+) -> anyhow::Result<()> {
+    insert_multiple_test_data(
+        conn,
+        vec![
+            expr_test_data(10, "alpha", true, None),
+            expr_test_data(20, "beta", false, Some("x")),
+            expr_test_data(30, "gamma", true, Some("y")),
+            expr_test_data(40, "alpha", false, None),
+        ],
+    )
+    .await
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_some_and_none() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let name: Option<&str> = Some("alpha");
+    let min_int: Option<i32> = None;
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {?name} AND int_field > {?min_int}
+    )
+    .await?;
+    assert_eq!(results.len(), 2);
+
+    let name: Option<&str> = None;
+    let min_int = Some(15);
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {?name} AND int_field > {?min_int}
+    )
+    .await?;
+    assert_eq!(results.len(), 3);
+
+    let name = Some("alpha");
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {?name} AND int_field > {?min_int}
+    )
+    .await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].int_field, 40);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+/// Every predicate left out, all rows are returned
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_all_none() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let name: Option<String> = None;
+    let min_int: Option<i32> = None;
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {?name} AND int_field > {?min_int}
+    )
+    .await?;
+    assert_eq!(results.len(), 4);
+
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE int_field > {?min_int}
+    )
+    .await?;
+    assert_eq!(results.len(), 4);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+/// Parameters after left out predicates (including `IN {vec}` lists) keep matching their binds
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_parameter_numbering() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let ints = vec![10, 20, 30];
+    let names = vec!["alpha", "gamma"];
+    let skipped: Option<i32> = None;
+    let max_int = Some(25);
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE int_field IN {ints}
+            AND int_field = {?skipped}
+            AND (str_field IN {names} AND int_field < {?max_int})
+            AND bool_field = {true}
+    )
+    .await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].int_field, 10);
+
+    let max_int: Option<i32> = None;
+    let min_int = Some(5);
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE (str_field IN {names} AND int_field < {?max_int})
+            AND int_field >= {?min_int}
+            AND bool_field = {true}
+    )
+    .await?;
+    assert_eq!(results.len(), 2);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+/// Left out OR operands, empty parenthesized group is left out of its parent as well
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_or_and_groups() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let name: Option<&str> = None;
+    let nullable = Some("y");
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {?name} OR nullable_field = {?nullable}
+    )
+    .await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].str_field, "gamma");
+
+    let nullable: Option<&str> = None;
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE bool_field = false OR (str_field = {?name} OR nullable_field = {?nullable})
+    )
+    .await?;
+    assert_eq!(results.len(), 2);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+/// Chains mixing AND and OR keep AND over OR precedence after left out operands
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_mixed_and_or() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let beta = "beta";
+    let min_int: Option<i32> = None;
+
+    // Left out first: `bool_field = true OR str_field = 'beta'`
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE int_field > {?min_int} AND bool_field = true OR str_field = {beta}
+        ORDER BY int_field
+    )
+    .await?;
+    let ints = results.iter().map(|r| r.int_field).collect::<Vec<_>>();
+    assert_eq!(ints, vec![10, 20, 30]);
+
+    // Left out in the middle: `str_field = 'beta' OR bool_field = true`
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {beta} OR int_field > {?min_int} AND bool_field = true
+        ORDER BY int_field
+    )
+    .await?;
+    let ints = results.iter().map(|r| r.int_field).collect::<Vec<_>>();
+    assert_eq!(ints, vec![10, 20, 30]);
+
+    // Left out last: `str_field = 'beta' OR bool_field = true`
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {beta} OR bool_field = true AND int_field > {?min_int}
+        ORDER BY int_field
+    )
+    .await?;
+    let ints = results.iter().map(|r| r.int_field).collect::<Vec<_>>();
+    assert_eq!(ints, vec![10, 20, 30]);
+
+    // Whole AND group left out together with its OR: `str_field = 'beta' OR int_field = 40`
+    let nullable: Option<&str> = None;
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {beta}
+            OR int_field > {?min_int} AND nullable_field = {?nullable}
+            OR int_field = 40
+        ORDER BY int_field
+    )
+    .await?;
+    let ints = results.iter().map(|r| r.int_field).collect::<Vec<_>>();
+    assert_eq!(ints, vec![20, 40]);
+
+    // Nothing left out: `str_field = 'beta' OR (int_field > 15 AND bool_field = true)`
+    let min_int = Some(15);
+    let results: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable
+        WHERE str_field = {beta} OR int_field > {?min_int} AND bool_field = true
+        ORDER BY int_field
+    )
+    .await?;
+    let ints = results.iter().map(|r| r.int_field).collect::<Vec<_>>();
+    assert_eq!(ints, vec![20, 30]);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_optional_predicates_update_delete() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    insert_optional_test_data(&mut conn).await?;
+
+    let name: Option<&str> = None;
+    let max_int = Some(25);
+    query!(&mut conn,
+        UPDATE ExprTestTable SET bool_field = true
+        WHERE str_field = {?name} AND int_field < {?max_int}
+    )
+    .await?;
+
+    let updated: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE bool_field = true
+    )
+    .await?;
+    assert_eq!(updated.len(), 3);
+
+    let name = Some("alpha");
+    let max_int: Option<i32> = None;
+    query!(&mut conn,
+        DELETE FROM ExprTestTable WHERE str_field = {?name} AND int_field < {?max_int}
+    )
+    .await?;
+
+    let left: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true
+    )
+    .await?;
+    assert_eq!(left.len(), 2);
+
+    conn.rollback().await?;
+    Ok(())
+}
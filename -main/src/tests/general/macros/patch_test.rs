@@ -0,0 +1,188 @@
+use super::*;
+use crate::Patch;
+use serde_json::json;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(patch)]
+struct PatchTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    title: String,
+    views: i64,
+    note: Option<String>,
+    #[sql(lock_version)]
+    lock_version: i64,
+}
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(patch)]
+#[sql(soft_delete)]
+struct PatchSoftDeleteTestTable {
+    #[sql(primary_key)]
+    id: i32,
+    title: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = PatchTestTable)]
+#[sql(default = id)]
+struct PatchTestData {
+    title: String,
+    views: i64,
+    note: Option<String>,
+    lock_version: i64,
+}
+
+fn patch_test_data(title: &str) -> PatchTestData {
+    PatchTestData {
+        title: title.to_string(),
+        views: 0,
+        note: Some("note".to_string()),
+        lock_version: 0,
+    }
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_patch_from_json() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<PatchTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = vec![patch_test_data("first"), patch_test_data("second")];
+    query!(&mut conn, INSERT INTO PatchTestTable VALUES {data}).await?;
+
+    let patch = Patch::<PatchTestTable>::from_json(json!({"views": 10, "note": null}))?;
+    assert_eq!(patch.columns().collect::<Vec<_>>(), vec!["views", "note"]);
+    query!(&mut conn, UPDATE PatchTestTable SET {patch} WHERE id = 1).await?;
+
+    let patched: PatchTestData = query!(&mut conn,
+        SELECT PatchTestData FROM PatchTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(
+        patched,
+        PatchTestData {
+            title: "first".to_string(),
+            views: 10,
+            note: None,
+            lock_version: 1,
+        }
+    );
+
+    let untouched: PatchTestData = query!(&mut conn,
+        SELECT PatchTestData FROM PatchTestTable WHERE id = 2
+    )
+    .await?;
+    assert_eq!(untouched, patch_test_data("second"));
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_patch_from_pairs_with_lock_version() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<PatchTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = patch_test_data("first");
+    query!(&mut conn, INSERT INTO PatchTestTable VALUES {data}).await?;
+
+    let mut pairs = std::collections::HashMap::new();
+    pairs.insert("title", json!("patched"));
+    pairs.insert("lock_version", json!(0));
+    let patch = Patch::<PatchTestTable>::from_pairs(pairs)?;
+    let stale_patch = patch.clone();
+    query!(&mut conn, UPDATE PatchTestTable SET {patch} WHERE id = 1).await?;
+
+    let patched: PatchTestData = query!(&mut conn,
+        SELECT PatchTestData FROM PatchTestTable WHERE id = 1
+    )
+    .await?;
+    assert_eq!(patched.title, "patched");
+    assert_eq!(patched.lock_version, 1);
+
+    // Same patch again, the lock version it was created with is stale now
+    let err = query!(&mut conn, UPDATE PatchTestTable SET {stale_patch} WHERE id = 1)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::StaleObject {
+            table: "patch_test_table".to_string(),
+        })
+    );
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_patch_invalid_columns() -> anyhow::Result<()> {
+    let invalid_patch = |column: &str, reason: &str| crate::Error::InvalidPatch {
+        table: "patch_test_table".to_string(),
+        column: column.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let err = Patch::<PatchTestTable>::from_json(json!({"missing": 1})).unwrap_err();
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&invalid_patch("missing", "unknown column"))
+    );
+
+    let err = Patch::<PatchTestTable>::from_json(json!({"views": "many"})).unwrap_err();
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::InvalidPatch { column, .. }) if column == "views"
+    ));
+
+    let err = Patch::<PatchTestTable>::from_json(json!({"title": null})).unwrap_err();
+    assert!(matches!(
+        crate::Error::from_anyhow(&err),
+        Some(crate::Error::InvalidPatch { column, .. }) if column == "title"
+    ));
+
+    assert!(Patch::<PatchTestTable>::from_json(json!([1, 2])).is_err());
+
+    let err = Patch::<PatchTestTable>::from_json(json!({"id": 5, "title": "x"}))?
+        .allow_only(&["title", "note"])
+        .unwrap_err();
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&invalid_patch("id", "column is not allowed"))
+    );
+
+    let mut patch = Patch::<PatchTestTable>::new();
+    patch.set("title", "x")?.set("note", Some("y"))?;
+    let patch = patch.allow_only(&["title", "note"])?;
+    assert_eq!(patch.columns().collect::<Vec<_>>(), vec!["title", "note"]);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_patch_soft_delete_column() -> anyhow::Result<()> {
+    // Rows are only soft deleted by DELETE
+    let err =
+        Patch::<PatchSoftDeleteTestTable>::from_json(json!({"deleted_at": null})).unwrap_err();
+    assert_eq!(
+        crate::Error::from_anyhow(&err),
+        Some(&crate::Error::InvalidPatch {
+            table: "patch_soft_delete_test_table".to_string(),
+            column: "deleted_at".to_string(),
+            reason: "unknown column".to_string(),
+        })
+    );
+
+    let patch = Patch::<PatchSoftDeleteTestTable>::from_json(json!({"title": "x"}))?;
+    assert_eq!(patch.columns().collect::<Vec<_>>(), vec!["title"]);
+
+    Ok(())
+}
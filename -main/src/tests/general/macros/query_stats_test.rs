@@ -0,0 +1,72 @@
+// Tests for QueryStats returned by INSERT, UPDATE and DELETE without RETURNING
+
+use super::*;
+use crate::{QueryStats, QueryStatsFuture};
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_query_stats_rows_affected() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = vec![
+        expr_test_data(10, "alpha", true, None),
+        expr_test_data(20, "beta", false, None),
+        expr_test_data(30, "gamma", true, None),
+    ];
+    let stats: QueryStats = query!(&mut conn, INSERT INTO ExprTestTable VALUES {data}).await?;
+    assert_eq!(stats.rows_affected, 3);
+
+    let stats = query!(&mut conn,
+        UPDATE ExprTestTable SET str_field = "updated" WHERE bool_field = true
+    )
+    .await?;
+    assert_eq!(stats.rows_affected, 2);
+
+    let updated = query!(&mut conn,
+        UPDATE ExprTestTable SET str_field = "none" WHERE int_field > 100
+    )
+    .rows_affected()
+    .await?;
+    assert_eq!(updated, 0);
+
+    let deleted: u64 = query!(&mut conn,
+        DELETE FROM ExprTestTable WHERE int_field >= 20
+    )
+    .rows_affected()
+    .await?;
+    assert_eq!(deleted, 2);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+#[cfg(feature = "sqlite")]
+async fn test_query_stats_last_insert_id() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    let data = expr_test_data(10, "alpha", true, None);
+    let id = query!(&mut conn, INSERT INTO ExprTestTable VALUES {data})
+        .last_insert_id()
+        .await?;
+    assert_eq!(id, Some(1));
+
+    let data = expr_test_data(20, "beta", false, None);
+    let id = query!(&mut conn, INSERT INTO ExprTestTable VALUES {data})
+        .last_insert_id()
+        .await?
+        .context("last_insert_id missing")?;
+    let id = i32::try_from(id)?;
+
+    let inserted: ExprTestData = query!(&mut conn,
+        SELECT ExprTestData FROM ExprTestTable WHERE id = {id}
+    )
+    .await?;
+    assert_eq!(inserted.int_field, 20);
+
+    conn.rollback().await?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use super::*;
+use crate::{DatabaseSetup, LoadRelated, load_related};
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = RelatedTestTable)]
+struct RelatedChild {
+    data: String,
+}
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = ExprTestTable)]
+struct RelatedParent {
+    id: i32,
+    str_field: String,
+    #[sql(related)]
+    children: Vec<RelatedChild>,
+}
+
+#[always_context(skip(!))]
+async fn related_test_setup(
+    conn: &mut (impl crate::EasyExecutor<TestDriver> + Send + Sync),
+) -> anyhow::Result<()> {
+    RelatedTestTable::setup(&mut *conn).await?;
+
+    insert_multiple_test_data(
+        &mut *conn,
+        vec![
+            expr_test_data(1, "first", true, None),
+            expr_test_data(2, "second", true, None),
+            expr_test_data(3, "childless", true, None),
+        ],
+    )
+    .await?;
+
+    let children = vec![
+        RelatedTestData {
+            parent_id: 1,
+            data: "a".to_string(),
+        },
+        RelatedTestData {
+            parent_id: 2,
+            data: "b".to_string(),
+        },
+        RelatedTestData {
+            parent_id: 1,
+            data: "c".to_string(),
+        },
+    ];
+    query!(&mut *conn, INSERT INTO RelatedTestTable VALUES {children}).await?;
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_load_related_map() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    related_test_setup(&mut conn).await?;
+
+    let mut related =
+        load_related::<RelatedTestTable, ExprTestTable, RelatedChild, _>(&mut conn, [1, 2, 3, 1])
+            .await?;
+
+    let mut first = related.remove(&1).unwrap_or_default();
+    first.sort_by(|a, b| a.data.cmp(&b.data));
+    assert_eq!(
+        first,
+        vec![
+            RelatedChild {
+                data: "a".to_string()
+            },
+            RelatedChild {
+                data: "c".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        related.remove(&2),
+        Some(vec![RelatedChild {
+            data: "b".to_string()
+        }])
+    );
+    assert!(related.is_empty());
+
+    let empty = load_related::<RelatedTestTable, ExprTestTable, RelatedChild, _>(
+        &mut conn,
+        Vec::<i32>::new(),
+    )
+    .await?;
+    assert!(empty.is_empty());
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_load_related_field() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+    related_test_setup(&mut conn).await?;
+
+    let mut parents: Vec<RelatedParent> = query!(&mut conn,
+        SELECT Vec<RelatedParent> FROM ExprTestTable WHERE true ORDER BY ExprTestTable.id
+    )
+    .await?;
+    assert!(parents.iter().all(|parent| parent.children.is_empty()));
+
+    RelatedParent::load_related(&mut conn, &mut parents).await?;
+
+    let children = parents
+        .iter()
+        .map(|parent| {
+            let mut data = parent
+                .children
+                .iter()
+                .map(|child| child.data.as_str())
+                .collect::<Vec<_>>();
+            data.sort();
+            (parent.str_field.as_str(), data)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        children,
+        vec![
+            ("first", vec!["a", "c"]),
+            ("second", vec!["b"]),
+            ("childless", vec![]),
+        ]
+    );
+
+    conn.rollback().await?;
+    Ok(())
+}
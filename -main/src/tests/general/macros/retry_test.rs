@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use super::*;
+use crate::{RetryPolicy, RetryableError};
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy {
+        max_attempts: 10,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+        multiplier: 2,
+    };
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(10));
+    assert_eq!(policy.backoff(2), Duration::from_millis(20));
+    assert_eq!(policy.backoff(3), Duration::from_millis(40));
+    assert_eq!(policy.backoff(4), Duration::from_millis(50));
+    assert_eq!(policy.backoff(100), Duration::from_millis(50));
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_retry_fatal_error_is_not_retried() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+
+    let mut attempts = 0;
+    let result: anyhow::Result<()> = db
+        .in_transaction_with_retry(&RetryPolicy::default(), tokio::time::sleep, async |tx| {
+            attempts += 1;
+            insert_test_data(&mut *tx, default_expr_test_data()).await?;
+            // Missing table is a fatal error
+            sqlx::query("SELECT * FROM easy_sql_missing_table")
+                .execute(&mut **tx)
+                .await?;
+            Ok(())
+        })
+        .await;
+
+    let err = result.unwrap_err();
+    assert!(!err.is_retryable::<TestDriver>());
+    assert_eq!(attempts, 1);
+
+    let mut conn = db.conn().await?;
+    let rows: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true
+    )
+    .await?;
+    assert!(rows.is_empty());
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_retry_success_commits() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+
+    let inserted = db
+        .in_transaction_with_retry(&RetryPolicy::default(), tokio::time::sleep, async |tx| {
+            insert_test_data(&mut *tx, default_expr_test_data()).await?;
+            Ok(1)
+        })
+        .await?;
+    assert_eq!(inserted, 1);
+
+    let mut conn = db.conn().await?;
+    let rows: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true
+    )
+    .await?;
+    assert_eq!(rows.len(), 1);
+
+    Ok(())
+}
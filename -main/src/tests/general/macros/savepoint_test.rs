@@ -0,0 +1,113 @@
+use super::*;
+
+#[always_context(skip(!))]
+async fn count_rows(conn: &mut impl crate::EasyExecutor<TestDriver>) -> anyhow::Result<usize> {
+    let rows: Vec<ExprTestData> = query!(*conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true
+    )
+    .await?;
+    Ok(rows.len())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_savepoint_release_and_rollback_to() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    insert_test_data(&mut conn, expr_test_data(1, "outer", true, None)).await?;
+
+    let mut savepoint = conn.savepoint().await?;
+    insert_test_data(&mut savepoint, expr_test_data(2, "released", true, None)).await?;
+    savepoint.release().await?;
+
+    let mut savepoint = conn.savepoint().await?;
+    insert_test_data(&mut savepoint, expr_test_data(3, "rolled back", true, None)).await?;
+    assert_eq!(count_rows(&mut savepoint).await?, 3);
+    savepoint.rollback_to().await?;
+
+    assert_eq!(count_rows(&mut conn).await?, 2);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_savepoint_dropped_is_rolled_back() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.transaction().await?;
+
+    {
+        let mut savepoint = conn.savepoint().await?;
+        insert_test_data(&mut savepoint, default_expr_test_data()).await?;
+    }
+
+    assert_eq!(count_rows(&mut conn).await?, 0);
+
+    conn.rollback().await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_in_transaction_commit_and_rollback() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+
+    db.in_transaction(async |tx| {
+        insert_test_data(&mut *tx, expr_test_data(1, "committed", true, None)).await?;
+        Ok(())
+    })
+    .await?;
+
+    let result: anyhow::Result<()> = db
+        .in_transaction(async |tx| {
+            insert_test_data(&mut *tx, expr_test_data(2, "rolled back", true, None)).await?;
+            anyhow::bail!("Rollback requested")
+        })
+        .await;
+    assert!(result.is_err());
+
+    let mut conn = db.conn().await?;
+    assert_eq!(count_rows(&mut conn).await?, 1);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_nested_in_transaction_uses_savepoints() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+
+    db.in_transaction(async |tx| {
+        insert_test_data(&mut *tx, expr_test_data(1, "outer", true, None)).await?;
+
+        let nested: anyhow::Result<()> = tx
+            .in_transaction(async |savepoint| {
+                insert_test_data(&mut *savepoint, expr_test_data(2, "inner", true, None)).await?;
+                anyhow::bail!("Inner rollback requested")
+            })
+            .await;
+        assert!(nested.is_err());
+
+        tx.in_transaction(async |savepoint| {
+            insert_test_data(&mut *savepoint, expr_test_data(3, "inner kept", true, None)).await?;
+            Ok(())
+        })
+        .await?;
+
+        Ok(())
+    })
+    .await?;
+
+    let mut conn = db.conn().await?;
+    let rows: Vec<ExprTestData> = query!(&mut conn,
+        SELECT Vec<ExprTestData> FROM ExprTestTable WHERE true ORDER BY ExprTestTable.id
+    )
+    .await?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].str_field, "outer");
+    assert_eq!(rows[1].str_field, "inner kept");
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+#[always_context(skip(!))]
+async fn scalar_test_setup() -> anyhow::Result<Database> {
+    let db = Database::setup_for_testing::<ExprTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(10, "first", true, None),
+            expr_test_data(20, "second", false, Some("note")),
+            expr_test_data(30, "third", true, None),
+        ],
+    )
+    .await?;
+
+    Ok(db)
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_scalar_output() -> anyhow::Result<()> {
+    let db = scalar_test_setup().await?;
+    let mut conn = db.conn().await?;
+
+    let count: i64 = query!(&mut conn, SELECT i64 = COUNT(*) FROM ExprTestTable WHERE true).await?;
+    assert_eq!(count, 3);
+
+    let min_int = 15;
+    let names: Vec<String> = query!(&mut conn,
+        SELECT Vec<String> = str_field FROM ExprTestTable
+        WHERE int_field > {min_int}
+        ORDER BY int_field
+    )
+    .await?;
+    assert_eq!(names, vec!["second".to_string(), "third".to_string()]);
+
+    let missing: Option<i32> = query!(&mut conn,
+        SELECT Option<i32> = int_field FROM ExprTestTable WHERE str_field = "missing"
+    )
+    .await?;
+    assert_eq!(missing, None);
+
+    let nullable: Option<Option<String>> = query!(&mut conn,
+        SELECT Option<Option<String>> = nullable_field FROM ExprTestTable WHERE str_field = "first"
+    )
+    .await?;
+    assert_eq!(nullable, Some(None));
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_tuple_output() -> anyhow::Result<()> {
+    let db = scalar_test_setup().await?;
+    let mut conn = db.conn().await?;
+
+    let rows: Vec<(i32, String)> = query!(&mut conn,
+        SELECT Vec<(i32, String)> = (int_field, str_field) FROM ExprTestTable
+        WHERE bool_field = true
+        ORDER BY int_field
+    )
+    .await?;
+    assert_eq!(
+        rows,
+        vec![(10, "first".to_string()), (30, "third".to_string())]
+    );
+
+    let (total, count): (i64, i64) = query!(&mut conn,
+        SELECT (i64, i64) = (SUM(int_field), COUNT(id)) FROM ExprTestTable WHERE true
+    )
+    .await?;
+    assert_eq!((total, count), (60, 3));
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use super::*;
+use crate::DatabaseSetup;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(schema = "billing")]
+struct SchemaTestInvoice {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    customer: String,
+    amount: i64,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SchemaTestInvoice)]
+#[sql(default = id)]
+struct SchemaTestInvoiceData {
+    customer: String,
+    amount: i64,
+}
+
+/// Same table name as `SchemaTestInvoice`, but in the default schema
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(table_name = "schema_test_invoice")]
+struct SchemaTestPublicInvoice {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    customer: String,
+    amount: i64,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SchemaTestPublicInvoice)]
+#[sql(default = id)]
+struct SchemaTestPublicInvoiceData {
+    customer: String,
+    amount: i64,
+}
+
+#[derive(DatabaseSetup)]
+struct SchemaTestSchema {
+    billing: SchemaTestInvoice,
+    public: SchemaTestPublicInvoice,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_schema_table_queries() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SchemaTestInvoice>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        SchemaTestInvoiceData {
+            customer: "alice".to_string(),
+            amount: 100,
+        },
+        SchemaTestInvoiceData {
+            customer: "bob".to_string(),
+            amount: 50,
+        },
+    ];
+    query!(&mut conn, INSERT INTO SchemaTestInvoice VALUES {data}).await?;
+
+    query!(&mut conn, UPDATE SchemaTestInvoice SET amount = 75 WHERE customer = "bob").await?;
+    query!(&mut conn, DELETE FROM SchemaTestInvoice WHERE customer = "alice").await?;
+
+    let results: Vec<SchemaTestInvoiceData> = query!(&mut conn,
+        SELECT Vec<SchemaTestInvoiceData> FROM SchemaTestInvoice WHERE SchemaTestInvoice.amount > 0
+    )
+    .await?;
+    assert_eq!(
+        results,
+        vec![SchemaTestInvoiceData {
+            customer: "bob".to_string(),
+            amount: 75,
+        }]
+    );
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_schema_tables_with_same_name() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SchemaTestSchema>().await?;
+    let mut conn = db.conn().await?;
+
+    let billing = SchemaTestInvoiceData {
+        customer: "alice".to_string(),
+        amount: 100,
+    };
+    query!(&mut conn, INSERT INTO SchemaTestInvoice VALUES {&billing}).await?;
+
+    let public = SchemaTestPublicInvoiceData {
+        customer: "bob".to_string(),
+        amount: 20,
+    };
+    query!(&mut conn, INSERT INTO SchemaTestPublicInvoice VALUES {&public}).await?;
+
+    let billing_results: Vec<SchemaTestInvoiceData> = query!(&mut conn,
+        SELECT Vec<SchemaTestInvoiceData> FROM SchemaTestInvoice WHERE true
+    )
+    .await?;
+    assert_eq!(billing_results, vec![billing]);
+
+    let public_results: Vec<SchemaTestPublicInvoiceData> = query!(&mut conn,
+        SELECT Vec<SchemaTestPublicInvoiceData> FROM SchemaTestPublicInvoice WHERE true
+    )
+    .await?;
+    assert_eq!(public_results, vec![public]);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_default_schema() -> anyhow::Result<()> {
+    use crate::EasyExecutor;
+
+    let options = Database::test_connect_options().await?;
+    let db =
+        Database::setup_with_default_schema::<SchemaTestPublicInvoice>(options, "tenant").await?;
+    let mut conn = db.conn().await?;
+
+    let data = SchemaTestPublicInvoiceData {
+        customer: "carol".to_string(),
+        amount: 30,
+    };
+    query!(&mut conn, INSERT INTO SchemaTestPublicInvoice VALUES {&data}).await?;
+
+    let results: Vec<SchemaTestPublicInvoiceData> = query!(&mut conn,
+        SELECT Vec<SchemaTestPublicInvoiceData> FROM SchemaTestPublicInvoice WHERE true
+    )
+    .await?;
+    assert_eq!(results, vec![data]);
+
+    let in_tenant: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT FROM information_schema.tables WHERE table_schema = 'tenant' AND table_name = 'schema_test_invoice')",
+    )
+    .fetch_one(conn.executor())
+    .await?;
+    assert!(in_tenant);
+
+    Ok(())
+}
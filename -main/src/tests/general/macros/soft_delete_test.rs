@@ -0,0 +1,172 @@
+use super::*;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(soft_delete)]
+struct SoftDeleteTestTable {
+    #[sql(primary_key)]
+    id: i32,
+    name: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SoftDeleteTestTable)]
+struct SoftDeleteTestData {
+    id: i32,
+    name: String,
+}
+
+#[always_context(skip(!))]
+async fn insert_soft_delete_test_data(
+    conn: &mut impl crate::EasyExecutor<TestDriver>,
+) -> anyhow::Result<()> {
+    let data = vec![
+        SoftDeleteTestData {
+            id: 1,
+            name: "alice".to_string(),
+        },
+        SoftDeleteTestData {
+            id: 2,
+            name: "bob".to_string(),
+        },
+    ];
+    query!(*conn, INSERT INTO SoftDeleteTestTable VALUES {data}).await?;
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_soft_delete_hides_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SoftDeleteTestTable>().await?;
+    let mut conn = db.conn().await?;
+    insert_soft_delete_test_data(&mut conn).await?;
+
+    query!(&mut conn, DELETE FROM SoftDeleteTestTable WHERE id = 1).await?;
+
+    let results: Vec<SoftDeleteTestData> = query!(&mut conn,
+        SELECT Vec<SoftDeleteTestData> FROM SoftDeleteTestTable
+    )
+    .await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "bob");
+
+    let exists: bool = query!(&mut conn, EXISTS SoftDeleteTestTable WHERE id = 1).await?;
+    assert!(!exists);
+
+    let all: Vec<SoftDeleteTestTable> = query!(&mut conn,
+        SELECT Vec<SoftDeleteTestTable> FROM SoftDeleteTestTable WITH DELETED ORDER BY id
+    )
+    .await?;
+    assert_eq!(all.len(), 2);
+    assert!(all[0].deleted_at.is_some());
+    assert!(all[1].deleted_at.is_none());
+
+    let exists: bool = query!(&mut conn,
+        EXISTS SoftDeleteTestTable WITH DELETED WHERE id = 1
+    )
+    .await?;
+    assert!(exists);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_soft_delete_update_skips_deleted_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SoftDeleteTestTable>().await?;
+    let mut conn = db.conn().await?;
+    insert_soft_delete_test_data(&mut conn).await?;
+
+    query!(&mut conn, DELETE FROM SoftDeleteTestTable WHERE id = 1).await?;
+    query!(&mut conn, UPDATE SoftDeleteTestTable SET name = "renamed").await?;
+
+    let all: Vec<SoftDeleteTestData> = query!(&mut conn,
+        SELECT Vec<SoftDeleteTestData> FROM SoftDeleteTestTable WITH DELETED ORDER BY id
+    )
+    .await?;
+    assert_eq!(all[0].name, "alice");
+    assert_eq!(all[1].name, "renamed");
+
+    query!(&mut conn,
+        UPDATE SoftDeleteTestTable WITH DELETED SET name = "restored" WHERE id = 1
+    )
+    .await?;
+    let restored: SoftDeleteTestData = query!(&mut conn,
+        SELECT SoftDeleteTestData FROM SoftDeleteTestTable WITH DELETED WHERE id = 1
+    )
+    .await?;
+    assert_eq!(restored.name, "restored");
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_hard_delete_removes_rows() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SoftDeleteTestTable>().await?;
+    let mut conn = db.conn().await?;
+    insert_soft_delete_test_data(&mut conn).await?;
+
+    query!(&mut conn, DELETE FROM SoftDeleteTestTable WHERE id = 1).await?;
+    query!(&mut conn, HARD DELETE FROM SoftDeleteTestTable WHERE id = 1).await?;
+    query!(&mut conn, HARD DELETE FROM SoftDeleteTestTable WHERE id = 2).await?;
+
+    let exists: bool = query!(&mut conn, EXISTS SoftDeleteTestTable WITH DELETED).await?;
+    assert!(!exists);
+
+    Ok(())
+}
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(soft_delete = removed_at)]
+struct SoftDeleteColumnTestTable {
+    #[sql(primary_key)]
+    id: i32,
+    name: String,
+    removed_at: Option<String>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SoftDeleteColumnTestTable)]
+struct SoftDeleteColumnTestData {
+    id: i32,
+    name: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_soft_delete_custom_column() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SoftDeleteColumnTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        SoftDeleteColumnTestData {
+            id: 1,
+            name: "alice".to_string(),
+        },
+        SoftDeleteColumnTestData {
+            id: 2,
+            name: "bob".to_string(),
+        },
+    ];
+    query!(&mut conn, INSERT INTO SoftDeleteColumnTestTable VALUES {data}).await?;
+
+    query!(&mut conn, DELETE FROM SoftDeleteColumnTestTable WHERE id = 1).await?;
+
+    let results: Vec<SoftDeleteColumnTestData> = query!(&mut conn,
+        SELECT Vec<SoftDeleteColumnTestData> FROM SoftDeleteColumnTestTable
+    )
+    .await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "bob");
+
+    let deleted: SoftDeleteColumnTestTable = query!(&mut conn,
+        SELECT SoftDeleteColumnTestTable FROM SoftDeleteColumnTestTable WITH DELETED WHERE id = 1
+    )
+    .await?;
+    assert!(deleted.removed_at.is_some());
+
+    Ok(())
+}
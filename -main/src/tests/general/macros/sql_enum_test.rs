@@ -0,0 +1,180 @@
+use super::*;
+use crate::SqlEnum;
+
+#[derive(SqlEnum, Debug, Clone, Copy, PartialEq)]
+enum SqlEnumTestStatus {
+    Draft,
+    #[sql(rename = "live")]
+    Published,
+    ArchivedForever,
+}
+
+#[derive(SqlEnum, Debug, Clone, Copy, PartialEq)]
+#[sql(repr = "i32")]
+enum SqlEnumTestPriority {
+    Low = 1,
+    Normal = 5,
+    High = 10,
+}
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct SqlEnumTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    status: SqlEnumTestStatus,
+    #[sql(default = SqlEnumTestPriority::Normal)]
+    priority: SqlEnumTestPriority,
+    previous_status: Option<SqlEnumTestStatus>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SqlEnumTestTable)]
+#[sql(default = id, priority)]
+struct SqlEnumTestData {
+    status: SqlEnumTestStatus,
+    previous_status: Option<SqlEnumTestStatus>,
+}
+
+#[derive(Output, Debug, Clone, PartialEq)]
+#[sql(table = SqlEnumTestTable)]
+struct SqlEnumTestPriorityData {
+    status: SqlEnumTestStatus,
+    priority: SqlEnumTestPriority,
+}
+
+#[test]
+fn test_sql_enum_values() {
+    assert_eq!(SqlEnumTestStatus::Draft.__easy_sql_enum_value(), "draft");
+    assert_eq!(SqlEnumTestStatus::Published.__easy_sql_enum_value(), "live");
+    assert_eq!(
+        SqlEnumTestStatus::ArchivedForever.__easy_sql_enum_value(),
+        "archived_forever"
+    );
+    assert_eq!(
+        SqlEnumTestStatus::__easy_sql_enum_from_value("live"),
+        Some(SqlEnumTestStatus::Published)
+    );
+    assert_eq!(
+        SqlEnumTestStatus::__easy_sql_enum_from_value("published"),
+        None
+    );
+
+    assert_eq!(SqlEnumTestPriority::High.__easy_sql_enum_value(), 10);
+    assert_eq!(
+        SqlEnumTestPriority::__easy_sql_enum_from_value(5),
+        Some(SqlEnumTestPriority::Normal)
+    );
+    assert_eq!(SqlEnumTestPriority::__easy_sql_enum_from_value(2), None);
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_sql_enum_roundtrip() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SqlEnumTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        SqlEnumTestData {
+            status: SqlEnumTestStatus::Draft,
+            previous_status: None,
+        },
+        SqlEnumTestData {
+            status: SqlEnumTestStatus::Published,
+            previous_status: Some(SqlEnumTestStatus::Draft),
+        },
+    ];
+    query!(&mut conn, INSERT INTO SqlEnumTestTable VALUES {data}).await?;
+
+    let status = SqlEnumTestStatus::Published;
+    let published: SqlEnumTestData = query!(&mut conn,
+        SELECT SqlEnumTestData FROM SqlEnumTestTable WHERE SqlEnumTestTable.status = {status}
+    )
+    .await?;
+    assert_eq!(published.previous_status, Some(SqlEnumTestStatus::Draft));
+
+    // Default value of the enum column
+    let rows: Vec<SqlEnumTestPriorityData> = query!(&mut conn,
+        SELECT Vec<SqlEnumTestPriorityData> FROM SqlEnumTestTable WHERE true
+    )
+    .await?;
+    assert_eq!(rows.len(), 2);
+    assert!(
+        rows.iter()
+            .all(|row| row.priority == SqlEnumTestPriority::Normal)
+    );
+
+    let priority = SqlEnumTestPriority::High;
+    query!(&mut conn,
+        UPDATE SqlEnumTestTable SET priority = {priority} WHERE SqlEnumTestTable.status = {status}
+    )
+    .await?;
+    let high: SqlEnumTestPriorityData = query!(&mut conn,
+        SELECT SqlEnumTestPriorityData FROM SqlEnumTestTable WHERE SqlEnumTestTable.priority = {priority}
+    )
+    .await?;
+    assert_eq!(high.status, SqlEnumTestStatus::Published);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "postgres", not(feature = "sqlite")))]
+mod native {
+    use super::*;
+    use crate::DatabaseSetup;
+
+    #[derive(SqlEnum, Debug, Clone, Copy, PartialEq)]
+    #[sql(native)]
+    #[sql(type_name = "sql_enum_test_mood")]
+    enum SqlEnumTestMood {
+        Happy,
+        Sad,
+    }
+
+    #[derive(Table, Debug, Clone)]
+    #[sql(no_version)]
+    struct SqlEnumNativeTestTable {
+        #[sql(primary_key)]
+        #[sql(auto_increment)]
+        id: i32,
+        mood: SqlEnumTestMood,
+    }
+
+    #[derive(Insert, Output, Debug, Clone, PartialEq)]
+    #[sql(table = SqlEnumNativeTestTable)]
+    #[sql(default = id)]
+    struct SqlEnumNativeTestData {
+        mood: SqlEnumTestMood,
+    }
+
+    #[derive(DatabaseSetup)]
+    struct SqlEnumNativeTestSchema {
+        mood: SqlEnumTestMood,
+        table: SqlEnumNativeTestTable,
+    }
+
+    #[always_context(skip(!))]
+    #[tokio::test]
+    async fn test_native_sql_enum() -> anyhow::Result<()> {
+        let db = Database::setup_for_testing::<SqlEnumNativeTestSchema>().await?;
+        let mut conn = db.conn().await?;
+
+        // Setup of an existing type only adds missing variants
+        <SqlEnumTestMood as DatabaseSetup<TestDriver>>::setup(&mut conn).await?;
+
+        let data = SqlEnumNativeTestData {
+            mood: SqlEnumTestMood::Sad,
+        };
+        query!(&mut conn, INSERT INTO SqlEnumNativeTestTable VALUES {data}).await?;
+
+        let mood = SqlEnumTestMood::Sad;
+        let row: SqlEnumNativeTestData = query!(&mut conn,
+            SELECT SqlEnumNativeTestData FROM SqlEnumNativeTestTable WHERE SqlEnumNativeTestTable.mood = {mood}
+        )
+        .await?;
+        assert_eq!(row.mood, SqlEnumTestMood::Sad);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use super::*;
+use crate::{SqlType, ToDefault};
+
+#[derive(SqlType, Debug, Clone, Copy, PartialEq)]
+struct SqlTypeTestUserId(i64);
+
+#[derive(SqlType, Debug, Clone, PartialEq)]
+struct SqlTypeTestName {
+    value: String,
+}
+
+/// Stored as `user@domain` text
+#[derive(SqlType, Debug, Clone, PartialEq)]
+#[sql(via = String, from = parse, into = to_string)]
+struct SqlTypeTestEmail {
+    user: String,
+    domain: String,
+}
+
+impl std::fmt::Display for SqlTypeTestEmail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.user, self.domain)
+    }
+}
+
+impl std::str::FromStr for SqlTypeTestEmail {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (user, domain) = value
+            .split_once('@')
+            .ok_or_else(|| format!("`{value}` is not an email address"))?;
+        Ok(SqlTypeTestEmail {
+            user: user.to_lowercase(),
+            domain: domain.to_lowercase(),
+        })
+    }
+}
+
+fn email(value: &str) -> SqlTypeTestEmail {
+    value.parse().unwrap()
+}
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct SqlTypeTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    #[sql(default = SqlTypeTestUserId(7))]
+    user_id: SqlTypeTestUserId,
+    name: SqlTypeTestName,
+    email: Option<SqlTypeTestEmail>,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SqlTypeTestTable)]
+#[sql(default = id)]
+struct SqlTypeTestData {
+    user_id: SqlTypeTestUserId,
+    name: SqlTypeTestName,
+    email: Option<SqlTypeTestEmail>,
+}
+
+#[test]
+fn test_sql_type_repr() {
+    assert_eq!(SqlTypeTestUserId(5).to_repr(), 5);
+    assert_eq!(
+        SqlTypeTestName::from_repr("name".to_string()).unwrap(),
+        SqlTypeTestName {
+            value: "name".to_string()
+        }
+    );
+    assert_eq!(
+        email("User@Example.com").to_repr(),
+        "user@example.com".to_string()
+    );
+    assert!(SqlTypeTestEmail::from_repr("invalid".to_string()).is_err());
+    assert_eq!(
+        <SqlTypeTestUserId as ToDefault<TestDriver>>::to_default(SqlTypeTestUserId(7)),
+        <i64 as ToDefault<TestDriver>>::to_default(7)
+    );
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_sql_type_roundtrip() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<SqlTypeTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        SqlTypeTestData {
+            user_id: SqlTypeTestUserId(1),
+            name: SqlTypeTestName {
+                value: "first".to_string(),
+            },
+            email: Some(email("First@Example.com")),
+        },
+        SqlTypeTestData {
+            user_id: SqlTypeTestUserId(2),
+            name: SqlTypeTestName {
+                value: "second".to_string(),
+            },
+            email: None,
+        },
+    ];
+    query!(&mut conn, INSERT INTO SqlTypeTestTable VALUES {&data}).await?;
+
+    let user_id = SqlTypeTestUserId(1);
+    let first: SqlTypeTestData = query!(&mut conn,
+        SELECT SqlTypeTestData FROM SqlTypeTestTable WHERE user_id = {user_id}
+    )
+    .await?;
+    assert_eq!(first, data[0]);
+
+    let first_email = email("first@example.com");
+    let by_email: SqlTypeTestData = query!(&mut conn,
+        SELECT SqlTypeTestData FROM SqlTypeTestTable WHERE email = {first_email}
+    )
+    .await?;
+    assert_eq!(by_email.user_id, SqlTypeTestUserId(1));
+
+    let user_ids = vec![
+        SqlTypeTestUserId(1),
+        SqlTypeTestUserId(2),
+        SqlTypeTestUserId(3),
+    ];
+    let rows: Vec<SqlTypeTestData> = query!(&mut conn,
+        SELECT Vec<SqlTypeTestData> FROM SqlTypeTestTable WHERE user_id IN {user_ids} ORDER BY id
+    )
+    .await?;
+    assert_eq!(rows, data);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_sql_type_column_types() -> anyhow::Result<()> {
+    use crate::TableMetadata;
+
+    let meta = <SqlTypeTestTable as TableMetadata<TestDriver>>::table_meta()?;
+    let sql_type = |name: &str| {
+        meta.column(name)
+            .map(|column| column.sql_type.clone())
+            .unwrap_or_default()
+    };
+    assert_eq!(sql_type("user_id"), sql_type_name::<i64>());
+    assert_eq!(sql_type("name"), sql_type_name::<String>());
+    assert_eq!(sql_type("email"), sql_type_name::<String>());
+    assert!(
+        meta.column("user_id")
+            .is_some_and(|column| column.default.is_some())
+    );
+
+    Ok(())
+}
+
+fn sql_type_name<
+    T: crate::macro_support::Type<crate::macro_support::InternalDriver<TestDriver>>,
+>() -> String {
+    crate::macro_support::TypeInfo::name(&T::type_info()).to_owned()
+}
@@ -0,0 +1,59 @@
+use super::*;
+use crate::drivers::sqlite::SqliteConnectOptions;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(schema = "billing")]
+struct SqliteSchemaTestInvoice {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    customer: String,
+    amount: i64,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = SqliteSchemaTestInvoice)]
+#[sql(default = id)]
+struct SqliteSchemaTestInvoiceData {
+    customer: String,
+    amount: i64,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_attached_database_tables() -> anyhow::Result<()> {
+    let current_path = std::env::current_dir()?;
+    let main_path = current_path.join("test_db_attached_main");
+    let billing_path = current_path.join("test_db_attached_billing");
+    let options = SqliteConnectOptions::default()
+        .filename(&main_path)
+        .create_if_missing(true);
+    let db = Database::setup_with_attached::<SqliteSchemaTestInvoice>(
+        options,
+        &[("billing", billing_path.as_path())],
+    )
+    .await?;
+
+    // Both connections are attached, the second one is opened by the pool after the setup
+    let mut first = db.conn().await?;
+    let mut second = db.conn().await?;
+
+    let data = SqliteSchemaTestInvoiceData {
+        customer: "alice".to_string(),
+        amount: 100,
+    };
+    query!(&mut first, INSERT INTO SqliteSchemaTestInvoice VALUES {&data}).await?;
+
+    let results: Vec<SqliteSchemaTestInvoiceData> = query!(&mut second,
+        SELECT Vec<SqliteSchemaTestInvoiceData> FROM SqliteSchemaTestInvoice WHERE true
+    )
+    .await?;
+    assert_eq!(results, vec![data]);
+
+    drop((first, second));
+    drop(db);
+    let _ = std::fs::remove_file(&billing_path);
+
+    Ok(())
+}
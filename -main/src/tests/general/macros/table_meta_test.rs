@@ -0,0 +1,66 @@
+use super::*;
+use crate::{ForeignKeyMeta, TableMetadata};
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct TableMetaTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    #[sql(unique)]
+    name: String,
+    #[sql(default = 5)]
+    score: i64,
+    #[sql(foreign_key = ExprTestTable, cascade)]
+    parent_id: Option<i32>,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_table_meta_columns() -> anyhow::Result<()> {
+    let meta = <TableMetaTestTable as TableMetadata<TestDriver>>::table_meta()?;
+    assert_eq!(meta.name, "table_meta_test_table");
+    assert_eq!(meta.primary_keys, vec!["id"]);
+
+    let names = meta
+        .columns()
+        .iter()
+        .map(|column| column.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["id", "name", "score", "parent_id"]);
+
+    let id = meta.column("id").context("id column missing")?;
+    assert!(id.primary_key && id.auto_increment && !id.nullable);
+    assert_eq!(id.rust_type, "i32");
+
+    let name = meta.column("name").context("name column missing")?;
+    assert!(name.unique && !name.primary_key);
+    assert_eq!(name.rust_type, std::any::type_name::<String>());
+    assert_eq!(name.default, None);
+    assert!(!name.sql_type.is_empty());
+
+    let score = meta.column("score").context("score column missing")?;
+    assert_eq!(score.default.as_deref(), Some("5"));
+    assert!(score.foreign_keys.is_empty());
+
+    let parent_id = meta
+        .column("parent_id")
+        .context("parent_id column missing")?;
+    assert!(parent_id.nullable);
+    assert_eq!(
+        parent_id.foreign_keys,
+        vec![ForeignKeyMeta {
+            table: "expr_test_table",
+            column: "id",
+            cascade: true,
+        }]
+    );
+
+    assert!(meta.column("missing").is_none());
+
+    // Built only once
+    let again = <TableMetaTestTable as TableMetadata<TestDriver>>::table_meta()?;
+    assert!(std::ptr::eq(meta, again));
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use super::*;
+use chrono::NaiveDateTime;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+struct TimestampTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    name: String,
+    #[sql(created_at)]
+    created_at: NaiveDateTime,
+    #[sql(updated_at)]
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Insert, Debug, Clone)]
+#[sql(table = TimestampTestTable)]
+#[sql(default = id)]
+struct TimestampTestInsert {
+    name: String,
+}
+
+#[derive(Update, Debug, Clone)]
+#[sql(table = TimestampTestTable)]
+struct TimestampTestRename {
+    name: String,
+}
+
+#[derive(Output, Debug, Clone, PartialEq)]
+#[sql(table = TimestampTestTable)]
+struct TimestampTestData {
+    name: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_timestamps_set_on_insert() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<TimestampTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = TimestampTestInsert {
+        name: "alice".to_string(),
+    };
+    query!(&mut conn, INSERT INTO TimestampTestTable VALUES {data}).await?;
+
+    let result: TimestampTestData = query!(&mut conn,
+        SELECT TimestampTestData FROM TimestampTestTable WHERE name = "alice"
+    )
+    .await?;
+    assert_eq!(result.created_at, result.updated_at);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_updated_at_changes_on_update() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<TimestampTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = TimestampTestInsert {
+        name: "alice".to_string(),
+    };
+    query!(&mut conn, INSERT INTO TimestampTestTable VALUES {data}).await?;
+
+    let before: TimestampTestData = query!(&mut conn,
+        SELECT TimestampTestData FROM TimestampTestTable WHERE name = "alice"
+    )
+    .await?;
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    let update = TimestampTestRename {
+        name: "bob".to_string(),
+    };
+    query!(&mut conn, UPDATE TimestampTestTable SET {update} WHERE name = "alice").await?;
+
+    let after: TimestampTestData = query!(&mut conn,
+        SELECT TimestampTestData FROM TimestampTestTable WHERE name = "bob"
+    )
+    .await?;
+    assert_eq!(after.created_at, before.created_at);
+    assert!(after.updated_at > before.updated_at);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_updated_at_changes_on_inline_set() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<TimestampTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = TimestampTestInsert {
+        name: "alice".to_string(),
+    };
+    query!(&mut conn, INSERT INTO TimestampTestTable VALUES {data}).await?;
+
+    let before: TimestampTestData = query!(&mut conn,
+        SELECT TimestampTestData FROM TimestampTestTable WHERE name = "alice"
+    )
+    .await?;
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    query!(&mut conn, UPDATE TimestampTestTable SET name = "carol" WHERE name = "alice").await?;
+
+    let after: TimestampTestData = query!(&mut conn,
+        SELECT TimestampTestData FROM TimestampTestTable WHERE name = "carol"
+    )
+    .await?;
+    assert_eq!(after.created_at, before.created_at);
+    assert!(after.updated_at > before.updated_at);
+
+    Ok(())
+}
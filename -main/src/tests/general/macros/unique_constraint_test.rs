@@ -0,0 +1,78 @@
+use super::*;
+
+#[derive(Table, Debug, Clone)]
+#[sql(no_version)]
+#[sql(unique(owner, slug))]
+#[sql(unique(owner, position, name = "unique_test_owner_position"))]
+struct UniqueConstraintTestTable {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    owner: String,
+    slug: String,
+    position: i32,
+}
+
+#[derive(Insert, Output, Debug, Clone, PartialEq)]
+#[sql(table = UniqueConstraintTestTable)]
+#[sql(default = id)]
+struct UniqueConstraintTestData {
+    owner: String,
+    slug: String,
+    position: i32,
+}
+
+fn unique_test_data(owner: &str, slug: &str, position: i32) -> UniqueConstraintTestData {
+    UniqueConstraintTestData {
+        owner: owner.to_string(),
+        slug: slug.to_string(),
+        position,
+    }
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_composite_unique_allows_partial_duplicates() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<UniqueConstraintTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = vec![
+        unique_test_data("alice", "post", 1),
+        unique_test_data("alice", "note", 2),
+        unique_test_data("bob", "post", 1),
+    ];
+    query!(&mut conn, INSERT INTO UniqueConstraintTestTable VALUES {data}).await?;
+
+    let rows: Vec<UniqueConstraintTestData> = query!(&mut conn,
+        SELECT Vec<UniqueConstraintTestData> FROM UniqueConstraintTestTable WHERE true
+    )
+    .await?;
+    assert_eq!(rows.len(), 3);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_composite_unique_rejects_duplicates() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<UniqueConstraintTestTable>().await?;
+    let mut conn = db.conn().await?;
+
+    let data = unique_test_data("alice", "post", 1);
+    query!(&mut conn, INSERT INTO UniqueConstraintTestTable VALUES {data}).await?;
+
+    for duplicate in [
+        unique_test_data("alice", "post", 2),
+        unique_test_data("alice", "other", 1),
+    ] {
+        let err = query!(&mut conn, INSERT INTO UniqueConstraintTestTable VALUES {duplicate})
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            crate::Error::from_anyhow(&err),
+            Some(crate::Error::UniqueViolation { .. })
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use super::*;
+use crate::{DatabaseSetup, View};
+
+#[derive(Output, Debug, Clone)]
+#[sql(table = ExprTestTable)]
+struct ViewTestActiveOutput {
+    id: i32,
+    str_field: String,
+}
+
+#[derive(View, Debug, Clone, PartialEq)]
+#[sql(query = SELECT ViewTestActiveOutput FROM ExprTestTable WHERE bool_field = true)]
+struct ViewTestActive {
+    id: i32,
+    name: String,
+}
+
+#[derive(Output, Debug, Clone)]
+#[sql(table = ExprTestTable)]
+struct ViewTestCountOutput {
+    bool_field: bool,
+    #[sql(select = COUNT(id))]
+    row_count: i64,
+}
+
+#[derive(View, Debug, Clone, PartialEq)]
+#[sql(query = SELECT ViewTestCountOutput FROM ExprTestTable GROUP BY bool_field)]
+#[sql(table_name = "view_test_counts")]
+struct ViewTestCount {
+    bool_field: bool,
+    row_count: i64,
+}
+
+#[derive(DatabaseSetup)]
+struct ViewTestSchema {
+    table: ExprTestTable,
+    active: ViewTestActive,
+    counts: ViewTestCount,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_view_select() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ViewTestSchema>().await?;
+    let mut conn = db.conn().await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(1, "first", true, None),
+            expr_test_data(2, "second", false, None),
+            expr_test_data(3, "third", true, None),
+        ],
+    )
+    .await?;
+
+    let active: Vec<ViewTestActive> = query!(&mut conn,
+        SELECT Vec<ViewTestActive> FROM ViewTestActive WHERE true ORDER BY name
+    )
+    .await?;
+    assert_eq!(active.len(), 2);
+    assert_eq!(active[0].name, "first");
+    assert_eq!(active[1].name, "third");
+
+    let third: ViewTestActive = query!(&mut conn,
+        SELECT ViewTestActive FROM ViewTestActive WHERE name = "third"
+    )
+    .await?;
+    assert_eq!(third, active[1]);
+
+    Ok(())
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_view_group_by_and_repeated_setup() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<ViewTestSchema>().await?;
+    let mut conn = db.conn().await?;
+
+    // Setup of existing views keeps them working
+    <ViewTestSchema as DatabaseSetup<TestDriver>>::setup(&mut conn).await?;
+
+    insert_multiple_test_data(
+        &mut conn,
+        vec![
+            expr_test_data(1, "first", true, None),
+            expr_test_data(2, "second", false, None),
+            expr_test_data(3, "third", true, None),
+        ],
+    )
+    .await?;
+
+    let counts: Vec<ViewTestCount> = query!(&mut conn,
+        SELECT Vec<ViewTestCount> FROM ViewTestCount WHERE true ORDER BY bool_field
+    )
+    .await?;
+    assert_eq!(
+        counts,
+        vec![
+            ViewTestCount {
+                bool_field: false,
+                row_count: 1,
+            },
+            ViewTestCount {
+                bool_field: true,
+                row_count: 2,
+            },
+        ]
+    );
+
+    Ok(())
+}
@@ -465,3 +465,239 @@ async fn test_migration_v1_to_v3_to_v6_with_update() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[derive(Table, Debug)]
+#[sql(version_test = 1)]
+#[sql(unique_id = "6d2f4a81-3b9e-4c07-a5d8-1e7f2b9c4a36")]
+#[sql(table_name = "migration_index_test_table")]
+struct MigrationIndexTestTableV1 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    email: String,
+}
+
+/// Add unique index on 'email'
+#[derive(Table, Debug)]
+#[sql(version_test = 2)]
+#[sql(unique_id = "6d2f4a81-3b9e-4c07-a5d8-1e7f2b9c4a36")]
+#[sql(table_name = "migration_index_test_table")]
+#[sql(index(name = "migration_index_test_email", columns = [email], unique))]
+struct MigrationIndexTestTableV2 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    email: String,
+}
+
+#[derive(Insert)]
+#[sql(table = MigrationIndexTestTableV2)]
+#[sql(default = id)]
+struct MigrationIndexTestInsertV2 {
+    email: String,
+}
+
+/// Drop unique index on 'email'
+#[derive(Table, Debug)]
+#[sql(version_test = 3)]
+#[sql(unique_id = "6d2f4a81-3b9e-4c07-a5d8-1e7f2b9c4a36")]
+#[sql(table_name = "migration_index_test_table")]
+struct MigrationIndexTestTableV3 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    email: String,
+}
+
+#[derive(Insert)]
+#[sql(table = MigrationIndexTestTableV3)]
+#[sql(default = id)]
+struct MigrationIndexTestInsertV3 {
+    email: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_migration_create_and_drop_index() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<MigrationIndexTestTableV1>().await?;
+
+    let mut conn = db.conn().await?;
+    <MigrationIndexTestTableV2 as DatabaseSetup<TestDriver>>::setup(&mut &mut conn).await?;
+
+    let insert = MigrationIndexTestInsertV2 {
+        email: "same@example.com".to_string(),
+    };
+    query!(&mut conn, INSERT INTO MigrationIndexTestTableV2 VALUES {&insert}).await?;
+    let duplicate = query!(&mut conn, INSERT INTO MigrationIndexTestTableV2 VALUES {&insert}).await;
+    assert!(
+        duplicate.is_err(),
+        "Unique index should be created by the migration"
+    );
+
+    <MigrationIndexTestTableV3 as DatabaseSetup<TestDriver>>::setup(&mut &mut conn).await?;
+
+    let insert = MigrationIndexTestInsertV3 {
+        email: "same@example.com".to_string(),
+    };
+    query!(&mut conn, INSERT INTO MigrationIndexTestTableV3 VALUES {insert}).await?;
+
+    let table_id = "6d2f4a81-3b9e-4c07-a5d8-1e7f2b9c4a36".to_string();
+    let version = crate::EasySqlTables_get_version!(TestDriver, &mut conn, table_id);
+    assert_eq!(
+        version,
+        Some(3),
+        "Expected table version to be updated to 3"
+    );
+
+    Ok(())
+}
+
+#[derive(Table, Debug)]
+#[sql(version_test = 1)]
+#[sql(unique_id = "b7c1e5d2-8f4a-4e39-9a6b-2d3c5f7e1a84")]
+#[sql(table_name = "migration_unique_test_table")]
+#[sql(index(name = "migration_unique_test_code", columns = [code], unique))]
+struct MigrationUniqueTestTableV1 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    first: String,
+    second: String,
+    code: String,
+}
+
+#[derive(Insert)]
+#[sql(table = MigrationUniqueTestTableV1)]
+#[sql(default = id)]
+struct MigrationUniqueTestInsertV1 {
+    first: String,
+    second: String,
+    code: String,
+}
+
+/// Add unique constraint on ('first', 'second'), SQLite recreates the table
+#[derive(Table, Debug)]
+#[sql(version_test = 2)]
+#[sql(unique_id = "b7c1e5d2-8f4a-4e39-9a6b-2d3c5f7e1a84")]
+#[sql(table_name = "migration_unique_test_table")]
+#[sql(index(name = "migration_unique_test_code", columns = [code], unique))]
+#[sql(unique(first, second, name = "migration_unique_test_pair"))]
+struct MigrationUniqueTestTableV2 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    first: String,
+    second: String,
+    code: String,
+}
+
+#[derive(Insert)]
+#[sql(table = MigrationUniqueTestTableV2)]
+#[sql(default = id)]
+struct MigrationUniqueTestInsertV2 {
+    first: String,
+    second: String,
+    code: String,
+}
+
+#[derive(Output, Debug, PartialEq)]
+#[sql(table = MigrationUniqueTestTableV2)]
+struct MigrationUniqueTestRowV2 {
+    first: String,
+    second: String,
+    code: String,
+}
+
+/// Drop unique constraint on ('first', 'second')
+#[derive(Table, Debug)]
+#[sql(version_test = 3)]
+#[sql(unique_id = "b7c1e5d2-8f4a-4e39-9a6b-2d3c5f7e1a84")]
+#[sql(table_name = "migration_unique_test_table")]
+#[sql(index(name = "migration_unique_test_code", columns = [code], unique))]
+struct MigrationUniqueTestTableV3 {
+    #[sql(primary_key)]
+    #[sql(auto_increment)]
+    id: i32,
+    first: String,
+    second: String,
+    code: String,
+}
+
+#[derive(Insert)]
+#[sql(table = MigrationUniqueTestTableV3)]
+#[sql(default = id)]
+struct MigrationUniqueTestInsertV3 {
+    first: String,
+    second: String,
+    code: String,
+}
+
+#[always_context(skip(!))]
+#[tokio::test]
+async fn test_migration_add_and_drop_unique_constraint() -> anyhow::Result<()> {
+    let db = Database::setup_for_testing::<MigrationUniqueTestTableV1>().await?;
+
+    let mut conn = db.conn().await?;
+    let insert = MigrationUniqueTestInsertV1 {
+        first: "a".to_string(),
+        second: "b".to_string(),
+        code: "code-1".to_string(),
+    };
+    query!(&mut conn, INSERT INTO MigrationUniqueTestTableV1 VALUES {insert}).await?;
+
+    <MigrationUniqueTestTableV2 as DatabaseSetup<TestDriver>>::setup(&mut &mut conn).await?;
+
+    let rows: Vec<MigrationUniqueTestRowV2> = query!(&mut conn,
+        SELECT Vec<MigrationUniqueTestRowV2> FROM MigrationUniqueTestTableV2 WHERE true
+    )
+    .await?;
+    assert_eq!(
+        rows,
+        vec![MigrationUniqueTestRowV2 {
+            first: "a".to_string(),
+            second: "b".to_string(),
+            code: "code-1".to_string(),
+        }],
+        "Existing rows should be preserved"
+    );
+
+    let duplicate_pair = MigrationUniqueTestInsertV2 {
+        first: "a".to_string(),
+        second: "b".to_string(),
+        code: "code-2".to_string(),
+    };
+    let result =
+        query!(&mut conn, INSERT INTO MigrationUniqueTestTableV2 VALUES {&duplicate_pair}).await;
+    assert!(
+        result.is_err(),
+        "Unique constraint should be added by the migration"
+    );
+
+    let duplicate_code = MigrationUniqueTestInsertV2 {
+        first: "a".to_string(),
+        second: "c".to_string(),
+        code: "code-1".to_string(),
+    };
+    let result =
+        query!(&mut conn, INSERT INTO MigrationUniqueTestTableV2 VALUES {&duplicate_code}).await;
+    assert!(result.is_err(), "Unique index should be kept");
+
+    <MigrationUniqueTestTableV3 as DatabaseSetup<TestDriver>>::setup(&mut &mut conn).await?;
+
+    let insert = MigrationUniqueTestInsertV3 {
+        first: "a".to_string(),
+        second: "b".to_string(),
+        code: "code-2".to_string(),
+    };
+    query!(&mut conn, INSERT INTO MigrationUniqueTestTableV3 VALUES {insert}).await?;
+
+    let table_id = "b7c1e5d2-8f4a-4e39-9a6b-2d3c5f7e1a84".to_string();
+    let version = crate::EasySqlTables_get_version!(TestDriver, &mut conn, table_id);
+    assert_eq!(
+        version,
+        Some(3),
+        "Expected table version to be updated to 3"
+    );
+
+    Ok(())
+}
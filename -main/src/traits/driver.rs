@@ -2,7 +2,10 @@ use std::{collections::HashMap, fmt::Debug};
 
 use easy_macros::always_context;
 
-use crate::{driver::TableField, traits::EasyExecutor};
+use crate::{
+    driver::{TableConstraint, TableField},
+    traits::EasyExecutor,
+};
 
 pub type DriverRow<D> = <<D as Driver>::InternalDriver as sqlx::database::Database>::Row;
 
@@ -28,21 +31,107 @@ pub trait Driver: Debug + Send + Sync + Sized {
     /// Build a parameter placeholder for the driver (`index` is 0-based).
     fn parameter_placeholder(index: usize) -> String;
 
+    /// Maximum number of bind parameters allowed in a single statement.
+    ///
+    /// `INSERT` queries with more values are split into multiple statements. Defaults to `999`,
+    /// the lowest limit of the supported databases (SQLite before 3.32).
+    fn max_bind_parameters() -> usize {
+        999
+    }
+
+    /// Number of rows changed by an `INSERT`, `UPDATE` or `DELETE` statement.
+    fn rows_affected(result: &DriverQueryResult<Self>) -> u64;
+
+    /// Row id of the last row inserted by the statement.
+    ///
+    /// For drivers which can't return generated keys with `RETURNING`, `None` otherwise.
+    fn last_insert_id(result: &DriverQueryResult<Self>) -> Option<i64> {
+        let _ = result;
+        None
+    }
+
+    /// Whether the error is temporary and the whole transaction can be retried.
+    ///
+    /// For example serialization failures or deadlocks. Errors from other drivers return `false`.
+    ///
+    /// Defaults to `false`, errors are never retried.
+    fn is_retryable_error(error: &(dyn sqlx::error::DatabaseError + 'static)) -> bool {
+        let _ = error;
+        false
+    }
+
+    /// SQL expression returning the current date and time.
+    ///
+    /// Used by `#[sql(created_at)]` and `#[sql(updated_at)]` columns.
+    fn current_timestamp() -> &'static str {
+        "CURRENT_TIMESTAMP"
+    }
+
+    /// Value used inside of `INSERT ... VALUES` for `#[sql(default_if_none)]` fields set to `None`.
+    ///
+    /// `column_default` is the `DEFAULT` value of the column (see
+    /// [`ColumnMeta::default`](crate::ColumnMeta::default)), for drivers without `DEFAULT` support
+    /// inside of `VALUES`.
+    fn insert_default_value(column_default: Option<&str>) -> String {
+        let _ = column_default;
+        "DEFAULT".to_string()
+    }
+
+    /// `schema`: see [`Table::schema`](crate::Table::schema)
     async fn table_exists(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         name: &'static str,
     ) -> anyhow::Result<bool>;
 
     /// Create a table with the provided schema metadata.
     ///
+    /// `schema`: see [`Table::schema`](crate::Table::schema)
+    ///
     /// `foreign_keys`:
     /// - Key: referenced table name
-    /// - Value: local field names, referenced field names, on delete/update cascade flag
+    /// - Value: referenced table schema, local field names, referenced field names, on
+    ///   delete/update cascade flag
+    ///
+    /// `constraints`: additional table constraints (like `CHECK`)
     async fn create_table(
         conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
         table_name: &'static str,
         fields: Vec<TableField>,
         primary_keys: Vec<&'static str>,
-        foreign_keys: HashMap<&'static str, (Vec<&'static str>, Vec<&'static str>, bool)>,
+        foreign_keys: HashMap<
+            &'static str,
+            (
+                Option<&'static str>,
+                Vec<&'static str>,
+                Vec<&'static str>,
+                bool,
+            ),
+        >,
+        constraints: Vec<TableConstraint>,
     ) -> anyhow::Result<()>;
+
+    /// Create a view selecting rows with `query`, an existing view with the same name is replaced.
+    ///
+    /// `schema`: see [`Table::schema`](crate::Table::schema)
+    ///
+    /// `columns`: view column names, in the order they are selected by `query`
+    ///
+    /// Defaults to an error, views are not supported by such drivers.
+    async fn create_view(
+        conn: &mut (impl EasyExecutor<Self> + Send + Sync),
+        schema: Option<&'static str>,
+        view_name: &'static str,
+        columns: Vec<&'static str>,
+        query: String,
+    ) -> anyhow::Result<()> {
+        let _ = (conn, columns, query);
+        anyhow::bail!(
+            "Views are not supported by driver `{}` (view: {:?}, schema: {:?})",
+            std::any::type_name::<Self>(),
+            view_name,
+            schema
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use easy_macros::always_context;
 
-use crate::Driver;
+use crate::{Driver, Transaction};
 
 use super::DriverConnection;
 
@@ -24,6 +24,17 @@ pub trait EasyExecutor<D: Driver> {
         DriverConnection<D>: Send + Sync;
 
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a>;
+
+    /// Starts a transaction on this executor (a savepoint if it's already inside of one).
+    ///
+    /// Used by the query macros when a single call is executed as multiple statements. Defaults to
+    /// an error, such queries are not supported by the executor then.
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        anyhow::bail!(
+            "Transactions are not supported by executor `{}`",
+            std::any::type_name::<Self>()
+        );
+    }
 }
 
 pub trait EasyExecutorInto<D: Driver>: EasyExecutor<D> {
@@ -56,6 +67,10 @@ impl<D: Driver, E: EasyExecutor<D> + ?Sized> EasyExecutor<D> for &mut E {
     fn executor<'a>(&'a mut self) -> Self::InternalExecutor<'a> {
         (**self).executor()
     }
+
+    async fn transaction(&mut self) -> anyhow::Result<Transaction<'_, D>> {
+        (**self).transaction().await
+    }
 }
 
 impl<D: Driver, E: EasyExecutor<D> + ?Sized> EasyExecutorInto<D> for &mut E {
@@ -1,7 +1,7 @@
 use anyhow::Context;
 use easy_macros::always_context;
 
-use crate::traits::{Driver, DriverArguments};
+use crate::traits::{Driver, DriverArguments, InternalDriver};
 
 #[always_context]
 /// Insert payload mapping for a table.
@@ -11,11 +11,174 @@ use crate::traits::{Driver, DriverArguments};
 pub trait Insert<'a, Table, D: Driver> {
     fn insert_columns() -> Vec<String>;
 
+    /// Number of parameters bound by a single row, used to size the statements of
+    /// [`insert_values_chunked`](Insert::insert_values_chunked)
+    ///
+    /// Has to be an upper bound, defaults to one parameter per column.
+    fn insert_binds_per_row() -> usize {
+        Self::insert_columns().len()
+    }
+
     /// Returns (new arguments list, number of inserted rows)
     fn insert_values(
         self,
         args_list: DriverArguments<'a, D>,
     ) -> anyhow::Result<(DriverArguments<'a, D>, usize)>;
+
+    /// Binds values like [`insert_values`](Insert::insert_values) and appends the value list of
+    /// every row (` (?1,?2),`) to `values_sql`, returns (new arguments list, number of rows)
+    ///
+    /// `parameter_n` is the number of already bound parameters. Overridden by types which don't
+    /// bind every column (`#[sql(default_if_none)]`, `#[sql(value = ...)]`).
+    fn insert_rows(
+        self,
+        args_list: DriverArguments<'a, D>,
+        values_sql: &mut String,
+        parameter_n: &mut usize,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, usize)>
+    where
+        Self: Sized,
+    {
+        let columns = Self::insert_columns().len();
+        let (args_list, rows) = self.insert_values(args_list)?;
+        for _ in 0..rows {
+            let values = (0..columns).map(|_| {
+                let placeholder = D::parameter_placeholder(*parameter_n);
+                *parameter_n += 1;
+                placeholder
+            });
+            push_insert_row(values_sql, values);
+        }
+        Ok((args_list, rows))
+    }
+
+    /// Values of every statement, in insertion order
+    ///
+    /// Each chunk contains at most `max_rows` rows, used to stay below
+    /// [`Driver::max_bind_parameters`]. Single row types always return one chunk.
+    fn insert_values_chunked(self, max_rows: usize) -> anyhow::Result<Vec<InsertChunk<'a, D>>>
+    where
+        Self: Sized,
+    {
+        let _ = max_rows;
+        let mut chunk = InsertChunk::default();
+        (chunk.args, chunk.rows) =
+            self.insert_rows(chunk.args, &mut chunk.values_sql, &mut chunk.parameters)?;
+        Ok(vec![chunk])
+    }
+
+    /// Passes every value to `visitor` (in [`insert_columns`](Insert::insert_columns) order),
+    /// returns number of visited rows
+    ///
+    /// Used by APIs which don't bind values to a query, like `COPY`
+    fn visit_values(self, visitor: &mut impl InsertVisitor<'a, D>) -> anyhow::Result<usize>
+    where
+        Self: Sized,
+    {
+        let _ = visitor;
+        anyhow::bail!(
+            "Insert::visit_values is not implemented for this type, use #[derive(Insert)]"
+        )
+    }
+}
+
+/// Values of a single `INSERT` statement, see [`Insert::insert_values_chunked`].
+pub struct InsertChunk<'a, D: Driver> {
+    pub args: DriverArguments<'a, D>,
+    /// Value lists of every row following `VALUES` (` (?1,?2), (?3,?4),`, with a trailing comma)
+    pub values_sql: String,
+    /// Number of bound parameters
+    pub parameters: usize,
+    /// Number of inserted rows
+    pub rows: usize,
+}
+
+impl<'a, D: Driver> Default for InsertChunk<'a, D> {
+    fn default() -> Self {
+        InsertChunk {
+            args: DriverArguments::<D>::default(),
+            values_sql: String::new(),
+            parameters: 0,
+            rows: 0,
+        }
+    }
+}
+
+/// Appends value list of a single row (` (a,b),`) to `values_sql`
+pub fn push_insert_row(values_sql: &mut String, values: impl IntoIterator<Item = String>) {
+    values_sql.push_str(" (");
+    for (i, value) in values.into_iter().enumerate() {
+        if i > 0 {
+            values_sql.push(',');
+        }
+        values_sql.push_str(&value);
+    }
+    values_sql.push_str("),");
+}
+
+/// Receives values of [`Insert`] types one by one.
+///
+/// Implemented by driver integrations which need to encode values themselves (like Postgres
+/// `COPY`).
+pub trait InsertVisitor<'a, D: Driver> {
+    fn value<T>(&mut self, value: T) -> anyhow::Result<()>
+    where
+        T: sqlx::Encode<'a, InternalDriver<D>> + sqlx::Type<InternalDriver<D>> + 'a;
+
+    /// Called after all values of a single row were visited
+    fn end_row(&mut self) -> anyhow::Result<()>;
+}
+
+/// Splits `items` into statements of at most `max_rows` rows
+///
+/// Rows of a single item are never split between statements
+#[always_context]
+fn insert_items_chunked<'a, Table, D: Driver, T: Insert<'a, Table, D>, I: Iterator<Item = T>>(
+    items: I,
+    max_rows: usize,
+) -> anyhow::Result<Vec<InsertChunk<'a, D>>> {
+    let max_rows = max_rows.max(1);
+    let mut chunks = Vec::new();
+    let mut chunk = InsertChunk::default();
+    for item in items {
+        if chunk.rows >= max_rows {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        let (new_args, new_count) = item.insert_rows(
+            #[context(no)]
+            chunk.args,
+            &mut chunk.values_sql,
+            &mut chunk.parameters,
+        )?;
+        chunk.args = new_args;
+        chunk.rows += new_count;
+    }
+    if chunk.rows > 0 || chunks.is_empty() {
+        chunks.push(chunk);
+    }
+    Ok(chunks)
+}
+
+/// Appends rows of every item, used by collection implementations of [`Insert::insert_rows`]
+#[always_context]
+fn insert_items_rows<'a, Table, D: Driver, T: Insert<'a, Table, D>>(
+    items: impl Iterator<Item = T>,
+    mut args: DriverArguments<'a, D>,
+    values_sql: &mut String,
+    parameter_n: &mut usize,
+) -> anyhow::Result<(DriverArguments<'a, D>, usize)> {
+    let mut item_count = 0;
+    for item in items {
+        let (new_args, new_count) = item.insert_rows(
+            #[context(no)]
+            args,
+            values_sql,
+            parameter_n,
+        )?;
+        args = new_args;
+        item_count += new_count;
+    }
+    Ok((args, item_count))
 }
 
 #[always_context]
@@ -24,6 +187,10 @@ impl<'a, Table, T: Insert<'a, Table, D>, D: Driver> Insert<'a, Table, D> for Vec
         T::insert_columns()
     }
 
+    fn insert_binds_per_row() -> usize {
+        T::insert_binds_per_row()
+    }
+
     fn insert_values(
         self,
         args_list: DriverArguments<'a, D>,
@@ -40,6 +207,27 @@ impl<'a, Table, T: Insert<'a, Table, D>, D: Driver> Insert<'a, Table, D> for Vec
         }
         Ok((args, item_count))
     }
+
+    fn insert_rows(
+        self,
+        args_list: DriverArguments<'a, D>,
+        values_sql: &mut String,
+        parameter_n: &mut usize,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, usize)> {
+        insert_items_rows::<Table, D, _>(self.into_iter(), args_list, values_sql, parameter_n)
+    }
+
+    fn insert_values_chunked(self, max_rows: usize) -> anyhow::Result<Vec<InsertChunk<'a, D>>> {
+        insert_items_chunked::<Table, D, _, _>(self.into_iter(), max_rows)
+    }
+
+    fn visit_values(self, visitor: &mut impl InsertVisitor<'a, D>) -> anyhow::Result<usize> {
+        let mut item_count = 0;
+        for item in self.into_iter() {
+            item_count += item.visit_values(visitor)?;
+        }
+        Ok(item_count)
+    }
 }
 
 #[always_context]
@@ -51,6 +239,10 @@ where
         T::insert_columns()
     }
 
+    fn insert_binds_per_row() -> usize {
+        T::insert_binds_per_row()
+    }
+
     fn insert_values(
         self,
         args_list: DriverArguments<'a, D>,
@@ -67,6 +259,27 @@ where
         }
         Ok((args, item_count))
     }
+
+    fn insert_rows(
+        self,
+        args_list: DriverArguments<'a, D>,
+        values_sql: &mut String,
+        parameter_n: &mut usize,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, usize)> {
+        insert_items_rows::<Table, D, _>(self.iter(), args_list, values_sql, parameter_n)
+    }
+
+    fn insert_values_chunked(self, max_rows: usize) -> anyhow::Result<Vec<InsertChunk<'a, D>>> {
+        insert_items_chunked::<Table, D, _, _>(self.iter(), max_rows)
+    }
+
+    fn visit_values(self, visitor: &mut impl InsertVisitor<'a, D>) -> anyhow::Result<usize> {
+        let mut item_count = 0;
+        for item in self.iter() {
+            item_count += item.visit_values(visitor)?;
+        }
+        Ok(item_count)
+    }
 }
 
 #[always_context]
@@ -78,6 +291,10 @@ where
         T::insert_columns()
     }
 
+    fn insert_binds_per_row() -> usize {
+        T::insert_binds_per_row()
+    }
+
     fn insert_values(
         self,
         args_list: DriverArguments<'a, D>,
@@ -94,4 +311,25 @@ where
         }
         Ok((args, item_count))
     }
+
+    fn insert_rows(
+        self,
+        args_list: DriverArguments<'a, D>,
+        values_sql: &mut String,
+        parameter_n: &mut usize,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, usize)> {
+        insert_items_rows::<Table, D, _>(self.iter(), args_list, values_sql, parameter_n)
+    }
+
+    fn insert_values_chunked(self, max_rows: usize) -> anyhow::Result<Vec<InsertChunk<'a, D>>> {
+        insert_items_chunked::<Table, D, _, _>(self.iter(), max_rows)
+    }
+
+    fn visit_values(self, visitor: &mut impl InsertVisitor<'a, D>) -> anyhow::Result<usize> {
+        let mut item_count = 0;
+        for item in self.iter() {
+            item_count += item.visit_values(visitor)?;
+        }
+        Ok(item_count)
+    }
 }
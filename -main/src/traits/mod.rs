@@ -10,11 +10,21 @@ pub use update::*;
 
 mod table;
 pub use table::*;
+mod table_metadata;
+pub use table_metadata::*;
 mod driver;
 pub use driver::*;
 
 mod to_default;
 pub use to_default::*;
+mod sql_type;
+pub use sql_type::*;
 
 mod easy_executor;
 pub use easy_executor::*;
+
+mod retryable_error;
+pub use retryable_error::*;
+
+mod related;
+pub use related::*;
@@ -17,6 +17,26 @@ pub trait ToConvert<D: Driver> {
     ) -> anyhow::Result<Self>
     where
         Self: Sized;
+
+    /// Merges data fetched by the next statement of the same query (chunked `INSERT`)
+    ///
+    /// Defaults to keeping the data of the first statement (same as `fetch_one`), collections
+    /// should override it.
+    fn merge(self, next: Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = next;
+        self
+    }
+
+    /// Whether no rows were returned, `#[sql(lock_version)]` updates with `RETURNING` fail with
+    /// [`Error::StaleObject`](crate::Error::StaleObject) then
+    ///
+    /// Defaults to `false`, optional rows and collections should override it.
+    fn is_empty(&self) -> bool {
+        false
+    }
 }
 
 #[always_context]
@@ -0,0 +1,120 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use anyhow::Context;
+use easy_macros::always_context;
+use sqlx::{Arguments, ColumnIndex, Decode, Encode, IntoArguments, Row, Type};
+
+use crate::{
+    Driver, EasyExecutor, Output, Table, macro_support,
+    traits::{DriverArguments, DriverRow, InternalDriver},
+};
+
+/// Alias of the foreign key column selected next to the child output
+const RELATED_KEY_ALIAS: &str = "__easy_sql_related_key";
+
+/// Single column foreign key of `Self` referencing the primary key of `Parent`.
+///
+/// Implemented by the [`Table`](macro@crate::Table) derive macro for every
+/// `#[sql(foreign_key = Parent)]` field (tables with composite foreign keys to `Parent` are
+/// skipped).
+#[diagnostic::on_unimplemented(
+    message = "Table `{Self}` has no single column `#[sql(foreign_key = {Parent})]` field."
+)]
+pub trait Related<Parent, D: Driver>: Table<D> {
+    /// Type of the foreign key column (without `Option`)
+    type Key;
+
+    fn foreign_key_column() -> &'static str;
+}
+
+#[always_context]
+/// Loads `#[sql(related)]` fields of already fetched rows.
+///
+/// Implemented by the [`Output`](macro@crate::Output) derive macro for structs with
+/// `#[sql(related)]` fields, a single query is executed per field (see [`load_related`]).
+pub trait LoadRelated<D: Driver>: Sized {
+    async fn load_related(
+        conn: &mut (impl EasyExecutor<D> + Send + Sync),
+        parents: &mut [Self],
+    ) -> anyhow::Result<()>;
+}
+
+/// Fetches rows of `Child` referencing any of `keys` and groups them by the foreign key.
+///
+/// Avoids running one query per parent, keys are sent in `IN (...)` lists (split into multiple
+/// statements when there are more keys than [`Driver::max_bind_parameters`]). Soft deleted
+/// children are skipped. Keys without children are missing in the returned map.
+#[always_context]
+#[no_context_inputs]
+pub async fn load_related<Child, Parent, O, D>(
+    conn: &mut (impl EasyExecutor<D> + Send + Sync),
+    keys: impl IntoIterator<Item = Child::Key>,
+) -> anyhow::Result<HashMap<Child::Key, Vec<O>>>
+where
+    D: Driver,
+    Child: Related<Parent, D>,
+    Child::Key: Eq + Hash + Clone + Send + Sync,
+    for<'q> Child::Key: Encode<'q, InternalDriver<D>> + Decode<'q, InternalDriver<D>>,
+    Child::Key: Type<InternalDriver<D>>,
+    O: Output<Child, D, DataToConvert = DriverRow<D>>,
+    for<'a> DriverArguments<'a, D>: IntoArguments<'a, InternalDriver<D>>,
+    for<'a> &'a str: ColumnIndex<DriverRow<D>>,
+{
+    let mut keys = keys.into_iter().collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    keys.retain(|key| seen.insert(key.clone()));
+
+    let mut related: HashMap<Child::Key, Vec<O>> = HashMap::new();
+    if keys.is_empty() {
+        return Ok(related);
+    }
+
+    let delimiter = D::identifier_delimiter();
+    let table_name = Child::table_name();
+    let column = format!(
+        "{delimiter}{table_name}{delimiter}.{delimiter}{}{delimiter}",
+        Child::foreign_key_column()
+    );
+    let (soft_delete_before, soft_delete_after, _) =
+        macro_support::soft_delete_filter(Child::soft_delete_column(), delimiter);
+
+    for chunk in keys.chunks(D::max_bind_parameters().max(1)) {
+        let mut query = String::from("SELECT ");
+        O::select(&mut query);
+        query.push_str(&format!(
+            ", {column} AS {delimiter}{RELATED_KEY_ALIAS}{delimiter} FROM {}",
+            macro_support::quoted_qualified_table_name::<Child, D>()
+        ));
+        Child::table_joins(&mut query);
+
+        let placeholders = (0..chunk.len())
+            .map(D::parameter_placeholder)
+            .collect::<Vec<_>>()
+            .join(", ");
+        query.push_str(&format!(
+            " WHERE {soft_delete_before}{column} IN ({placeholders}){soft_delete_after}"
+        ));
+
+        let mut args = DriverArguments::<D>::default();
+        for key in chunk {
+            args.add(key.clone()).map_err(anyhow::Error::from_boxed)?;
+        }
+
+        let rows = sqlx::query_with(&query, args)
+            .fetch_all(conn.executor())
+            .await?;
+
+        for row in rows {
+            let key: Child::Key = row
+                .try_get(RELATED_KEY_ALIAS)
+                .context("Failed to read the foreign key of a related row")?;
+            let output = O::convert(row).context("Output::convert failed")?;
+            related.entry(key).or_default().push(output);
+        }
+    }
+
+    Ok(related)
+}
@@ -0,0 +1,29 @@
+use crate::Driver;
+
+/// Classifies errors into retryable (serialization failures, deadlocks, `SQLITE_BUSY`) and fatal.
+///
+/// Implemented for errors returned by the query macros and `Database` helpers, the decision is
+/// made by [`Driver::is_retryable_error`] of the driver `D` which returned the error.
+pub trait RetryableError {
+    /// Whether the failed transaction can be safely retried
+    fn is_retryable<D: Driver>(&self) -> bool;
+}
+
+impl RetryableError for sqlx::Error {
+    fn is_retryable<D: Driver>(&self) -> bool {
+        match self {
+            sqlx::Error::Database(error) => D::is_retryable_error(error.as_ref()),
+            _ => false,
+        }
+    }
+}
+
+impl RetryableError for anyhow::Error {
+    fn is_retryable<D: Driver>(&self) -> bool {
+        self.chain().any(|cause| {
+            cause
+                .downcast_ref::<sqlx::Error>()
+                .is_some_and(|error| error.is_retryable::<D>())
+        })
+    }
+}
@@ -0,0 +1,15 @@
+use sqlx::error::BoxDynError;
+
+/// Rust type stored as another SQL type, for example a `UserId(i64)` newtype.
+///
+/// Implemented by the [`SqlType`](macro@crate::SqlType) derive, together with `sqlx`
+/// [`Type`](sqlx::Type)/[`Encode`](sqlx::Encode)/[`Decode`](sqlx::Decode) and
+/// [`ToDefault`](crate::ToDefault) delegating to [`Repr`](SqlType::Repr).
+pub trait SqlType: Sized {
+    /// Type the value is stored as
+    type Repr;
+
+    fn to_repr(&self) -> Self::Repr;
+
+    fn from_repr(repr: Self::Repr) -> Result<Self, BoxDynError>;
+}
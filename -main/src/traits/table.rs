@@ -11,8 +11,37 @@ pub trait Table<D: Driver>: Sized {
     fn table_name() -> &'static str;
     fn primary_keys() -> Vec<&'static str>;
 
+    /// Schema the table lives in (`#[sql(schema = ...)]`)
+    ///
+    /// PostgreSQL schema or SQLite attached database alias, `None` keeps the name unqualified so
+    /// the connection default is used
+    fn schema() -> Option<&'static str> {
+        None
+    }
+
     /// WARNING: This signature may change in future releases; prefer the macros above.
     ///
     /// The name and first argument are stable, but return type and more arguments can be added.
     fn table_joins(current_query: &mut String);
+
+    /// Columns set to [`Driver::current_timestamp`] by every update (`#[sql(updated_at)]`)
+    fn updated_at_columns() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Column marking soft deleted rows (`#[sql(soft_delete)]`)
+    ///
+    /// `DELETE` sets it to [`Driver::current_timestamp`] instead of removing rows, other queries
+    /// skip rows where it's not `NULL` (unless `WITH DELETED` is used)
+    fn soft_delete_column() -> Option<&'static str> {
+        None
+    }
+
+    /// Integer column used for optimistic locking (`#[sql(lock_version)]`)
+    ///
+    /// Incremented by every update of [`Update`](crate::Update) data, updates containing the
+    /// version they were read with only change rows still having it
+    fn lock_version_column() -> Option<&'static str> {
+        None
+    }
 }
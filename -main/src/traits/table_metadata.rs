@@ -0,0 +1,14 @@
+use easy_macros::always_context;
+
+use crate::{Driver, Table, TableMeta};
+
+/// Runtime column metadata of a table.
+///
+/// Implemented by the [`Table`](macro@crate::Table) derive macro for every supported driver, for
+/// generic code like admin UIs or exporters that shouldn't repeat the schema.
+#[always_context]
+pub trait TableMetadata<D: Driver>: Table<D> {
+    /// Built on the first call, fails when a `#[sql(default = ...)]` value can't be converted or
+    /// a foreign key has more fields than the referenced primary key
+    fn table_meta() -> anyhow::Result<&'static TableMeta>;
+}
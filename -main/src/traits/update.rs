@@ -14,4 +14,18 @@ pub trait Update<'a, Table, D: Driver>: Sized {
         current_query: &mut String,
         parameter_n: &mut usize,
     ) -> anyhow::Result<DriverArguments<'a, D>>;
+
+    /// Binds the value of `column` (see [`Table::lock_version_column`](crate::Table::lock_version_column))
+    /// `self` was read with, returns `false` (without binding anything) when `self` doesn't
+    /// contain it
+    ///
+    /// Called before [`Update::updates`], which has to skip `column`
+    fn lock_version(
+        &self,
+        args_list: DriverArguments<'a, D>,
+        column: &str,
+    ) -> anyhow::Result<(DriverArguments<'a, D>, bool)> {
+        let _ = column;
+        Ok((args_list, false))
+    }
 }
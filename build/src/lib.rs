@@ -6,6 +6,7 @@
 //! ## What it does
 //! - Generates missing `#[sql(unique_id = "...")]` attributes for tables.
 //! - Updates migration metadata in `easy_sql.ron` (feature `migrations`).
+//! - Tracks view definitions, so views are recreated when their query changes (feature `migrations`).
 //! - Writes build errors to `easy_sql_logs/YYYY-MM-DD.txt` when parsing fails.
 //!
 //! ## Example (build script)
@@ -25,7 +26,8 @@
 use std::{io::Write, path::Path};
 #[cfg(feature = "migrations")]
 use {
-    easy_sql_compilation_data::{TableData, TableDataVersion},
+    easy_macros::token_stream_to_consistent_string,
+    easy_sql_compilation_data::{TableData, TableDataVersion, ViewData},
     quote::quote,
     std::collections::{HashMap, hash_map::Entry},
     syn::LitInt,
@@ -58,6 +60,8 @@ struct SearchData {
     created_unique_ids: Vec<(String, LineColumn)>,
     compilation_data: CompilationData,
     found_existing_tables_ids: Vec<String>,
+    #[cfg(feature = "migrations")]
+    found_view_names: Vec<String>,
     //Use also created_unique_ids to check if tables were updated
     tables_updated: bool,
     #[cfg(feature = "check_duplicate_table_names")]
@@ -79,6 +83,8 @@ impl SearchData {
             created_unique_ids: Vec::new(),
             compilation_data,
             found_existing_tables_ids: Vec::new(),
+            #[cfg(feature = "migrations")]
+            found_view_names: Vec::new(),
             tables_updated: false,
             #[cfg(feature = "check_duplicate_table_names")]
             base_dir,
@@ -97,6 +103,8 @@ impl SearchData {
             created_unique_ids: Vec::new(),
             compilation_data,
             found_existing_tables_ids: Vec::new(),
+            #[cfg(feature = "migrations")]
+            found_view_names: Vec::new(),
             tables_updated: false,
             unsorted_errors: Vec::new(),
             file_matched_errors: Vec::new(),
@@ -258,10 +266,19 @@ fn struct_table_handle(
                 .clone()
                 .unwrap_or_else(|| "<unknown file>".to_string());
 
+            // Tables from different schemas can share the same name
+            let used_name = match get_attributes!(item, #[sql(schema = __unknown__)]).first() {
+                Some(attr_data) => {
+                    let schema: LitStr = syn::parse2(attr_data.clone())?;
+                    format!("{}.{}", schema.value(), table_name)
+                }
+                None => table_name.clone(),
+            };
+
             context_info
                 .compilation_data
                 .used_table_names
-                .entry(table_name.clone())
+                .entry(used_name)
                 .or_insert_with(Vec::new)
                 .push(TableNameData {
                     filename: file_name,
@@ -309,9 +326,22 @@ fn struct_table_handle(
 
         //Migration check if data exists before
         if !newly_created && !is_version_test {
+            //Only names are needed here, rendered constraints are generated by the derive macro
+            let constraints = version_data
+                .checks
+                .iter()
+                .map(|check| (check.name.clone(), quote! {}))
+                .collect::<HashMap<_, _>>();
             context_info
                 .compilation_data
-                .generate_migrations(&unique_id, &version_data, version, &quote! {}, &quote! {})
+                .generate_migrations(
+                    &unique_id,
+                    &version_data,
+                    version,
+                    &quote! {},
+                    &quote! {},
+                    &constraints,
+                )
                 .with_context(|| {
                     format!("Compilation data: {:?}", context_info.compilation_data)
                 })?;
@@ -354,6 +384,78 @@ fn struct_table_handle(
     Ok(())
 }
 
+///View handling
+#[cfg(feature = "migrations")]
+#[always_context]
+fn struct_view_handle(
+    item: &mut syn::ItemStruct,
+    context_info: &mut SearchData,
+) -> anyhow::Result<()> {
+    let Some(attr) = get_attributes!(item, #[derive(__unknown__)])
+        .into_iter()
+        .next()
+    else {
+        //No Sql View derive
+        return Ok(());
+    };
+    let Ok(parsed) = syn::parse2::<DeriveInsides>(attr) else {
+        //Ignore invalid attributes, error should be shown by derive macro
+        return Ok(());
+    };
+    let is_sql_view = parsed.list.iter().any(|path| {
+        let path_str = path
+            .to_token_stream()
+            .to_string()
+            .replace(|c: char| c.is_whitespace(), "");
+        matches!(
+            path_str.as_str(),
+            "View" | "easy_sql::View" | "ViewDebug" | "easy_sql::ViewDebug"
+        )
+    });
+    if !is_sql_view || has_attributes!(item, #[sql(no_version)]) {
+        return Ok(());
+    }
+
+    let Some(query) = get_attributes!(item, #[sql(query = __unknown__)])
+        .into_iter()
+        .next()
+    else {
+        //Missing query, error should be shown by derive macro
+        return Ok(());
+    };
+
+    let mut view_name = item.ident.to_string().to_case(Case::Snake);
+    if let Some(attr_data) = get_attributes!(item, #[sql(table_name = __unknown__)]).first() {
+        let lit_str: LitStr = syn::parse2(attr_data.clone())?;
+        view_name = lit_str.value();
+    }
+
+    let definition = token_stream_to_consistent_string(query);
+
+    context_info.found_view_names.push(view_name.clone());
+
+    //Recreate the view (new version) when the definition changes
+    match context_info.compilation_data.views.entry(view_name) {
+        Entry::Occupied(occupied_entry) => {
+            let view_data = occupied_entry.into_mut();
+            if view_data.definition != definition {
+                view_data.definition = definition;
+                view_data.version += 1;
+                context_info.tables_updated = true;
+            }
+        }
+        Entry::Vacant(vacant_entry) => {
+            vacant_entry.insert(ViewData {
+                definition,
+                version: 1,
+            });
+            context_info.tables_updated = true;
+        }
+    }
+
+    Ok(())
+}
+
 fn struct_table_handle_wrapper(item: &mut syn::ItemStruct, context_info: &mut SearchData) {
     match struct_table_handle(item, context_info) {
         Ok(_) => {}
@@ -361,6 +463,10 @@ fn struct_table_handle_wrapper(item: &mut syn::ItemStruct, context_info: &mut Se
             context_info.unsorted_errors.push(err);
         }
     }
+    #[cfg(feature = "migrations")]
+    if let Err(err) = struct_view_handle(item, context_info) {
+        context_info.unsorted_errors.push(err);
+    }
 }
 
 #[always_context]
@@ -598,6 +704,20 @@ fn build_result(ignore_list: &[regex::Regex], default_drivers: &[&str]) -> anyho
         });
     }
 
+    //Remove deleted views
+    #[cfg(feature = "migrations")]
+    if !search_data.errors_found
+        && search_data.compilation_data.views.len() != search_data.found_view_names.len()
+    {
+        search_data.tables_updated = true;
+
+        let found_view_names = &search_data.found_view_names;
+        search_data
+            .compilation_data
+            .views
+            .retain(|name, _| found_view_names.contains(name));
+    }
+
     //Update compilation data (if needed)
     search_data.compilation_data.save()?;
 
@@ -5,13 +5,10 @@ use std::{
 };
 
 use anyhow::{self, Context};
-use quote::ToTokens;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
 #[cfg(feature = "migrations")]
-use {
-    easy_macros::TokensBuilder,
-    proc_macro2::{Span, TokenStream},
-    quote::quote,
-};
+use {easy_macros::TokensBuilder, proc_macro2::Span};
 
 use easy_macros::{
     always_context, get_attributes, has_attributes, token_stream_to_consistent_string,
@@ -23,6 +20,14 @@ pub struct TableField {
     pub name: String,
     #[serde(default)]
     pub ty_to_bytes: bool,
+    #[serde(default)]
+    pub ty_to_json: bool,
+    /// `#[sql(created_at)]` or `#[sql(updated_at)]`
+    #[serde(default)]
+    pub auto_timestamp: bool,
+    /// `#[sql(generated = ...)]` tokens converted to_string()
+    #[serde(default)]
+    pub generated: Option<String>,
     pub field_type: String,
     ///Tokens converted to_string()
     pub default: Option<String>,
@@ -38,6 +43,330 @@ pub struct TableDataVersion {
     ///value - current field name
     #[serde(serialize_with = "ordered_map")]
     pub foreign_keys: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub indexes: Vec<TableIndex>,
+    #[serde(default)]
+    pub checks: Vec<TableCheck>,
+    #[serde(default)]
+    pub uniques: Vec<TableUnique>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct TableCheck {
+    pub name: String,
+    ///Tokens converted to_string()
+    pub expression: String,
+}
+
+impl TableCheck {
+    /// Collects `#[sql(check = ...)]` field and struct attributes
+    ///
+    /// Returns constraint names with the expression tokens
+    pub fn collect(
+        item: &syn::ItemStruct,
+        table_name: &str,
+    ) -> anyhow::Result<Vec<(String, TokenStream)>> {
+        let mut checks = Vec::new();
+
+        for field in item.fields.iter() {
+            let field_name = field.ident.as_ref().context("Unnamed field")?;
+            for (i, expression) in get_attributes!(field, #[sql(check = __unknown__)])
+                .into_iter()
+                .enumerate()
+            {
+                let name = if i == 0 {
+                    format!("{table_name}_{field_name}_check")
+                } else {
+                    format!("{table_name}_{field_name}_check{}", i + 1)
+                };
+                checks.push((name, expression));
+            }
+        }
+
+        for (i, expression) in get_attributes!(item, #[sql(check = __unknown__)])
+            .into_iter()
+            .enumerate()
+        {
+            checks.push((format!("{table_name}_check{}", i + 1), expression));
+        }
+
+        Ok(checks)
+    }
+
+    pub fn from_struct(item: &syn::ItemStruct, table_name: &str) -> anyhow::Result<Vec<Self>> {
+        Ok(Self::collect(item, table_name)?
+            .into_iter()
+            .map(|(name, expression)| TableCheck {
+                name,
+                expression: token_stream_to_consistent_string(expression),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct TableUnique {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+impl TableUnique {
+    /// Collects `#[sql(unique(...))]` struct attributes
+    pub fn from_struct(item: &syn::ItemStruct, table_name: &str) -> anyhow::Result<Vec<Self>> {
+        let field_names = item
+            .fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+            .collect::<Vec<_>>();
+
+        let mut uniques = Vec::new();
+
+        for attr in item.attrs.iter() {
+            if !attr.path().is_ident("sql") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("unique") {
+                    return skip_nested_meta(meta);
+                }
+
+                let mut name = None;
+                let mut columns = Vec::new();
+
+                meta.parse_nested_meta(|unique_meta| {
+                    if unique_meta.path.is_ident("name") && unique_meta.input.peek(syn::Token![=]) {
+                        let lit: syn::LitStr = unique_meta.value()?.parse()?;
+                        name = Some(lit.value());
+                    } else if let Some(column) = unique_meta.path.get_ident() {
+                        columns.push(column.to_string());
+                    } else {
+                        return Err(unique_meta
+                            .error("Expected column name or `name = \"...\"` in unique(...)"));
+                    }
+                    Ok(())
+                })?;
+
+                if columns.is_empty() {
+                    return Err(meta.error("Unique constraint requires at least one column"));
+                }
+
+                uniques.push(TableUnique {
+                    name: name.unwrap_or_else(|| format!("{table_name}_{}_key", columns.join("_"))),
+                    columns,
+                });
+                Ok(())
+            })
+            .context("Invalid #[sql(...)] attribute")?;
+        }
+
+        for unique in uniques.iter() {
+            for column in unique.columns.iter() {
+                if !field_names.contains(column) {
+                    anyhow::bail!(
+                        "Unique constraint `{}` references unknown column `{}`, available columns: {:?}",
+                        unique.name,
+                        column,
+                        field_names
+                    );
+                }
+            }
+            if uniques
+                .iter()
+                .filter(|other| other.name == unique.name)
+                .count()
+                > 1
+            {
+                anyhow::bail!("Duplicate unique constraint name `{}`", unique.name);
+            }
+        }
+
+        Ok(uniques)
+    }
+
+    /// `easy_sql::driver::TableConstraint::Unique` value of this constraint
+    pub fn to_driver_tokens(&self, sql_crate: &TokenStream) -> TokenStream {
+        let name = self.name.as_str();
+        let columns = self.columns.iter().map(String::as_str);
+
+        quote! {
+            #sql_crate::driver::TableConstraint::Unique {
+                name: #name,
+                columns: vec![#(#columns),*],
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct TableIndex {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub is_unique: bool,
+    ///SQL predicate of a partial index
+    pub where_clause: Option<String>,
+}
+
+impl TableIndex {
+    /// Collects `#[sql(index)]` field attributes and `#[sql(index(...))]` struct attributes
+    pub fn from_struct(item: &syn::ItemStruct, table_name: &str) -> anyhow::Result<Vec<Self>> {
+        let field_names = item
+            .fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+            .collect::<Vec<_>>();
+
+        let mut indexes = Vec::new();
+
+        for field in item.fields.iter() {
+            if has_attributes!(field, #[sql(index)]) {
+                let column = field.ident.as_ref().context("Unnamed field")?.to_string();
+                indexes.push(TableIndex {
+                    name: format!("{table_name}_{column}_idx"),
+                    columns: vec![column],
+                    is_unique: false,
+                    where_clause: None,
+                });
+            }
+        }
+
+        for attr in item.attrs.iter() {
+            if !attr.path().is_ident("sql") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("index") {
+                    return skip_nested_meta(meta);
+                }
+
+                let mut name = None;
+                let mut columns = Vec::new();
+                let mut is_unique = false;
+                let mut where_clause = None;
+
+                meta.parse_nested_meta(|index_meta| {
+                    if index_meta.path.is_ident("name") {
+                        let lit: syn::LitStr = index_meta.value()?.parse()?;
+                        name = Some(lit.value());
+                    } else if index_meta.path.is_ident("columns") {
+                        let content;
+                        syn::bracketed!(content in index_meta.value()?);
+                        let parsed = content.parse_terminated(
+                            <syn::Ident as syn::parse::Parse>::parse,
+                            syn::Token![,],
+                        )?;
+                        columns = parsed.iter().map(|column| column.to_string()).collect();
+                    } else if index_meta.path.is_ident("unique") {
+                        is_unique = true;
+                    } else if index_meta.path.is_ident("where") {
+                        let input = index_meta.value()?;
+                        let mut expression = TokenStream::new();
+                        while !input.is_empty() && !input.peek(syn::Token![,]) {
+                            expression.extend([input.parse::<proc_macro2::TokenTree>()?]);
+                        }
+                        if expression.is_empty() {
+                            return Err(index_meta.error("Partial index requires an expression"));
+                        }
+                        where_clause = Some(token_stream_to_consistent_string(expression));
+                    } else {
+                        return Err(index_meta.error(
+                            "Unknown index attribute, expected `name`, `columns`, `unique` or `where`",
+                        ));
+                    }
+                    Ok(())
+                })?;
+
+                if columns.is_empty() {
+                    return Err(
+                        meta.error("Index requires at least one column: `columns = [..]`")
+                    );
+                }
+
+                indexes.push(TableIndex {
+                    name: name
+                        .unwrap_or_else(|| format!("{table_name}_{}_idx", columns.join("_"))),
+                    columns,
+                    is_unique,
+                    where_clause,
+                });
+                Ok(())
+            })
+            .context("Invalid #[sql(...)] attribute")?;
+        }
+
+        for index in indexes.iter() {
+            for column in index.columns.iter() {
+                if !field_names.contains(column) {
+                    anyhow::bail!(
+                        "Index `{}` references unknown column `{}`, available columns: {:?}",
+                        index.name,
+                        column,
+                        field_names
+                    );
+                }
+            }
+            if indexes
+                .iter()
+                .filter(|other| other.name == index.name)
+                .count()
+                > 1
+            {
+                anyhow::bail!("Duplicate index name `{}`", index.name);
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Tokens of the partial index expression (query syntax, like `#[sql(check = ...)]`)
+    pub fn where_tokens(&self) -> anyhow::Result<Option<TokenStream>> {
+        self.where_clause
+            .as_deref()
+            .map(|where_clause| {
+                where_clause
+                    .parse::<TokenStream>()
+                    .map_err(|err| anyhow::anyhow!("Invalid partial index expression: {err}"))
+            })
+            .transpose()
+    }
+
+    /// `easy_sql::driver::TableIndex` value of this index
+    ///
+    /// `where_clause` is the expression rendered into SQL `String` for the driver
+    pub fn to_driver_tokens(
+        &self,
+        sql_crate: &TokenStream,
+        where_clause: Option<TokenStream>,
+    ) -> TokenStream {
+        let name = self.name.as_str();
+        let columns = self.columns.iter().map(String::as_str);
+        let is_unique = self.is_unique;
+        let where_clause = match where_clause {
+            Some(where_clause) => quote! { Some(#where_clause) },
+            None => quote! { None },
+        };
+
+        quote! {
+            #sql_crate::driver::TableIndex {
+                name: #name,
+                columns: vec![#(#columns),*],
+                is_unique: #is_unique,
+                where_clause: #where_clause,
+            }
+        }
+    }
+}
+
+/// Skips value of `#[sql(...)]` attribute handled elsewhere
+fn skip_nested_meta(meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?;
+        while !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+            meta.input.parse::<proc_macro2::TokenTree>()?;
+        }
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
 }
 
 fn ordered_map<S, K: Ord + Serialize, V: Serialize>(
@@ -68,6 +397,10 @@ impl TableDataVersion {
 
         let mut auto_increment = false;
 
+        let indexes = TableIndex::from_struct(item, &table_name)?;
+        let checks = TableCheck::from_struct(item, &table_name)?;
+        let uniques = TableUnique::from_struct(item, &table_name)?;
+
         for field in fields.iter() {
             let name = field.ident.as_ref().unwrap().to_string();
 
@@ -80,11 +413,18 @@ impl TableDataVersion {
             }
 
             let ty_to_bytes = has_attributes!(field, #[sql(bytes)]);
+            let ty_to_json = has_attributes!(field, #[sql(json)]);
+            let auto_timestamp = has_attributes!(field, #[sql(created_at)])
+                || has_attributes!(field, #[sql(updated_at)]);
 
             let default = get_attributes!(field, #[sql(default = __unknown__)])
                 .into_iter()
                 .next()
                 .map(token_stream_to_consistent_string);
+            let generated = get_attributes!(field, #[sql(generated = __unknown__)])
+                .into_iter()
+                .next()
+                .map(token_stream_to_consistent_string);
 
             for foreign_key in get_attributes!(field, #[sql(foreign_key = __unknown__)])
                 .into_iter()
@@ -108,6 +448,9 @@ impl TableDataVersion {
                 default,
                 is_unique,
                 ty_to_bytes,
+                ty_to_json,
+                auto_timestamp,
+                generated,
             });
         }
 
@@ -117,6 +460,9 @@ impl TableDataVersion {
             foreign_keys,
             primary_keys,
             auto_increment,
+            indexes,
+            checks,
+            uniques,
         })
     }
 }
@@ -127,6 +473,13 @@ pub struct TableData {
     pub saved_versions: HashMap<i64, TableDataVersion>,
     pub latest_version: i64,
 }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViewData {
+    ///`#[sql(query = ...)]` tokens converted with token_stream_to_consistent_string
+    pub definition: String,
+    ///Increased every time the definition changes, view is recreated on version change
+    pub version: i64,
+}
 #[cfg(feature = "check_duplicate_table_names")]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TableNameData {
@@ -146,6 +499,10 @@ pub struct CompilationData {
     pub used_table_names: HashMap<String, Vec<TableNameData>>,
     #[serde(default)]
     pub default_drivers: Vec<String>,
+    ///Key - view name
+    #[serde(serialize_with = "ordered_map")]
+    #[serde(default)]
+    pub views: HashMap<String, ViewData>,
 }
 #[always_context]
 impl CompilationData {
@@ -170,6 +527,7 @@ impl CompilationData {
                     #[cfg(feature = "check_duplicate_table_names")]
                     used_table_names: HashMap::new(),
                     default_drivers,
+                    views: HashMap::new(),
                 }
             } else {
                 let data = std::fs::read_to_string(&data_path)
@@ -201,6 +559,7 @@ impl CompilationData {
                         #[cfg(feature = "check_duplicate_table_names")]
                         used_table_names: HashMap::new(),
                         default_drivers: Vec::new(),
+                        views: HashMap::new(),
                     });
                 }
 
@@ -289,6 +648,8 @@ impl CompilationData {
         latest_version_number: i64,
         sql_crate: &TokenStream,
         item_name: &TokenStream,
+        latest_constraints: &HashMap<String, TokenStream>,
+        latest_indexes: &HashMap<String, TokenStream>,
     ) -> anyhow::Result<TokenStream> {
         let macro_support = quote! { #sql_crate::macro_support };
 
@@ -361,7 +722,10 @@ impl CompilationData {
                     });
                 }
                 //Everything else on old column is not supported
-                if old_field.field_type != new_field.field_type {
+                if old_field.field_type != new_field.field_type
+                    || old_field.ty_to_bytes != new_field.ty_to_bytes
+                    || old_field.ty_to_json != new_field.ty_to_json
+                {
                     anyhow::bail!(
                         "Field type change is not supported (yet) (only rename) -> Latest Version: {:?} ||| Version {}: {:?}",
                         latest_version.fields,
@@ -385,12 +749,31 @@ impl CompilationData {
                         version_data.fields
                     );
                 }
+                if old_field.generated != new_field.generated {
+                    anyhow::bail!(
+                        "Generated column expression change is not supported (yet) (only rename) -> Latest Version: {:?} ||| Version {}: {:?}",
+                        latest_version.fields,
+                        version_number,
+                        version_data.fields
+                    );
+                }
             }
 
             //New Columns Check
             for new_field in latest_version.fields.iter().skip(version_data.fields.len()) {
+                if new_field.generated.is_some() {
+                    anyhow::bail!(
+                        "New generated column is not supported (yet) -> Latest Version: {:?} ||| Version {}: {:?}",
+                        latest_version.fields,
+                        version_number,
+                        version_data.fields
+                    );
+                }
                 //New columns need default value
-                if new_field.default.is_none() && !new_field.field_type.starts_with("Option<") {
+                if new_field.default.is_none()
+                    && !new_field.auto_timestamp
+                    && !new_field.field_type.starts_with("Option<")
+                {
                     anyhow::bail!(
                         "New (not null) column without default value is not supported -> Latest Version: {:?} ||| Version {}: {:?}",
                         latest_version.fields,
@@ -401,7 +784,13 @@ impl CompilationData {
 
                 let field_name = new_field.name.as_str();
                 let field_ident = syn::Ident::new(field_name, Span::call_site());
-                let data_type: syn::Type = syn::parse_str(new_field.field_type.as_str())?;
+                let data_type = if new_field.ty_to_bytes {
+                    quote! { Vec<u8> }
+                } else if new_field.ty_to_json {
+                    quote! { #macro_support::JsonValue }
+                } else {
+                    syn::parse_str::<syn::Type>(new_field.field_type.as_str())?.to_token_stream()
+                };
                 let is_not_null = !new_field.field_type.starts_with("Option<");
                 let is_unique = new_field.is_unique;
 
@@ -411,6 +800,15 @@ impl CompilationData {
                     //For compatibility sake
                     let default_value = default_expr;
 
+                    //Binary and JSON columns store converted default value
+                    let converted_default = if new_field.ty_to_bytes {
+                        quote! { #macro_support::to_binary(#default_value)? }
+                    } else if new_field.ty_to_json {
+                        quote! { #macro_support::to_json(#default_value)? }
+                    } else {
+                        quote! { #default_value }
+                    };
+
                     quote! {
                         {
                             //Check if default value has valid type for the current column
@@ -419,9 +817,13 @@ impl CompilationData {
                                 table_instance.#field_ident = #default_value;
                             };
 
-                            Some(#sql_crate::ToDefault::to_default(#default_value))
+                            Some(#sql_crate::ToDefault::to_default(#converted_default))
                         }
                     }
+                } else if new_field.auto_timestamp {
+                    quote! {
+                        Some(<_EasySqlMigrationDriver as #sql_crate::Driver>::current_timestamp().to_string())
+                    }
                 } else {
                     quote! {
                         None
@@ -443,11 +845,96 @@ impl CompilationData {
                             is_not_null: #is_not_null,
                             default: #default_value,
                             is_auto_increment: false,
+                            generated: None,
                         }
                     }
                 });
             }
 
+            //Index changes (changed indexes are dropped and created again)
+            for old_index in version_data.indexes.iter() {
+                if !latest_version.indexes.contains(old_index) {
+                    let index_name = old_index.name.as_str();
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::DropIndex{
+                            name: #index_name,
+                        }
+                    });
+                }
+            }
+            for new_index in latest_version.indexes.iter() {
+                if !version_data.indexes.contains(new_index) {
+                    let index = latest_indexes
+                        .get(&new_index.name)
+                        .with_context(|| format!("Index `{}` not found", new_index.name))?;
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::CreateIndex{
+                            index: #index,
+                        }
+                    });
+                }
+            }
+
+            //Check and unique constraint changes (changed constraints are dropped and added again)
+            let mut constraints_changed = false;
+            for old_check in version_data.checks.iter() {
+                if !latest_version.checks.contains(old_check) {
+                    let check_name = old_check.name.as_str();
+                    constraints_changed = true;
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::DropConstraint{
+                            name: #check_name,
+                        }
+                    });
+                }
+            }
+            for new_check in latest_version.checks.iter() {
+                if !version_data.checks.contains(new_check) {
+                    let constraint = latest_constraints
+                        .get(&new_check.name)
+                        .with_context(|| format!("Constraint `{}` not found", new_check.name))?;
+                    constraints_changed = true;
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::AddConstraint{
+                            constraint: #constraint,
+                        }
+                    });
+                }
+            }
+
+            for old_unique in version_data.uniques.iter() {
+                if !latest_version.uniques.contains(old_unique) {
+                    let unique_name = old_unique.name.as_str();
+                    constraints_changed = true;
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::DropConstraint{
+                            name: #unique_name,
+                        }
+                    });
+                }
+            }
+            for new_unique in latest_version.uniques.iter() {
+                if !version_data.uniques.contains(new_unique) {
+                    let constraint = new_unique.to_driver_tokens(sql_crate);
+                    constraints_changed = true;
+                    changes_needed.push(quote! {
+                        #sql_crate::driver::AlterTableSingle::AddConstraint{
+                            constraint: #constraint,
+                        }
+                    });
+                }
+            }
+
+            //Not every driver can alter constraints of an existing table (SQLite)
+            if constraints_changed {
+                result.add(quote! {
+                    let _ = || {
+                        fn __easy_sql_assert<T: #sql_crate::markers::SupportsAlterConstraint>() {}
+                        __easy_sql_assert::<_EasySqlMigrationDriver>();
+                    };
+                });
+            }
+
             if let Some(rename_table) = rename_table {
                 changes_needed.push(rename_table);
             }
@@ -460,6 +947,7 @@ impl CompilationData {
                 result.add(quote! {
                     if current_version_number == #version_number{
                         #sql_crate::EasyExecutor::query_setup(conn, #sql_crate::driver::AlterTable{
+                            schema: <Self as #sql_crate::Table<_EasySqlMigrationDriver>>::schema(),
                             table_name: #table_name,
                             alters: vec![#(#changes_needed),*],
                         }).await?;
@@ -1,6 +1,6 @@
 use ::{
     anyhow::{self, Context},
-    proc_macro2::TokenStream,
+    proc_macro2::{Delimiter, Group, TokenStream, TokenTree},
     quote::{ToTokens, quote},
     syn::{self, parse::Parse, punctuated::Punctuated},
 };
@@ -9,9 +9,10 @@ use easy_sql_compilation_data::CompilationData;
 
 use crate::{
     derive_components::{
-        INSERT_FIELD_KEYS, INSERT_STRUCT_KEYS, supported_drivers, ty_to_variant,
-        validate_sql_attribute_keys,
+        FieldStorage, INSERT_FIELD_KEYS, INSERT_STRUCT_KEYS, option_inner, supported_drivers,
+        ty_to_variant, validate_sql_attribute_keys,
     },
+    macros_components::{CollectedData, ProvidedDrivers, expr::Expr},
     sql_crate,
 };
 
@@ -27,6 +28,166 @@ impl Parse for DefaultAttr {
     }
 }
 
+/// `#[sql(field = column)]` / `#[sql(field = Table.column)]`
+struct FieldAttribute {
+    table: Option<syn::Path>,
+    column: syn::Ident,
+}
+
+#[always_context]
+impl Parse for FieldAttribute {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<syn::Path>()?;
+        if input.peek(syn::Token![.]) {
+            input.parse::<syn::Token![.]>()?;
+            let column = input.parse::<syn::Ident>()?;
+            Ok(FieldAttribute {
+                table: Some(path),
+                column,
+            })
+        } else {
+            let column = path.require_ident()?.clone();
+            Ok(FieldAttribute {
+                table: None,
+                column,
+            })
+        }
+    }
+}
+
+enum InsertFieldKind {
+    /// Bound as a single parameter
+    Value,
+    /// `#[sql(default_if_none)]` - `Option<T>` field, column default is used for `None`
+    DefaultIfNone { inner_ty: syn::Type },
+    /// `#[sql(value = ...)]` - SQL expression, `{field}` is replaced with the bound value
+    Expression(Expr),
+}
+
+struct InsertField<'a> {
+    field: &'a syn::Field,
+    column: syn::Ident,
+    /// Table named in `#[sql(field = Table.column)]`
+    column_table: Option<syn::Path>,
+    storage: FieldStorage,
+    kind: InsertFieldKind,
+}
+
+/// Replaces `{field_name}` with `{arg0}`, returns number of replaced references
+#[always_context]
+fn replace_value_reference(
+    tokens: TokenStream,
+    field_name: &syn::Ident,
+    references: &mut usize,
+) -> anyhow::Result<TokenStream> {
+    let mut result = TokenStream::new();
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                let inner = group.stream().into_iter().collect::<Vec<_>>();
+                match inner.as_slice() {
+                    [TokenTree::Ident(ident)] if ident == field_name => {
+                        *references += 1;
+                        let mut replaced =
+                            Group::new(Delimiter::Brace, quote! { arg0 }.into_iter().collect());
+                        replaced.set_span(group.span());
+                        result.extend([TokenTree::Group(replaced)]);
+                    }
+                    _ => anyhow::bail!(
+                        "#[sql(value = ...)] of field `{}` can only reference its own value as `{{{}}}`, found `{}`",
+                        field_name,
+                        field_name,
+                        group
+                    ),
+                }
+            }
+            TokenTree::Group(group) => {
+                let stream = replace_value_reference(group.stream(), field_name, references)?;
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                result.extend([TokenTree::Group(replaced)]);
+            }
+            other => result.extend([other]),
+        }
+    }
+    Ok(result)
+}
+
+#[always_context]
+fn insert_field(field: &syn::Field) -> anyhow::Result<Option<InsertField<'_>>> {
+    let field_name = field.ident.as_ref().unwrap();
+
+    if has_attributes!(field, #[sql(skip)]) {
+        return Ok(None);
+    }
+
+    let mut field_attr = None;
+    for attr in get_attributes!(field, #[sql(field = __unknown__)]) {
+        if field_attr.is_some() {
+            anyhow::bail!(
+                "Only one #[sql(field = ...)] attribute is allowed per field: {}",
+                field_name
+            );
+        }
+        field_attr = Some(syn::parse2::<FieldAttribute>(attr)?);
+    }
+    let (column, column_table) = match field_attr {
+        Some(attr) => (attr.column, attr.table),
+        None => (field_name.clone(), None),
+    };
+
+    let mut value_attr = None;
+    for attr in get_attributes!(field, #[sql(value = __unknown__)]) {
+        if value_attr.is_some() {
+            anyhow::bail!(
+                "Only one #[sql(value = ...)] attribute is allowed per field: {}",
+                field_name
+            );
+        }
+        value_attr = Some(attr);
+    }
+    let default_if_none = has_attributes!(field, #[sql(default_if_none)]);
+
+    let kind = match (value_attr, default_if_none) {
+        (Some(_), true) => anyhow::bail!(
+            "Field `{}` can't use both #[sql(value = ...)] and #[sql(default_if_none)]",
+            field_name
+        ),
+        (Some(tokens), false) => {
+            let mut references = 0;
+            let tokens = replace_value_reference(tokens, field_name, &mut references)?;
+            if references == 0 {
+                anyhow::bail!(
+                    "#[sql(value = ...)] of field `{}` has to reference the field value as `{{{}}}`",
+                    field_name,
+                    field_name
+                );
+            }
+            InsertFieldKind::Expression(syn::parse2::<Expr>(tokens)?)
+        }
+        (None, true) => {
+            let inner_ty = option_inner(&field.ty).with_context(|| {
+                format!(
+                    "#[sql(default_if_none)] requires `{}` to be an Option<T> field",
+                    field_name
+                )
+            })?;
+            InsertFieldKind::DefaultIfNone {
+                inner_ty: inner_ty.clone(),
+            }
+        }
+        (None, false) => InsertFieldKind::Value,
+    };
+
+    Ok(Some(InsertField {
+        field,
+        column,
+        column_table,
+        storage: FieldStorage::of(field)?,
+        kind,
+    }))
+}
+
 #[always_context]
 pub fn sql_insert_base(
     item_name: &syn::Ident,
@@ -35,60 +196,218 @@ pub fn sql_insert_base(
     drivers: &[syn::Path],
     defaults: Vec<syn::Ident>,
 ) -> anyhow::Result<TokenStream> {
-    let field_names = fields.iter().map(|field| field.ident.as_ref().unwrap());
-    let field_names_str = field_names
-        .clone()
-        .map(|field| field.to_string())
-        .collect::<Vec<_>>();
+    let mut insert_fields = Vec::new();
+    for field in fields.iter() {
+        if let Some(insert_field) = insert_field(field)? {
+            insert_fields.push(insert_field);
+        }
+    }
 
     let sql_crate = sql_crate();
     let macro_support = quote! { #sql_crate::macro_support };
 
+    // Fields which aren't bound as a single parameter each, rows need custom `VALUES` SQL
+    let has_row_sql = insert_fields
+        .iter()
+        .any(|field| !matches!(field.kind, InsertFieldKind::Value));
+
+    let columns_str = insert_fields
+        .iter()
+        .map(|field| field.column.to_string())
+        .collect::<Vec<_>>();
+
     let mut insert_values = Vec::new();
     let mut insert_values_ref = Vec::new();
     let mut insert_values_support = Vec::new();
     let mut insert_values_debug = Vec::new();
     let mut insert_values_debug_ref = Vec::new();
+    let mut column_checks = Vec::new();
+    let mut where_clauses_types = Vec::new();
+    let mut row_values = Vec::new();
+    let mut row_values_ref = Vec::new();
+    let mut types_driver_support_needed = Vec::new();
+    let mut expression_checks = Vec::new();
+    // Parameters bound by a single row, `None` values of `#[sql(default_if_none)]` fields aren't
+    // bound but are counted
+    let mut binds_per_row = 0usize;
+
+    let drivers_for_checks = drivers
+        .iter()
+        .map(|driver| driver.to_token_stream())
+        .collect::<Vec<_>>();
 
-    for field in fields.iter() {
-        let bytes = has_attributes!(field, #[sql(bytes)]);
+    for insert_field in insert_fields.iter() {
+        let InsertField {
+            field,
+            column,
+            column_table,
+            storage,
+            kind,
+        } = insert_field;
+        let storage = *storage;
         let field_name = field.ident.as_ref().unwrap();
-        let mapped = ty_to_variant(
-            quote! {self},
-            field_name.to_token_stream(),
-            bytes,
-            &sql_crate,
-        )?;
-        let mapped_support = ty_to_variant(
-            quote! {_self},
-            field_name.to_token_stream(),
-            bytes,
-            &sql_crate,
-        )?;
-        let debug_value = if bytes {
-            quote! { &self.#field_name }
-        } else {
-            quote! { #mapped }
-        };
+        let column_str = column.to_string();
+
         let debug_format_str = format!("Binding field `{}` to query failed", field_name);
         let debug_format_str_ref = format!(
             "Failed to add `{}` (= {{:?}}) to the sqlx arguments list",
             field_name
         );
-        insert_values_debug.push(quote! {
-            .context(#debug_format_str)
-        });
-        insert_values_debug_ref.push(quote! {
-            .with_context(|| format!(#debug_format_str_ref, #debug_value))
+        let debug = quote! { .context(#debug_format_str) };
+        let debug_ref = quote! {
+            .with_context(|| format!(#debug_format_str_ref, &self.#field_name))
+        };
+
+        if let Some(column_table) = column_table {
+            column_checks.push(quote! {
+                let _ = |table: #column_table| -> #table { table };
+            });
+        }
+
+        let bound_field_ty = match kind {
+            InsertFieldKind::DefaultIfNone { inner_ty } => inner_ty.to_token_stream(),
+            _ => field.ty.to_token_stream(),
+        };
+        let bound_ty = storage.bound_ty(&macro_support, &bound_field_ty);
+        where_clauses_types.push(quote! {
+            for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+            #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
         });
-        let mapped_ref = if bytes {
-            quote! { #macro_support::to_binary(&self.#field_name)? }
-        } else {
-            quote! { &self.#field_name }
+
+        let placeholder = quote! {
+            let placeholder = <D as #sql_crate::Driver>::parameter_placeholder(*parameter_n);
+            *parameter_n += 1;
         };
-        insert_values.push(mapped);
-        insert_values_ref.push(mapped_ref);
-        insert_values_support.push(mapped_support);
+
+        // Every field binds (at most) a single parameter, references in `#[sql(value = ...)]`
+        // expressions reuse its placeholder and literals are inlined
+        binds_per_row += 1;
+
+        match kind {
+            InsertFieldKind::Value | InsertFieldKind::Expression(_) => {
+                let mapped = ty_to_variant(
+                    quote! {self},
+                    field_name.to_token_stream(),
+                    storage,
+                    &sql_crate,
+                )?;
+                let mapped_ref = storage.to_column(&macro_support, quote! { &self.#field_name });
+                let mapped_support = ty_to_variant(
+                    quote! {_self},
+                    field_name.to_token_stream(),
+                    storage,
+                    &sql_crate,
+                )?;
+                column_checks.push(quote! {
+                    #column: __easy_sql_insert_value(this_instance.#field_name),
+                });
+
+                let push_value = if let InsertFieldKind::Expression(expr) = kind {
+                    let mut checks = Vec::new();
+                    let mut format_params = Vec::new();
+                    // Not used in output but required in Collected Data
+                    let mut format_str = String::new();
+                    let mut binds = Vec::new();
+                    let mut before_param_n = quote! {};
+                    let mut before_format = Vec::new();
+                    let mut current_param_n = 0usize;
+                    let driver_for_value = ProvidedDrivers::SingleWithChecks {
+                        driver: quote! { D },
+                        checks: drivers_for_checks.clone(),
+                    };
+                    let mut data = CollectedData::new(
+                        &mut format_str,
+                        &mut format_params,
+                        &mut binds,
+                        &mut checks,
+                        &sql_crate,
+                        &driver_for_value,
+                        &mut current_param_n,
+                        &mut before_param_n,
+                        &mut before_format,
+                        None,
+                        Some(table),
+                        &mut types_driver_support_needed,
+                    );
+                    let sql_template = expr.clone().into_query_string(
+                        &mut data, false, true, // for_custom_select
+                    );
+                    expression_checks.extend(checks);
+
+                    quote! {
+                        let arg0 = placeholder;
+                        values.push(format!(#sql_template, #(#format_params),*));
+                    }
+                } else {
+                    quote! {
+                        values.push(placeholder);
+                    }
+                };
+
+                row_values.push(quote! {
+                    #placeholder
+                    #push_value
+                    args_list.add(#mapped).map_err(#macro_support::Error::from_boxed)#debug?;
+                });
+                row_values_ref.push(quote! {
+                    #placeholder
+                    #push_value
+                    args_list.add(#mapped_ref).map_err(#macro_support::Error::from_boxed)#debug_ref?;
+                });
+
+                insert_values.push(mapped);
+                insert_values_ref.push(mapped_ref);
+                insert_values_support.push(mapped_support);
+            }
+            InsertFieldKind::DefaultIfNone { .. } => {
+                column_checks.push(quote! {
+                    #column: __easy_sql_insert_value(this_instance.#field_name.unwrap()),
+                });
+
+                let value = if storage == FieldStorage::Native {
+                    quote! { value }
+                } else {
+                    storage.to_column(&macro_support, quote! { &value })
+                };
+                let value_ref = storage.to_column(&macro_support, quote! { value });
+                let column_default = quote! {
+                    None => {
+                        let column_default = <#table as #sql_crate::TableMetadata<D>>::table_meta()?
+                            .column(#column_str)
+                            .and_then(|column| column.default.as_deref());
+                        values.push(<D as #sql_crate::Driver>::insert_default_value(column_default));
+                    }
+                };
+
+                row_values.push(quote! {
+                    match self.#field_name {
+                        Some(value) => {
+                            #placeholder
+                            values.push(placeholder);
+                            args_list.add(#value).map_err(#macro_support::Error::from_boxed)#debug?;
+                        }
+                        #column_default
+                    }
+                });
+                row_values_ref.push(quote! {
+                    match &self.#field_name {
+                        Some(value) => {
+                            #placeholder
+                            values.push(placeholder);
+                            args_list.add(#value_ref).map_err(#macro_support::Error::from_boxed)#debug_ref?;
+                        }
+                        #column_default
+                    }
+                });
+
+                insert_values_support.push(
+                    storage.to_column(&macro_support, quote! { &_self.#field_name.unwrap() }),
+                );
+            }
+        }
+
+        insert_values_debug.push(debug);
+        insert_values_debug_ref.push(debug_ref);
     }
 
     let insert_driver_tests=drivers.iter().map(|driver|{
@@ -101,29 +420,172 @@ pub fn sql_insert_base(
                 #macro_support::Result::<()>::Ok(())
             };
         }
-    });
+    }).collect::<Vec<_>>();
 
-    let where_clauses_types = fields
+    if insert_fields
         .iter()
-        .map(|field| {
-            let bytes = has_attributes!(field, #[sql(bytes)]);
-            if bytes {
-                let bound_ty = quote! { Vec<u8> };
-                quote! {
-                    for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                    #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+        .any(|field| matches!(field.kind, InsertFieldKind::DefaultIfNone { .. }))
+    {
+        // Column defaults are read from table metadata
+        where_clauses_types.push(quote! {
+            #table: #sql_crate::TableMetadata<D>,
+        });
+    }
+    for ty in types_driver_support_needed.iter() {
+        where_clauses_types.push(quote! {
+            #ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+        });
+    }
+
+    let columns_count = insert_fields.len();
+
+    // Owned and borrowed implementations only differ in the way fields are accessed
+    let value_methods = |by_ref: bool| {
+        let binds_per_row_method = quote! {
+            fn insert_binds_per_row() -> usize {
+                #binds_per_row
+            }
+        };
+
+        let (insert_values, insert_values_debug, row_values) = if by_ref {
+            (
+                &insert_values_ref,
+                &insert_values_debug_ref,
+                &row_values_ref,
+            )
+        } else {
+            (&insert_values, &insert_values_debug, &row_values)
+        };
+        // Checked once, in the owned implementation
+        let driver_tests = if by_ref {
+            quote! {}
+        } else {
+            quote! {
+                {
+                    use #macro_support::Context as _;
+
+                    use #macro_support::Arguments;
+
+                    #(#insert_driver_tests)*
+                }
+            }
+        };
+
+        if has_row_sql {
+            let unsupported = format!(
+                "`{}` has #[sql(default_if_none)] or #[sql(value = ...)] fields, it can only be inserted with INSERT statements",
+                item_name
+            );
+            quote! {
+                #binds_per_row_method
+
+                fn insert_values(
+                    self,
+                    _args_list: #macro_support::DriverArguments<'a, D>,
+                ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, usize)> {
+                    #driver_tests
+
+                    Err(#macro_support::Error::msg(#unsupported))
+                }
+
+                fn insert_rows(
+                    self,
+                    mut args_list: #macro_support::DriverArguments<'a, D>,
+                    values_sql: &mut String,
+                    parameter_n: &mut usize,
+                ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, usize)> {
+                    use #macro_support::Context as _;
+
+                    use #macro_support::Arguments;
+
+                    #[allow(unused_variables)]
+                    let delimeter = <D as #sql_crate::Driver>::identifier_delimiter();
+                    let mut values = Vec::with_capacity(#columns_count);
+                    #(#row_values)*
+                    #macro_support::push_insert_row(values_sql, values);
+
+                    Ok((args_list, 1))
                 }
-            } else {
-                let field_ty = &field.ty;
-                quote! {
-                    for<'__easy_sql_x> #field_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                    #field_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+
+                fn visit_values(
+                    self,
+                    _visitor: &mut impl #sql_crate::driver::InsertVisitor<'a, D>,
+                ) -> #macro_support::Result<usize> {
+                    Err(#macro_support::Error::msg(#unsupported))
                 }
             }
-        })
-        .collect::<Vec<_>>();
+        } else {
+            quote! {
+                #binds_per_row_method
+
+                fn insert_values(
+                    self,
+                    mut args_list: #macro_support::DriverArguments<'a, D>,
+                ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, usize)> {
+                    use #macro_support::Context as _;
+
+                    use #macro_support::Arguments;
+
+                    #driver_tests
+
+                    #(
+                        args_list.add(#insert_values).map_err(#macro_support::Error::from_boxed)#insert_values_debug?;
+                    )*
+
+                    Ok((args_list, 1))
+                }
+
+                fn visit_values(
+                    self,
+                    visitor: &mut impl #sql_crate::driver::InsertVisitor<'a, D>,
+                ) -> #macro_support::Result<usize> {
+                    use #macro_support::Context as _;
+
+                    #(
+                        visitor.value(#insert_values)#insert_values_debug?;
+                    )*
+                    visitor.end_row()?;
+
+                    Ok(1)
+                }
+            }
+        }
+    };
+    let value_methods_owned = value_methods(false);
+    let value_methods_ref = value_methods(true);
+
+    // Rows with SQL expressions instead of values can't be loaded by `copy_in`
+    let binds_all_columns = if has_row_sql {
+        quote! {}
+    } else {
+        quote! {
+            impl #sql_crate::markers::BindsAllColumns for #item_name {}
+
+            impl<'a> #sql_crate::markers::BindsAllColumns for &'a #item_name {}
+        }
+    };
+
+    let provided_columns = columns_str
+        .iter()
+        .cloned()
+        .chain(defaults.iter().map(|default| default.to_string()));
+    let missing_columns_message = format!(
+        "`{item_name}` doesn't provide every column of `{}`, add the missing fields or list columns with database defaults in #[sql(default = ...)]",
+        table.to_string().replace(' ', "")
+    );
 
     Ok(quote! {
+        const _: () = {
+            use #macro_support::InsertColumnsFallback as _;
+            assert!(
+                #macro_support::insert_columns_provided(
+                    <#table>::__EASY_SQL_INSERT_COLUMNS,
+                    &[#(#provided_columns),*],
+                ),
+                #missing_columns_message
+            );
+        };
+
         impl<'a,D:#sql_crate::Driver> #sql_crate::Insert<'a,#table,D> for #item_name
         where #(#where_clauses_types)* {
             fn insert_columns() -> Vec<String> {
@@ -158,40 +620,28 @@ pub fn sql_insert_base(
                     }
 
                     let this_instance = #macro_support::never_any::<Self>();
+                    let ___t___ = #macro_support::never_any::<#table>();
+                    #(#expression_checks)*
 
-                    #table {
+                    // Columns filled in by the database are skipped, missing columns are
+                    // checked by `__EASY_SQL_INSERT_COLUMNS`
+                    #[allow(clippy::needless_update)]
+                    let _ = #table {
                         #(
                             #defaults: Default::default(),
                         )*
-                        #(
-                        #field_names: __easy_sql_insert_value(this_instance.#field_names),
-                        )*
-                    }
+                        #(#column_checks)*
+                        ..#macro_support::never_any()
+                    };
                 };
                 vec![
                     #(
-                        #field_names_str.to_string(),
+                        #columns_str.to_string(),
                     )*
                 ]
             }
 
-            fn insert_values(
-                self,
-                mut args_list: #macro_support::DriverArguments<'a, D>,
-            ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, usize)> {
-                use #macro_support::Context as _;
-
-                use #macro_support::Arguments;
-
-                #(#insert_driver_tests)*
-
-                    #(
-                        args_list.add(#insert_values).map_err(#macro_support::Error::from_boxed)#insert_values_debug?;
-                    )*
-
-
-                Ok((args_list, 1))
-            }
+            #value_methods_owned
         }
 
         impl<'a,D:#sql_crate::Driver> #sql_crate::Insert<'a,#table,D> for &'a #item_name where #(#where_clauses_types)*{
@@ -199,26 +649,15 @@ pub fn sql_insert_base(
                 // Validity check needs to be done only once since they are compile time
                 vec![
                     #(
-                        #field_names_str.to_string(),
+                        #columns_str.to_string(),
                     )*
                 ]
             }
 
-            fn insert_values(
-                self,
-                mut args_list: #macro_support::DriverArguments<'a, D>,
-            ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, usize)> {
-                use #macro_support::Context as _;
-
-                use #macro_support::Arguments;
-                #(
-                    args_list.add(#insert_values_ref).map_err(#macro_support::Error::from_boxed)#insert_values_debug_ref?;
-                )*
-                Ok((args_list, 1))
-            }
-
+            #value_methods_ref
         }
 
+        #binds_all_columns
     })
 }
 
@@ -268,12 +707,18 @@ pub fn insert(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::Token
 
     let supported_drivers = supported_drivers(&item, &compilation_data, true)?;
 
-    sql_insert_base(
+    let json_feature_check = FieldStorage::json_feature_check(fields.iter(), &sql_crate())?;
+    let insert_impl = sql_insert_base(
         item_name,
         fields,
         &table,
         &supported_drivers,
         defaults.clone(),
-    )
-    .map(|s| s.into())
+    )?;
+
+    Ok(quote! {
+        #json_feature_check
+        #insert_impl
+    }
+    .into())
 }
@@ -1,11 +1,17 @@
 mod database_setup;
 mod insert;
 mod output;
+mod sql_enum;
+mod sql_type;
 mod table;
 mod update;
+mod view;
 
 pub use database_setup::*;
 pub use insert::*;
 pub use output::*;
+pub use sql_enum::*;
+pub use sql_type::*;
 pub use table::*;
 pub use update::*;
+pub use view::*;
@@ -14,9 +14,10 @@ use easy_sql_compilation_data::CompilationData;
 use quote::quote_spanned;
 
 use crate::{
-    CUSTOM_SELECT_ALIAS_PREFIX,
+    CUSTOM_SELECT_ALIAS_PREFIX, FLATTEN_ALIAS_PREFIX,
     derive_components::{
-        OUTPUT_FIELD_KEYS, OUTPUT_STRUCT_KEYS, supported_drivers, validate_sql_attribute_keys,
+        FieldStorage, OUTPUT_FIELD_KEYS, OUTPUT_STRUCT_KEYS, option_inner, supported_drivers,
+        validate_sql_attribute_keys, vec_inner,
     },
     macros_components::{CollectedData, ProvidedDrivers, expr::Expr, joined_field::JoinedField},
     sql_crate,
@@ -33,6 +34,23 @@ pub fn sql_output_base(
     let sql_crate = sql_crate();
     let macro_support = quote! { #sql_crate::macro_support };
 
+    // === Separate #[sql(flatten)] and #[sql(related)] fields, they aren't read from own columns ===
+    let mut flattened_fields = Vec::<FlattenedField>::new();
+    let mut related_fields = Vec::<RelatedField>::new();
+    let mut column_fields = Punctuated::<syn::Field, syn::Token![,]>::new();
+
+    for field in fields.clone() {
+        if let Some(related) = RelatedField::of(&field)? {
+            related_fields.push(related);
+        } else if has_attributes!(field, #[sql(flatten)]) {
+            flattened_fields.push(FlattenedField::new(field)?);
+        } else {
+            column_fields.push(field);
+        }
+    }
+
+    let fields = &column_fields;
+
     // === Process custom select attributes FIRST ===
     // Separate fields into regular fields and fields with #[sql(select = ...)]
     struct FieldWithSelect {
@@ -80,29 +98,15 @@ pub fn sql_output_base(
         .map(|i| format!("___easy_sql_joined_field_{}", i))
         .collect::<Vec<_>>();
 
-    let joined_checks_field_names = joined_fields
-        .iter()
-        .map(|joined_field| joined_field.field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
-
-    let context_strs2 = joined_fields
-        .iter()
-        .map(|joined_field| {
-            format!(
-                "Getting joined field `{}` with type {} for struct `{}` from table `{}`",
-                joined_field.field.ident.as_ref().unwrap(),
-                joined_field.field.ty.to_token_stream(),
-                item_name,
-                joined_field.table.to_token_stream()
-            )
-        })
-        .collect::<Vec<_>>();
-
-    let mut fields_quotes: Vec<Box<dyn Fn(&syn::Path) -> TokenStream>> = Vec::new();
+    // Reads a single field, `prefixed` is set inside of `OutputFlatten` methods, where every
+    // column is aliased as `{prefix}{field name}`
+    let mut fields_quotes: Vec<Box<dyn Fn(&syn::Path, bool) -> TokenStream>> = Vec::new();
+    // Names of fields read from a single column, used by `OutputFlatten::all_null_prefixed`
+    let mut column_field_names = Vec::<String>::new();
 
     //Handle regular fields (without custom select)
     for field in regular_fields.iter() {
-        let field_name = field.ident.as_ref().unwrap();
+        let field_name = field.ident.clone().unwrap();
         let field_name_str = field_name.to_string();
         let context_str = format!(
             "Getting field `{}` with type {} for struct `{}`",
@@ -112,27 +116,36 @@ pub fn sql_output_base(
         );
 
         let macro_support = &macro_support;
+        column_field_names.push(field_name_str.clone());
 
-        if has_attributes!(field, #[sql(bytes)]) {
+        let storage = FieldStorage::of(field)?;
+        if let Some(from_column) = storage.from_column_fn(macro_support) {
             let context_str2 = format!(
-                "Getting field `{}` with type {} for struct `{}` (Converting from binary)",
+                "Getting field `{}` with type {} for struct `{}` (Converting from {})",
                 field.ident.as_ref().unwrap(),
                 field.ty.to_token_stream(),
-                item_name
+                item_name,
+                storage.description()
             );
 
-            fields_quotes.push(Box::new(move |driver|quote! {
-                #field_name: #macro_support::from_binary_vec( <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(&data, #field_name_str).with_context(
-                    #macro_support::context!(#context_str),
-                )?).with_context(
-                    #macro_support::context!(#context_str2),
-                )?,
+            fields_quotes.push(Box::new(move |driver, prefixed| {
+                let column = column_name(prefixed, &field_name_str, &field_name_str);
+                quote! {
+                    #field_name: #from_column( <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(data, #column).with_context(
+                        #macro_support::context!(#context_str),
+                    )?).with_context(
+                        #macro_support::context!(#context_str2),
+                    )?,
+                }
             }));
         } else {
-            fields_quotes.push(Box::new(move |driver|quote! {
-                #field_name: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(&data, #field_name_str).with_context(
-                    #macro_support::context!(#context_str),
-                )?,
+            fields_quotes.push(Box::new(move |driver, prefixed| {
+                let column = column_name(prefixed, &field_name_str, &field_name_str);
+                quote! {
+                    #field_name: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(data, #column).with_context(
+                        #macro_support::context!(#context_str),
+                    )?,
+                }
             }));
         }
     }
@@ -151,34 +164,118 @@ pub fn sql_output_base(
             item_name
         );
         let macro_support = &macro_support;
+        column_field_names.push(field_name_str.clone());
 
         // Custom select fields are read using their aliased column names
         // The custom SQL expression is used in the SELECT clause with an AS alias,
         // and we read the result from the aliased column
-        if has_attributes!(field, #[sql(bytes)]) {
+        let storage = FieldStorage::of(field)?;
+        if let Some(from_column) = storage.from_column_fn(macro_support) {
             let context_str2 = format!(
-                "Getting field `{}` with type {} for struct `{}` (Converting from binary)",
+                "Getting field `{}` with type {} for struct `{}` (Converting from {})",
                 field_name,
                 field.ty.to_token_stream(),
-                item_name
+                item_name,
+                storage.description()
             );
 
-            fields_quotes.push(Box::new(move |driver|quote! {
-                #field_name: #macro_support::from_binary_vec( <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(&data, #aliased_name).with_context(
-                    #macro_support::context!(#context_str),
-                )?).with_context(
-                    #macro_support::context!(#context_str2),
-                )?,
+            fields_quotes.push(Box::new(move |driver, prefixed| {
+                let column = column_name(prefixed, &field_name_str, &aliased_name);
+                quote! {
+                    #field_name: #from_column( <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(data, #column).with_context(
+                        #macro_support::context!(#context_str),
+                    )?).with_context(
+                        #macro_support::context!(#context_str2),
+                    )?,
+                }
             }));
         } else {
-            fields_quotes.push(Box::new(move |driver|quote! {
-                #field_name: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(&data, #aliased_name).with_context(
-                    #macro_support::context!(#context_str),
-                )?,
+            fields_quotes.push(Box::new(move |driver, prefixed| {
+                let column = column_name(prefixed, &field_name_str, &aliased_name);
+                quote! {
+                    #field_name: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(data, #column).with_context(
+                        #macro_support::context!(#context_str),
+                    )?,
+                }
             }));
         }
     }
 
+    //Handle joined fields
+    for (joined_field, alias) in joined_fields.iter().zip(joined_field_aliases.iter()) {
+        let field_name = joined_field.field.ident.clone().unwrap();
+        let field_name_str = field_name.to_string();
+        let alias = alias.clone();
+        let context_str = format!(
+            "Getting joined field `{}` with type {} for struct `{}` from table `{}`",
+            field_name,
+            joined_field.field.ty.to_token_stream(),
+            item_name,
+            joined_field.table.to_token_stream()
+        );
+        let macro_support = &macro_support;
+        column_field_names.push(field_name_str.clone());
+
+        fields_quotes.push(Box::new(move |driver, prefixed| {
+            let column = column_name(prefixed, &field_name_str, &alias);
+            quote! {
+                #field_name: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(data, #column).with_context(
+                    #macro_support::context!(#context_str),
+                )?,
+            }
+        }));
+    }
+
+    //Handle flattened fields
+    for flattened in flattened_fields.iter() {
+        let field_name = flattened.field.ident.clone().unwrap();
+        let field_name_str = field_name.to_string();
+        let inner_ty = flattened.inner_ty.clone();
+        let optional = flattened.optional;
+        let table = table.clone();
+        let context_str = format!(
+            "Getting flattened field `{}` with type {} for struct `{}`",
+            field_name,
+            flattened.field.ty.to_token_stream(),
+            item_name
+        );
+        let macro_support = &macro_support;
+
+        fields_quotes.push(Box::new(move |driver, prefixed| {
+            let prefix = flattened_prefix(prefixed, &field_name_str);
+            let flatten = quote! { <#inner_ty as #macro_support::OutputFlatten<#table, #driver>> };
+            if optional {
+                quote! {
+                    #field_name: if #flatten::all_null_prefixed(data, #prefix).with_context(
+                        #macro_support::context!(#context_str),
+                    )? {
+                        None
+                    } else {
+                        Some(#flatten::convert_prefixed(data, #prefix).with_context(
+                            #macro_support::context!(#context_str),
+                        )?)
+                    },
+                }
+            } else {
+                quote! {
+                    #field_name: #flatten::convert_prefixed(data, #prefix).with_context(
+                        #macro_support::context!(#context_str),
+                    )?,
+                }
+            }
+        }));
+    }
+
+    //Handle related fields, filled in by `LoadRelated`
+    for related in related_fields.iter() {
+        let field_name = related.field_name.clone();
+        fields_quotes.push(Box::new(move |_driver, _prefixed| {
+            quote! {
+                #field_name: Vec::new(),
+            }
+        }));
+    }
+
     let select_str = regular_fields
         .iter()
         .map(|field| {
@@ -223,6 +320,22 @@ pub fn sql_output_base(
         }
     });
 
+    let select_flattened = flattened_fields.iter().enumerate().map(|(i, flattened)| {
+        let inner_ty = &flattened.inner_ty;
+        let prefix = flattened_prefix(false, &flattened.field.ident.as_ref().unwrap().to_string());
+
+        let comma = if i == 0 && select_str.is_empty() && joined_fields.is_empty() {
+            quote! {}
+        } else {
+            quote! { current_query.push_str(", "); }
+        };
+
+        quote! {
+            #comma
+            <#inner_ty as #macro_support::OutputFlatten<#table, D>>::select_prefixed(current_query, #prefix);
+        }
+    });
+
     // Generate the body for select method
     // Note: select is only called when NormalSelect trait is implemented (no args)
     let select_body = if has_custom_select && !has_custom_select_args {
@@ -231,11 +344,12 @@ pub fn sql_output_base(
             current_query.push_str(&Self::__easy_sql_select::<D>(delimeter));
         }
     } else {
-        // Otherwise, build the select list from regular and joined fields
+        // Otherwise, build the select list from regular, joined and flattened fields
         // (This covers both: no custom select at all, or custom select with args)
         quote! {
             #select_str_call
             #(#select_joined)*
+            #(#select_flattened)*
         }
     };
 
@@ -252,12 +366,75 @@ pub fn sql_output_base(
         }
     };
 
+    let mut types_driver_support_needed = Vec::new();
+
+    // Parts of `OutputFlatten::select_prefixed`, every column is aliased as `{prefix}{field name}`
+    let mut prefixed_select_parts = Vec::new();
+
+    for field in regular_fields.iter() {
+        let field_str = field.ident.as_ref().unwrap().to_string();
+        prefixed_select_parts.push(quote! {
+            parts.push(format!("{delimeter}{}{delimeter} AS {delimeter}{}{}{delimeter}", #field_str, prefix, #field_str));
+        });
+    }
+
+    for joined_field in joined_fields.iter() {
+        let ref_table_ts = &joined_field.table;
+        let field_str = joined_field.field.ident.as_ref().unwrap().to_string();
+
+        let parts_format_str = format!(
+            "{{delimeter}}{{}}{{delimeter}}.{{delimeter}}{}{{delimeter}} AS {{delimeter}}{{}}{}{{delimeter}}",
+            joined_field.table_field, field_str
+        );
+
+        prefixed_select_parts.push(quote! {
+            parts.push(format!(#parts_format_str,
+                <#ref_table_ts as #sql_crate::Table<D>>::table_name(),
+                prefix,
+            ));
+        });
+    }
+
+    let flattened_where_clauses = flattened_fields
+        .iter()
+        .map(|flattened| {
+            let inner_ty = &flattened.inner_ty;
+            quote! {
+                #inner_ty: #macro_support::OutputFlatten<#table, D>,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let flattened_select_parts = flattened_fields
+        .iter()
+        .map(|flattened| {
+            let inner_ty = &flattened.inner_ty;
+            let field_str = flattened.field.ident.as_ref().unwrap().to_string();
+            let prefix = flattened_prefix(false, &field_str);
+            let nested_prefix = flattened_prefix(true, &field_str);
+            (
+                quote! {
+                    {
+                        let mut flattened = String::new();
+                        <#inner_ty as #macro_support::OutputFlatten<#table, D>>::select_prefixed(&mut flattened, #prefix);
+                        parts.push(flattened);
+                    }
+                },
+                quote! {
+                    {
+                        let mut flattened = String::new();
+                        <#inner_ty as #macro_support::OutputFlatten<#table, D>>::select_prefixed(&mut flattened, #nested_prefix);
+                        parts.push(flattened);
+                    }
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
     // Generate __easy_sql_select() method if custom select expressions exist
     let custom_select_impl = if has_custom_select {
         let max_idx = indices.iter().max().copied().unwrap_or_default();
 
-        let mut types_driver_support_needed = Vec::new();
-
         // Verify no gaps in argument sequence
         if has_custom_select_args {
             for i in 0..=max_idx {
@@ -391,17 +568,32 @@ pub fn sql_output_base(
                         parts.push(format!(#parts_format_str, formatted_expr));
                     }
                 });
+
+                // Flattened outputs can't provide arguments, checks are already done above
+                if !has_custom_select_args {
+                    let prefixed_format_str =
+                        format!("{{}} AS {{delimeter}}{{}}{}{{delimeter}}", field_str);
+                    prefixed_select_parts.push(quote! {
+                        {
+                            let formatted_expr = format!(#sql_template, #(#format_params),*);
+                            parts.push(format!(#prefixed_format_str, formatted_expr, prefix));
+                        }
+                    });
+                }
             }
 
+            let flattened_parts = flattened_select_parts.iter().map(|(part, _)| part);
+
             quote! {
                 let mut parts = Vec::new();
                 #(#field_generation)*
+                #(#flattened_parts)*
                 parts.join(", ")
             }
         };
 
         let where_clause_types = types_driver_support_needed
-            .into_iter()
+            .iter()
             .map(|ty| {
                 quote! {
                     #ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
@@ -415,6 +607,7 @@ pub fn sql_output_base(
                 where
                     Self: #sql_crate::Output<#table, D>,
                     #(#where_clause_types)*
+                    #(#flattened_where_clauses)*
                 {
                     #select_generation_code
                 }
@@ -427,6 +620,7 @@ pub fn sql_output_base(
                 where
                     Self: #sql_crate::Output<#table, D>,
                     #(#where_clause_types)*
+                    #(#flattened_where_clauses)*
                 {
                     Self::__easy_sql_select::<D>(delimeter, #(#arg_params_in_call),*)
                 }
@@ -436,52 +630,211 @@ pub fn sql_output_base(
         quote! {}
     };
 
-    let driver_checks = drivers.iter().map(|driver| {
-        let fields_quotes = fields_quotes.iter().map(|f| f(driver));
-        quote! {
-            let _ = |data: #macro_support::DriverRow<#driver>| {
-                #macro_support::Result::<Self>::Ok(Self {
-                    #(
-                        #fields_quotes
-                    )*
-                    #(
-                        #joined_checks_field_names: <#macro_support::DriverRow<#driver> as #macro_support::SqlxRow>::try_get(&data, #joined_field_aliases).with_context(
-                            #macro_support::context!(#context_strs2),
-                        )?,
-                    )*
-                })
-            };
-        }
-    }).collect::<Vec<_>>();
+    prefixed_select_parts.extend(flattened_select_parts.into_iter().map(|(_, part)| part));
+
+    let driver_checks = drivers
+        .iter()
+        .map(|driver| {
+            let fields_quotes = fields_quotes.iter().map(|f| f(driver, false));
+            quote! {
+                let _ = |data: &#macro_support::DriverRow<#driver>| {
+                    #macro_support::Result::<Self>::Ok(Self {
+                        #(
+                            #fields_quotes
+                        )*
+                    })
+                };
+            }
+        })
+        .collect::<Vec<_>>();
 
     let where_clauses_types = joined_fields
         .iter()
         .map(|e| &e.field)
         .chain(fields.iter())
         .map(|field| {
-            let bytes = has_attributes!(field, #[sql(bytes)]);
-            if bytes {
-                let bound_ty = quote! { Vec<u8> };
+            let bound_ty = FieldStorage::of(field)?
+                .bound_ty(&macro_support, &field.ty.to_token_stream());
+            Ok(quote! {
+                for<'__easy_sql_x> #bound_ty: #macro_support::Decode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+                #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let driver_d: syn::Path = syn::parse_quote! {D};
+    let prefixed_fields_quotes = fields_quotes
+        .iter()
+        .map(|f| f(&driver_d, true))
+        .collect::<Vec<_>>();
+    let fields_quotes = fields_quotes.iter().map(|f| f(&driver_d, false));
+
+    // Outputs with custom select arguments can't be flattened, select_prefixed has no way to
+    // receive them
+    let flatten_impl = if !has_custom_select_args {
+        let types_driver_support_where = types_driver_support_needed.iter().map(|ty| {
+            quote! {
+                #ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+            }
+        });
+
+        let null_checks = column_field_names.iter().map(|field_str| {
+            let context_str = format!(
+                "Checking if column of field `{}` for struct `{}` is NULL",
+                field_str, item_name
+            );
+            quote! {
+                if !#macro_support::ValueRef::is_null(
+                    &<#macro_support::DriverRow<D> as #macro_support::SqlxRow>::try_get_raw(
+                        data,
+                        format!("{}{}", prefix, #field_str).as_str(),
+                    )
+                    .with_context(#macro_support::context!(#context_str))?,
+                ) {
+                    return Ok(false);
+                }
+            }
+        });
+
+        let flattened_null_checks = flattened_fields.iter().map(|flattened| {
+            let inner_ty = &flattened.inner_ty;
+            let prefix = flattened_prefix(
+                true,
+                &flattened.field.ident.as_ref().unwrap().to_string(),
+            );
+            quote! {
+                if !<#inner_ty as #macro_support::OutputFlatten<#table, D>>::all_null_prefixed(data, #prefix)? {
+                    return Ok(false);
+                }
+            }
+        });
+
+        quote! {
+            impl<D: #sql_crate::Driver> #macro_support::OutputFlatten<#table, D> for #item_name
+            where #macro_support::DriverRow<D>: #macro_support::ToConvert<D>,
+            for<'__easy_sql_x> &'__easy_sql_x str: #macro_support::ColumnIndex<#macro_support::DriverRow<D>>,
+            #(#where_clauses_types)*
+            #(#flattened_where_clauses)*
+            #(#types_driver_support_where)*
+            {
+                fn select_prefixed(current_query: &mut String, prefix: &str) {
+                    let delimeter = <D as #sql_crate::Driver>::identifier_delimiter();
+                    let mut parts = Vec::<String>::new();
+                    #(#prefixed_select_parts)*
+                    current_query.push_str(&parts.join(", "));
+                }
+
+                fn convert_prefixed(data: &#macro_support::DriverRow<D>, prefix: &str) -> #macro_support::Result<Self> {
+                    use #macro_support::{Context,context};
+
+                    Ok(Self {
+                        #(
+                            #prefixed_fields_quotes
+                        )*
+                    })
+                }
+
+                fn all_null_prefixed(data: &#macro_support::DriverRow<D>, prefix: &str) -> #macro_support::Result<bool> {
+                    use #macro_support::{Context,context};
+
+                    #(#null_checks)*
+                    #(#flattened_null_checks)*
+                    Ok(true)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let load_related_impl = if related_fields.is_empty() {
+        quote! {}
+    } else {
+        let load_fields = related_fields
+            .iter()
+            .map(|related| {
+                let field_name = &related.field_name;
+                let child_ty = &related.child_ty;
+                let key_field = &related.key_field;
                 quote! {
-                    for<'__easy_sql_x> #bound_ty: #macro_support::Decode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                    #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+                    {
+                        let keys = parents
+                            .iter()
+                            .map(|parent| Clone::clone(&parent.#key_field))
+                            .collect::<Vec<_>>();
+                        let mut related = #sql_crate::load_related::<
+                            <#child_ty as #macro_support::OutputTable>::Table,
+                            #table,
+                            #child_ty,
+                            _,
+                        >(&mut *conn, keys)
+                        .await?;
+                        for parent in parents.iter_mut() {
+                            parent.#field_name = related.remove(&parent.#key_field).unwrap_or_default();
+                        }
+                    }
                 }
-            } else {
-                let field_ty = &field.ty;
+            })
+            .collect::<Vec<_>>();
+
+        // Child foreign keys reference the primary key, so the parent key has to be read from it
+        // (fields with custom selects are not checked)
+        let primary_key_checks = related_fields
+            .iter()
+            .filter(|related| {
+                column_fields.iter().any(|field| {
+                    field.ident.as_ref() == Some(&related.key_field)
+                        && get_attributes!(field, #[sql(select = __unknown__)]).is_empty()
+                })
+            })
+            .map(|related| {
+                let key_column = related.key_field.to_string();
+                let message = format!(
+                    "#[sql(related)] field `{}` of `{item_name}` reads the parent key from `{key_column}`, which isn't the single primary key of `{}`, use #[sql(related = primary_key_field)]",
+                    related.field_name,
+                    table.to_string().replace(' ', "")
+                );
                 quote! {
-                    for<'__easy_sql_x> #field_ty: #macro_support::Decode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                    #field_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+                    const _: () = {
+                        use #macro_support::PrimaryKeysFallback as _;
+                        assert!(
+                            #macro_support::is_single_primary_key(
+                                <#table>::__EASY_SQL_PRIMARY_KEYS,
+                                #key_column,
+                            ),
+                            #message
+                        );
+                    };
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let impls = drivers.iter().map(|driver| {
+            quote! {
+                impl #sql_crate::LoadRelated<#driver> for #item_name {
+                    async fn load_related(
+                        conn: &mut (impl #sql_crate::EasyExecutor<#driver> + Send + Sync),
+                        parents: &mut [Self],
+                    ) -> #macro_support::Result<()> {
+                        #(#load_fields)*
+                        Ok(())
+                    }
                 }
             }
         });
 
-    let fields_quotes = fields_quotes.into_iter().map(|f| f(&syn::parse_quote! {D}));
+        quote! {
+            #(#primary_key_checks)*
+            #(#impls)*
+        }
+    };
 
     Ok(quote! {
         impl<D: #sql_crate::Driver> #sql_crate::Output<#table, D> for #item_name
         where #macro_support::DriverRow<D>: #macro_support::ToConvert<D>,
         for<'__easy_sql_x> &'__easy_sql_x str: #macro_support::ColumnIndex<#macro_support::DriverRow<D>>,
         #(#where_clauses_types)*
+        #(#flattened_where_clauses)*
      {
             type DataToConvert = #macro_support::DriverRow<D>;
             type UsedForChecks = Self;
@@ -497,15 +850,12 @@ pub fn sql_output_base(
 
                 #(#driver_checks)*
 
+                let data = &data;
+
                 Ok(Self {
                     #(
                         #fields_quotes
                     )*
-                    #(
-                        #joined_checks_field_names: <#macro_support::DriverRow<D> as #macro_support::SqlxRow>::try_get(&data, #joined_field_aliases).with_context(
-                            #macro_support::context!(#context_strs2),
-                        )?,
-                    )*
                 })
             }
         }
@@ -514,11 +864,129 @@ pub fn sql_output_base(
             type SelectProvider = Self;
         }
 
+        impl #macro_support::OutputTable for #item_name {
+            type Table = #table;
+        }
+
         #trait_impl
         #custom_select_impl
+        #flatten_impl
+        #load_related_impl
     })
 }
 
+/// Column the field is read from, `prefix` is a variable available inside of `OutputFlatten`
+/// methods
+fn column_name(prefixed: bool, field_name: &str, unprefixed: &str) -> TokenStream {
+    if prefixed {
+        quote! { format!("{}{}", prefix, #field_name).as_str() }
+    } else {
+        quote! { #unprefixed }
+    }
+}
+
+/// Alias prefix passed to `OutputFlatten` methods of the flattened field
+fn flattened_prefix(prefixed: bool, field_name: &str) -> TokenStream {
+    let field_prefix = format!("{}__", field_name);
+    if prefixed {
+        quote! { format!("{}{}", prefix, #field_prefix).as_str() }
+    } else {
+        let prefix = format!("{}{}", FLATTEN_ALIAS_PREFIX, field_prefix);
+        quote! { #prefix }
+    }
+}
+
+/// Field with `#[sql(related)]`, filled in by `LoadRelated` instead of being selected
+struct RelatedField {
+    field_name: syn::Ident,
+    /// Output type of the related rows (`T` of `Vec<T>`)
+    child_ty: syn::Type,
+    /// Field of this struct holding the key referenced by the child foreign key
+    key_field: syn::Ident,
+}
+
+#[always_context]
+impl RelatedField {
+    fn of(field: &syn::Field) -> anyhow::Result<Option<Self>> {
+        let field_name = field.ident.clone()?;
+
+        // `#[sql(related)]` uses the `id` field as the parent key
+        let mut key_field = if has_attributes!(field, #[sql(related)]) {
+            Some(syn::Ident::new("id", field_name.span()))
+        } else {
+            None
+        };
+        for attr in get_attributes!(field, #[sql(related = __unknown__)]) {
+            if key_field.is_some() {
+                anyhow::bail!("Only one #[sql(related)] attribute is allowed per field!");
+            }
+            let key: syn::Ident = syn::parse2(attr.clone())
+                .context("Expected parent key field name in #[sql(related = ...)]")?;
+            key_field = Some(key);
+        }
+        let Some(key_field) = key_field else {
+            return Ok(None);
+        };
+
+        if has_attributes!(field, #[sql(flatten)])
+            || FieldStorage::of(field)? != FieldStorage::Native
+            || !get_attributes!(field, #[sql(select = __unknown__)]).is_empty()
+        {
+            anyhow::bail!(
+                "Field `{}` with #[sql(related)] can't use #[sql(flatten)], #[sql(select = ...)], #[sql(bytes)] or #[sql(json)]",
+                field_name
+            );
+        }
+
+        #[no_context_inputs]
+        let child_ty = vec_inner(&field.ty)
+            .with_context(context!(
+                "#[sql(related)] field `{}` should be a Vec of Output type",
+                field_name
+            ))?
+            .clone();
+
+        Ok(Some(RelatedField {
+            field_name,
+            child_ty,
+            key_field,
+        }))
+    }
+}
+
+/// Field with `#[sql(flatten)]`, `Option<T>` becomes `None` when every column of `T` is NULL
+struct FlattenedField {
+    field: syn::Field,
+    /// Type implementing `OutputFlatten`
+    inner_ty: syn::Type,
+    optional: bool,
+}
+
+#[always_context]
+impl FlattenedField {
+    fn new(field: syn::Field) -> anyhow::Result<Self> {
+        if FieldStorage::of(&field)? != FieldStorage::Native
+            || !get_attributes!(field, #[sql(select = __unknown__)]).is_empty()
+        {
+            anyhow::bail!(
+                "Field `{}` with #[sql(flatten)] can't use #[sql(select = ...)], #[sql(bytes)] or #[sql(json)]",
+                field.ident.as_ref()?
+            );
+        }
+
+        let (inner_ty, optional) = match option_inner(&field.ty) {
+            Some(inner) => (inner.clone(), true),
+            None => (field.ty.clone(), false),
+        };
+
+        Ok(FlattenedField {
+            field,
+            inner_ty,
+            optional,
+        })
+    }
+}
+
 struct FieldAttribute {
     table: syn::Path,
     table_field: syn::Ident,
@@ -579,6 +1047,12 @@ pub fn output(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::Token
             attr = Some(a);
         }
         if let Some(attr) = attr {
+            if has_attributes!(field, #[sql(flatten)]) {
+                anyhow::bail!(
+                    "Field `{}` can't use both #[sql(flatten)] and #[sql(field = ...)]",
+                    field.ident.as_ref()?
+                );
+            }
             //Parse the attribute
             let attr: FieldAttribute = syn::parse2(attr.clone())?;
 
@@ -612,6 +1086,11 @@ pub fn output(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::Token
 
     let mut result = TokensBuilder::default();
 
+    result.add(FieldStorage::json_feature_check(
+        item.fields.iter(),
+        &sql_crate(),
+    )?);
+
     result.add(sql_output_base(
         item_name,
         &fields,
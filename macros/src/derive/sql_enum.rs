@@ -0,0 +1,271 @@
+use ::{
+    anyhow::{self, Context},
+    proc_macro2::TokenStream,
+    quote::{ToTokens, quote},
+    syn::{self, LitStr},
+};
+use convert_case::{Case, Casing};
+use easy_macros::{
+    TokensBuilder, always_context, get_attributes, has_attributes, parse_macro_input,
+};
+use easy_sql_compilation_data::CompilationData;
+
+use crate::{
+    derive_components::{supported_drivers_from_attribute, validate_sql_enum_attribute_keys},
+    sql_crate,
+};
+
+enum EnumRepr {
+    Text,
+    I32,
+}
+
+#[always_context]
+pub fn sql_enum(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenStream> {
+    let item = parse_macro_input!(item as syn::ItemEnum);
+
+    if let Some(error_tokens) = validate_sql_enum_attribute_keys(&item, "SqlEnum") {
+        return Ok(error_tokens.into());
+    }
+
+    let item_name = &item.ident;
+    let item_name_str = item_name.to_string();
+
+    let sql_crate = sql_crate();
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    if !item.generics.params.is_empty() {
+        anyhow::bail!("Generic enums are not supported");
+    }
+
+    let mut repr = EnumRepr::Text;
+    for repr_attr in get_attributes!(item, #[sql(repr = __unknown__)]) {
+        let repr_lit: LitStr = syn::parse2(repr_attr.clone())
+            .context("Expected string literal in the sql(repr) attribute")?;
+        repr = match repr_lit.value().as_str() {
+            "text" => EnumRepr::Text,
+            "i32" => EnumRepr::I32,
+            other => {
+                anyhow::bail!("Unknown enum representation `{other}`, expected `text` or `i32`")
+            }
+        };
+    }
+
+    let native = has_attributes!(item, #[sql(native)]);
+    let type_name_attr = get_attributes!(item, #[sql(type_name = __unknown__)])
+        .into_iter()
+        .next();
+    if type_name_attr.is_some() && !native {
+        anyhow::bail!("#[sql(type_name = ...)] can only be used together with #[sql(native)]");
+    }
+    if native && matches!(repr, EnumRepr::I32) {
+        anyhow::bail!("#[sql(native)] enums can't use #[sql(repr = \"i32\")]");
+    }
+    let type_name = match type_name_attr {
+        Some(type_name_attr) => {
+            let type_name_lit: LitStr = syn::parse2(type_name_attr.clone())
+                .context("Expected string literal in the sql(type_name) attribute")?;
+            type_name_lit.value()
+        }
+        None => item_name_str.to_case(Case::Snake),
+    };
+
+    let mut variant_idents = Vec::new();
+    let mut variant_values = Vec::new();
+    for variant in item.variants.iter() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            anyhow::bail!(
+                "Only unit variants are supported, variant `{}` has fields",
+                variant.ident
+            );
+        }
+
+        let rename = get_attributes!(variant, #[sql(rename = __unknown__)])
+            .into_iter()
+            .next();
+        if matches!(repr, EnumRepr::Text) {
+            let value = match rename {
+                Some(rename) => {
+                    let rename_lit: LitStr = syn::parse2(rename.clone())
+                        .context("Expected string literal in the sql(rename) attribute")?;
+                    rename_lit.value()
+                }
+                None => variant.ident.to_string().to_case(Case::Snake),
+            };
+            if variant_values.contains(&value) {
+                anyhow::bail!(
+                    "Duplicate enum value `{value}` (variant `{}`)",
+                    variant.ident
+                );
+            }
+            variant_values.push(value);
+        } else if rename.is_some() {
+            anyhow::bail!(
+                "#[sql(rename = ...)] is not supported with #[sql(repr = \"i32\")], use enum discriminants instead"
+            );
+        }
+
+        variant_idents.push(&variant.ident);
+    }
+
+    let invalid_value_msg = format!("Invalid `{item_name_str}` value: {{:?}}");
+
+    let (value_methods, base_ty, encode_ty, decode_ty, default_ty) = match repr {
+        EnumRepr::Text => (
+            quote! {
+                #[doc(hidden)]
+                pub fn __easy_sql_enum_value(&self) -> &'static str {
+                    match self {
+                        #(Self::#variant_idents => #variant_values,)*
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn __easy_sql_enum_from_value(value: &str) -> Option<Self> {
+                    match value {
+                        #(#variant_values => Some(Self::#variant_idents),)*
+                        _ => None,
+                    }
+                }
+            },
+            quote! { String },
+            quote! { &'q str },
+            quote! { &'r str },
+            quote! { &'static str },
+        ),
+        EnumRepr::I32 => (
+            quote! {
+                #[doc(hidden)]
+                pub fn __easy_sql_enum_value(&self) -> i32 {
+                    match self {
+                        #(Self::#variant_idents => Self::#variant_idents as i32,)*
+                    }
+                }
+
+                #[doc(hidden)]
+                pub fn __easy_sql_enum_from_value(value: i32) -> Option<Self> {
+                    #(
+                        if value == Self::#variant_idents as i32 {
+                            return Some(Self::#variant_idents);
+                        }
+                    )*
+                    None
+                }
+            },
+            quote! { i32 },
+            quote! { i32 },
+            quote! { i32 },
+            quote! { i32 },
+        ),
+    };
+
+    let mut result = TokensBuilder::default();
+
+    result.add(quote! {
+        impl #item_name {
+            #value_methods
+        }
+
+        impl<'q, DB: #macro_support::Database> #macro_support::Encode<'q, DB> for #item_name
+        where
+            #encode_ty: #macro_support::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as #macro_support::Database>::ArgumentBuffer<'q>,
+            ) -> ::std::result::Result<#macro_support::IsNull, #macro_support::BoxDynError> {
+                let value: #encode_ty = self.__easy_sql_enum_value();
+                <#encode_ty as #macro_support::Encode<'q, DB>>::encode_by_ref(&value, buf)
+            }
+        }
+
+        impl<'r, DB: #macro_support::Database> #macro_support::Decode<'r, DB> for #item_name
+        where
+            #decode_ty: #macro_support::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as #macro_support::Database>::ValueRef<'r>,
+            ) -> ::std::result::Result<Self, #macro_support::BoxDynError> {
+                let value = <#decode_ty as #macro_support::Decode<'r, DB>>::decode(value)?;
+                Self::__easy_sql_enum_from_value(value)
+                    .ok_or_else(|| format!(#invalid_value_msg, value).into())
+            }
+        }
+
+        impl<D: #sql_crate::Driver> #sql_crate::ToDefault<D> for #item_name
+        where
+            #default_ty: #sql_crate::ToDefault<D>,
+        {
+            fn to_default(self) -> String {
+                <#default_ty as #sql_crate::ToDefault<D>>::to_default(self.__easy_sql_enum_value())
+            }
+        }
+
+        impl<D: #sql_crate::Driver> #sql_crate::ToDefault<D> for &#item_name
+        where
+            #default_ty: #sql_crate::ToDefault<D>,
+        {
+            fn to_default(self) -> String {
+                <#default_ty as #sql_crate::ToDefault<D>>::to_default(self.__easy_sql_enum_value())
+            }
+        }
+    });
+
+    if native {
+        let compilation_data = CompilationData::load_in_macro()?;
+        let drivers_attr = get_attributes!(item, #[sql(drivers = __unknown__)])
+            .into_iter()
+            .next();
+        let supported_drivers =
+            supported_drivers_from_attribute(drivers_attr, &compilation_data, false)?;
+
+        for driver in supported_drivers {
+            let driver = driver.to_token_stream();
+            result.add(quote! {
+                impl #macro_support::Type<#macro_support::InternalDriver<#driver>> for #item_name {
+                    fn type_info() -> #macro_support::DriverTypeInfo<#driver> {
+                        <#driver as #sql_crate::markers::SupportsNativeEnum>::native_enum_type_info(#type_name)
+                    }
+                }
+
+                impl #sql_crate::DatabaseSetup<#driver> for #item_name {
+                    async fn setup(
+                        conn: &mut (impl #sql_crate::EasyExecutor<#driver> + Send + Sync),
+                    ) -> #macro_support::Result<()> {
+                        <#driver as #sql_crate::markers::SupportsNativeEnum>::setup_native_enum(
+                            conn,
+                            #type_name,
+                            vec![#(#variant_values),*],
+                        )
+                        .await
+                    }
+                }
+            });
+        }
+    } else {
+        result.add(quote! {
+            impl<DB: #macro_support::Database> #macro_support::Type<DB> for #item_name
+            where
+                #base_ty: #macro_support::Type<DB>,
+            {
+                fn type_info() -> <DB as #macro_support::Database>::TypeInfo {
+                    <#base_ty as #macro_support::Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &<DB as #macro_support::Database>::TypeInfo) -> bool {
+                    <#base_ty as #macro_support::Type<DB>>::compatible(ty)
+                }
+            }
+
+            impl<D: #sql_crate::Driver + 'static> #sql_crate::DatabaseSetup<D> for #item_name {
+                async fn setup(
+                    _conn: &mut (impl #sql_crate::EasyExecutor<D> + Send + Sync),
+                ) -> #macro_support::Result<()> {
+                    Ok(())
+                }
+            }
+        });
+    }
+
+    Ok(result.finalize().into())
+}
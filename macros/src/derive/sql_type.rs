@@ -0,0 +1,194 @@
+use ::{
+    anyhow::{self, Context},
+    proc_macro2::TokenStream,
+    quote::{ToTokens, quote},
+    syn,
+};
+use easy_macros::{always_context, get_attributes, parse_macro_input};
+
+use crate::{
+    derive_components::{SQL_TYPE_FIELD_KEYS, SQL_TYPE_STRUCT_KEYS, validate_sql_attribute_keys},
+    sql_crate,
+};
+
+/// Single attribute value of `#[sql(via = ...)]`, `#[sql(from = ...)]` or `#[sql(into = ...)]`
+#[always_context]
+fn single_attribute<T: syn::parse::Parse>(
+    attributes: Vec<TokenStream>,
+    name: &str,
+) -> anyhow::Result<Option<T>> {
+    let mut attributes = attributes.into_iter();
+    let Some(tokens) = attributes.next() else {
+        return Ok(None);
+    };
+    if attributes.next().is_some() {
+        anyhow::bail!("Only one #[sql({name} = ...)] attribute is allowed");
+    }
+    let parsed = syn::parse2::<T>(tokens)
+        .with_context(|| format!("Failed to parse #[sql({name} = ...)] attribute"))?;
+    Ok(Some(parsed))
+}
+
+/// Single identifiers are methods (`value.parse()`), longer paths are functions
+/// (`Email::try_new(value)`)
+fn conversion_call(conversion: &syn::Path, value: TokenStream) -> TokenStream {
+    match conversion.get_ident() {
+        Some(method) => quote! { #value.#method() },
+        None => quote! { #conversion(#value) },
+    }
+}
+
+#[always_context]
+pub fn sql_type(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenStream> {
+    let item = parse_macro_input!(item as syn::ItemStruct);
+
+    if let Some(error_tokens) =
+        validate_sql_attribute_keys(&item, "SqlType", SQL_TYPE_STRUCT_KEYS, SQL_TYPE_FIELD_KEYS)
+    {
+        return Ok(error_tokens.into());
+    }
+
+    let item_name = &item.ident;
+
+    let sql_crate = sql_crate();
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    if !item.generics.params.is_empty() {
+        anyhow::bail!("Generic structs are not supported");
+    }
+
+    let via: Option<syn::Type> =
+        single_attribute(get_attributes!(item, #[sql(via = __unknown__)]), "via")?;
+    let from: Option<syn::Path> =
+        single_attribute(get_attributes!(item, #[sql(from = __unknown__)]), "from")?;
+    let into: Option<syn::Path> =
+        single_attribute(get_attributes!(item, #[sql(into = __unknown__)]), "into")?;
+
+    let (repr_ty, to_repr, from_repr, encode, decode) = match (via, from, into) {
+        (Some(via), Some(from), Some(into)) => {
+            let into_call = conversion_call(&into, quote! { self });
+            let from_call = conversion_call(&from, quote! { repr });
+            (
+                via.to_token_stream(),
+                quote! { #into_call },
+                quote! {
+                    let value: Self = #from_call?;
+                    Ok(value)
+                },
+                quote! {
+                    let repr = <Self as #sql_crate::SqlType>::to_repr(self);
+                    <#via as #macro_support::Encode<'q, DB>>::encode_by_ref(&repr, buf)
+                },
+                quote! {
+                    let repr = <#via as #macro_support::Decode<'r, DB>>::decode(value)?;
+                    <Self as #sql_crate::SqlType>::from_repr(repr)
+                },
+            )
+        }
+        (None, None, None) => {
+            // Transparent newtype, stored as its only field
+            let mut fields = item.fields.iter();
+            let (Some(field), None) = (fields.next(), fields.next()) else {
+                anyhow::bail!(
+                    "SqlType without #[sql(via = ..., from = ..., into = ...)] requires a struct with a single field"
+                );
+            };
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(0.into()),
+            };
+            let inner_ty = &field.ty;
+            (
+                inner_ty.to_token_stream(),
+                quote! { ::std::clone::Clone::clone(&self.#member) },
+                quote! { Ok(Self { #member: repr }) },
+                quote! {
+                    <#inner_ty as #macro_support::Encode<'q, DB>>::encode_by_ref(&self.#member, buf)
+                },
+                quote! {
+                    Ok(Self {
+                        #member: <#inner_ty as #macro_support::Decode<'r, DB>>::decode(value)?,
+                    })
+                },
+            )
+        }
+        _ => anyhow::bail!(
+            "#[sql(via = ...)], #[sql(from = ...)] and #[sql(into = ...)] have to be used together"
+        ),
+    };
+
+    Ok(quote! {
+        impl #sql_crate::SqlType for #item_name {
+            type Repr = #repr_ty;
+
+            fn to_repr(&self) -> #repr_ty {
+                #to_repr
+            }
+
+            fn from_repr(
+                repr: #repr_ty,
+            ) -> ::std::result::Result<Self, #macro_support::BoxDynError> {
+                #from_repr
+            }
+        }
+
+        impl<'q, DB: #macro_support::Database> #macro_support::Encode<'q, DB> for #item_name
+        where
+            #repr_ty: #macro_support::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as #macro_support::Database>::ArgumentBuffer<'q>,
+            ) -> ::std::result::Result<#macro_support::IsNull, #macro_support::BoxDynError> {
+                #encode
+            }
+        }
+
+        impl<'r, DB: #macro_support::Database> #macro_support::Decode<'r, DB> for #item_name
+        where
+            #repr_ty: #macro_support::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as #macro_support::Database>::ValueRef<'r>,
+            ) -> ::std::result::Result<Self, #macro_support::BoxDynError> {
+                #decode
+            }
+        }
+
+        impl<DB: #macro_support::Database> #macro_support::Type<DB> for #item_name
+        where
+            #repr_ty: #macro_support::Type<DB>,
+        {
+            fn type_info() -> <DB as #macro_support::Database>::TypeInfo {
+                <#repr_ty as #macro_support::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &<DB as #macro_support::Database>::TypeInfo) -> bool {
+                <#repr_ty as #macro_support::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<D: #sql_crate::Driver> #sql_crate::ToDefault<D> for #item_name
+        where
+            #repr_ty: #sql_crate::ToDefault<D>,
+        {
+            fn to_default(self) -> String {
+                <#repr_ty as #sql_crate::ToDefault<D>>::to_default(
+                    <Self as #sql_crate::SqlType>::to_repr(&self),
+                )
+            }
+        }
+
+        impl<D: #sql_crate::Driver> #sql_crate::ToDefault<D> for &#item_name
+        where
+            #repr_ty: #sql_crate::ToDefault<D>,
+        {
+            fn to_default(self) -> String {
+                <#repr_ty as #sql_crate::ToDefault<D>>::to_default(
+                    <#item_name as #sql_crate::SqlType>::to_repr(self),
+                )
+            }
+        }
+    }
+    .into())
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use ::{
     anyhow::{self, Context},
-    proc_macro2::TokenStream,
+    proc_macro2::{TokenStream, TokenTree},
     quote::{ToTokens, quote},
     syn::{self, LitStr},
 };
@@ -10,22 +10,26 @@ use convert_case::{Case, Casing};
 use easy_macros::{
     TokensBuilder, always_context, get_attributes, has_attributes, parse_macro_input,
 };
-use easy_sql_compilation_data::CompilationData;
+use easy_sql_compilation_data::{CompilationData, TableCheck, TableIndex, TableUnique};
 #[cfg(feature = "migrations")]
 use {easy_macros::context, easy_sql_compilation_data::TableDataVersion, syn::LitInt};
 
 use crate::{
     derive::{sql_insert_base, sql_output_base, sql_update_base},
     derive_components::{
-        TABLE_FIELD_KEYS, TABLE_STRUCT_KEYS, supported_drivers, validate_sql_attribute_keys,
+        FieldStorage, TABLE_FIELD_KEYS, TABLE_STRUCT_KEYS, is_integer, option_inner,
+        supported_drivers, validate_sql_attribute_keys,
     },
-    macros_components::joined_field::JoinedField,
+    macros_components::{CollectedData, ProvidedDrivers, expr::Expr, joined_field::JoinedField},
     sql_crate,
 };
 
 mod keywords {
     syn::custom_keyword!(cascade);
 }
+/// Column used by `#[sql(soft_delete)]` without an explicit column
+const DEFAULT_SOFT_DELETE_COLUMN: &str = "deleted_at";
+
 struct ForeignKeyParsed {
     table_struct: syn::Path,
     cascade: bool,
@@ -49,6 +53,228 @@ impl syn::parse::Parse for ForeignKeyParsed {
     }
 }
 
+/// Renders `#[sql(check = ...)]` expression into `TableConstraint::Check` for `driver`
+#[always_context]
+fn check_constraint(
+    item_name: &syn::Ident,
+    name: &str,
+    expression: &TokenStream,
+    driver: &TokenStream,
+    sql_crate: &TokenStream,
+) -> anyhow::Result<TokenStream> {
+    let expression =
+        table_expression(item_name, expression, "check constraint", driver, sql_crate)?;
+
+    Ok(quote! {
+        #sql_crate::driver::TableConstraint::Check {
+            name: #name,
+            expression: #expression,
+        }
+    })
+}
+
+/// `TableIndex` driver value, with the partial index expression rendered for `driver`
+#[always_context]
+fn index_definition(
+    item_name: &syn::Ident,
+    index: &TableIndex,
+    driver: &TokenStream,
+    sql_crate: &TokenStream,
+) -> anyhow::Result<TokenStream> {
+    let where_clause = match index.where_tokens()? {
+        Some(expression) => Some(table_expression(
+            item_name,
+            &expression,
+            "partial index",
+            driver,
+            sql_crate,
+        )?),
+        None => None,
+    };
+
+    Ok(index.to_driver_tokens(sql_crate, where_clause))
+}
+
+/// Splits `#[sql(generated = expr, stored)]` into the expression and `stored` flag
+#[always_context]
+fn generated_column(field: &syn::Field) -> anyhow::Result<Option<(TokenStream, bool)>> {
+    let mut attributes = get_attributes!(field, #[sql(generated = __unknown__)]).into_iter();
+    let Some(attribute) = attributes.next() else {
+        return Ok(None);
+    };
+    if attributes.next().is_some() {
+        anyhow::bail!("Only one generated attribute is allowed");
+    }
+
+    let tokens = attribute.clone().into_iter().collect::<Vec<_>>();
+    match tokens.as_slice() {
+        [
+            expression @ ..,
+            TokenTree::Punct(comma),
+            TokenTree::Ident(stored),
+        ] if comma.as_char() == ',' && stored == "stored" => {
+            Ok(Some((expression.iter().cloned().collect(), true)))
+        }
+        _ => Ok(Some((attribute, false))),
+    }
+}
+
+/// Renders table level expression (check constraints, generated columns) into SQL `String`
+/// for `driver`
+///
+/// Columns are validated against the table struct, literals are inlined
+#[always_context]
+fn table_expression(
+    item_name: &syn::Ident,
+    expression: &TokenStream,
+    kind: &str,
+    driver: &TokenStream,
+    sql_crate: &TokenStream,
+) -> anyhow::Result<TokenStream> {
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    let expr: Expr = syn::parse2(expression.clone())
+        .with_context(|| format!("Invalid {kind} expression: `{expression}`"))?;
+
+    let mut indices = std::collections::BTreeSet::new();
+    expr.collect_indices_impl(&mut indices);
+    if !indices.is_empty() {
+        anyhow::bail!("Outside variables are not allowed inside of {kind}s");
+    }
+
+    let mut checks = Vec::new();
+    let mut format_params = Vec::new();
+    // Not used but required by CollectedData
+    let mut format_str = String::new();
+    let mut binds = Vec::new();
+    let mut before_param_n = quote! {};
+    let mut before_format = Vec::new();
+    let mut current_param_n = 0usize;
+    let mut types_driver_support_needed = Vec::new();
+
+    let item_name_tokens = item_name.to_token_stream();
+    let provided_driver = ProvidedDrivers::SingleWithChecks {
+        driver: driver.clone(),
+        checks: vec![driver.clone()],
+    };
+    let mut data = CollectedData::new(
+        &mut format_str,
+        &mut format_params,
+        &mut binds,
+        &mut checks,
+        sql_crate,
+        &provided_driver,
+        &mut current_param_n,
+        &mut before_param_n,
+        &mut before_format,
+        None,
+        Some(&item_name_tokens),
+        &mut types_driver_support_needed,
+    );
+    // Custom select mode inlines literals (constraints can't have bound parameters)
+    let sql_template = expr.into_query_string(&mut data, false, true);
+
+    Ok(quote! {
+        {
+            // Compile-time validation of columns used in the expression
+            let _ = || {
+                let ___t___ = #macro_support::never_any::<#item_name>();
+                #(#checks)*
+            };
+
+            #[allow(unused_variables)]
+            let delimeter = <#driver as #sql_crate::Driver>::identifier_delimiter();
+            format!(#sql_template, #(#format_params),*)
+        }
+    })
+}
+
+/// `PatchColumns` and `PatchBind` implementations used by `Patch`
+#[always_context]
+fn patch_impl(
+    item_name: &syn::Ident,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    table_name: &str,
+) -> anyhow::Result<TokenStream> {
+    let sql_crate = sql_crate();
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    let mut column_names = Vec::new();
+    let mut check_arms = Vec::new();
+    let mut bind_arms = Vec::new();
+    let mut where_clauses_types = Vec::new();
+    for field in fields.iter() {
+        let field_name_str = field.ident.as_ref()?.to_string();
+        let field_ty = &field.ty;
+        let storage = FieldStorage::of(field)?;
+        let bound_ty = storage.bound_ty(&macro_support, &field_ty.to_token_stream());
+        where_clauses_types.push(quote! {
+            for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+            #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+        });
+
+        check_arms.push(quote! {
+            #field_name_str => #macro_support::check_patch_value::<#field_ty>(#table_name, column, value),
+        });
+        let binding_expr = if storage != FieldStorage::Native {
+            storage.to_column(&macro_support, quote! { &value })
+        } else {
+            quote! { value }
+        };
+        let debug_format_str = format!("Binding field `{}` to query failed", field_name_str);
+        bind_arms.push(quote! {
+            #field_name_str => {
+                let value = #macro_support::patch_value::<#field_ty>(#table_name, column, value)?;
+                args_list
+                    .add(#binding_expr)
+                    .map_err(#macro_support::Error::from_boxed)
+                    .context(#debug_format_str)?;
+            }
+        });
+        column_names.push(field_name_str);
+    }
+
+    Ok(quote! {
+        impl #macro_support::PatchColumns for #item_name {
+            const TABLE_NAME: &'static str = #table_name;
+
+            fn patch_column(column: &str) -> Option<&'static str> {
+                match column {
+                    #(#column_names => Some(#column_names),)*
+                    _ => None,
+                }
+            }
+
+            fn check_patch_value(
+                column: &'static str,
+                value: &#macro_support::PatchValue,
+            ) -> #macro_support::Result<()> {
+                match column {
+                    #(#check_arms)*
+                    _ => Err(#macro_support::invalid_patch(#table_name, column, "unknown column")),
+                }
+            }
+        }
+
+        impl<'a, D: #sql_crate::Driver> #macro_support::PatchBind<'a, D> for #item_name
+        where #(#where_clauses_types)* {
+            fn bind_patch_value(
+                column: &'static str,
+                value: #macro_support::PatchValue,
+                mut args_list: #macro_support::DriverArguments<'a, D>,
+            ) -> #macro_support::Result<#macro_support::DriverArguments<'a, D>> {
+                use #macro_support::{Arguments as _, Context as _};
+
+                match column {
+                    #(#bind_arms)*
+                    _ => return Err(#macro_support::invalid_patch(#table_name, column, "unknown column")),
+                }
+                Ok(args_list)
+            }
+        }
+    })
+}
+
 #[always_context]
 pub fn table(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenStream> {
     let item = parse_macro_input!(item as syn::ItemStruct);
@@ -77,6 +303,124 @@ pub fn table(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenS
         .map(|field| field.ident.as_ref().unwrap().to_string())
         .collect::<Vec<_>>();
 
+    // `#[sql(created_at)]` / `#[sql(updated_at)]` columns, filled in by the database
+    let mut timestamp_fields = Vec::new();
+    let mut updated_at_fields = Vec::new();
+    for field in fields.iter() {
+        let created_at = has_attributes!(field, #[sql(created_at)]);
+        let updated_at = has_attributes!(field, #[sql(updated_at)]);
+        if created_at && updated_at {
+            anyhow::bail!(
+                "Field `{}` can't use both #[sql(created_at)] and #[sql(updated_at)]",
+                field.ident.as_ref()?
+            );
+        }
+        if created_at || updated_at {
+            timestamp_fields.push(field.ident.clone()?);
+        }
+        if updated_at {
+            updated_at_fields.push(field.ident.as_ref()?.to_string());
+        }
+    }
+    // `#[sql(generated = ...)]` columns, computed by the database
+    let mut generated_fields = Vec::new();
+    for field in fields.iter() {
+        if generated_column(field)?.is_none() {
+            continue;
+        }
+        let field_name = field.ident.as_ref()?;
+        if !get_attributes!(field, #[sql(default = __unknown__)]).is_empty()
+            || has_attributes!(field, #[sql(auto_increment)])
+            || timestamp_fields.contains(field_name)
+        {
+            anyhow::bail!(
+                "Generated field `{}` can't have a default value, auto increment or timestamp attribute",
+                field_name
+            );
+        }
+        generated_fields.push(field_name.clone());
+    }
+    // Fields set by the user in updates
+    let user_fields = fields
+        .iter()
+        .filter(|field| {
+            let field_name = field.ident.as_ref().unwrap();
+            !timestamp_fields.contains(field_name) && !generated_fields.contains(field_name)
+        })
+        .cloned()
+        .collect::<syn::punctuated::Punctuated<syn::Field, syn::Token![,]>>();
+
+    // `#[sql(soft_delete)]` / `#[sql(soft_delete = column)]` - rows are marked in the column
+    // instead of being removed
+    let mut soft_delete_columns = Vec::new();
+    if has_attributes!(item, #[sql(soft_delete)]) {
+        soft_delete_columns.push(DEFAULT_SOFT_DELETE_COLUMN.to_string());
+    }
+    for attr_data in get_attributes!(item, #[sql(soft_delete = __unknown__)]) {
+        let column: syn::Ident = syn::parse2(attr_data.clone())
+            .context("Invalid soft delete column, expected field name")?;
+        soft_delete_columns.push(column.to_string());
+    }
+    if soft_delete_columns.len() > 1 {
+        anyhow::bail!("Only one #[sql(soft_delete)] attribute is allowed");
+    }
+    let soft_delete_field = soft_delete_columns.pop();
+    if let Some(soft_delete_field) = &soft_delete_field {
+        let field = fields
+            .iter()
+            .find(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| ident == soft_delete_field)
+            })
+            .with_context(|| {
+                format!("#[sql(soft_delete)] requires a `{soft_delete_field}: Option<...>` field")
+            })?;
+        let is_option = matches!(&field.ty, syn::Type::Path(type_path)
+            if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Option"));
+        if !is_option {
+            anyhow::bail!(
+                "#[sql(soft_delete)] requires the `{}` field to be an Option",
+                soft_delete_field
+            );
+        }
+    }
+    let soft_delete_column = match &soft_delete_field {
+        Some(soft_delete_field) => quote! { Some(#soft_delete_field) },
+        None => quote! { None },
+    };
+    // `#[sql(lock_version)]` - optimistic locking, checked and incremented by every update
+    let mut lock_version_column = quote! { None };
+    let mut lock_version_field = None;
+    for field in fields.iter() {
+        if !has_attributes!(field, #[sql(lock_version)]) {
+            continue;
+        }
+        let field_name = field.ident.as_ref()?;
+        if lock_version_field.replace(field_name).is_some() {
+            anyhow::bail!("Only one #[sql(lock_version)] field is allowed");
+        }
+        if !is_integer(&field.ty) || generated_fields.contains(field_name) {
+            anyhow::bail!(
+                "#[sql(lock_version)] field `{}` has to be a (non generated) integer",
+                field_name
+            );
+        }
+        let field_name_str = field_name.to_string();
+        lock_version_column = quote! { Some(#field_name_str) };
+    }
+    // Fields set by the user in inserts, new rows are never soft deleted
+    let insert_fields = user_fields
+        .iter()
+        .filter(|field| {
+            soft_delete_field
+                .as_ref()
+                .is_none_or(|column| field.ident.as_ref().unwrap() != column)
+        })
+        .cloned()
+        .collect::<syn::punctuated::Punctuated<syn::Field, syn::Token![,]>>();
+
     let mut table_name = item_name.to_string().to_case(Case::Snake);
     #[cfg(feature = "check_duplicate_table_names")]
     let mut table_name_attr_used = false;
@@ -94,6 +438,17 @@ pub fn table(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenS
             table_name_attr_used = true;
         }
     }
+    // Postgres schema or SQLite attached database alias
+    let mut schema = None;
+    for attr_data in get_attributes!(item, #[sql(schema = __unknown__)]) {
+        if schema.is_some() {
+            anyhow::bail!("Only one schema attribute is allowed");
+        }
+        let lit_str: LitStr = syn::parse2(attr_data.clone())
+            .context("Invalid schema provided, expected string with quotes")?;
+        schema = Some(lit_str.value());
+    }
+
     #[cfg(feature = "migrations")]
     let no_version = has_attributes!(item, #[sql(no_version)]);
 
@@ -126,8 +481,14 @@ pub fn table(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenS
 
     let compilation_data = CompilationData::load_in_macro()?;
 
+    // Tables from different schemas can share the same name
+    #[cfg(feature = "check_duplicate_table_names")]
+    let used_name = match &schema {
+        Some(schema) => format!("{schema}.{table_name}"),
+        None => table_name.clone(),
+    };
     #[cfg(feature = "check_duplicate_table_names")]
-    if let Some(entries) = compilation_data.used_table_names.get(&table_name) {
+    if let Some(entries) = compilation_data.used_table_names.get(&used_name) {
         if entries.len() > 1 {
             let mut lines = entries
                 .iter()
@@ -226,6 +587,19 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
 
         let converted_to_version = TableDataVersion::from_struct(&item, table_name.clone())?;
 
+        let migration_driver = quote! { _EasySqlMigrationDriver };
+        let mut latest_constraints = HashMap::new();
+        for (name, expression) in TableCheck::collect(&item, &table_name)? {
+            let constraint =
+                check_constraint(item_name, &name, &expression, &migration_driver, &sql_crate)?;
+            latest_constraints.insert(name, constraint);
+        }
+        let mut latest_indexes = HashMap::new();
+        for index in TableIndex::from_struct(&item, &table_name)? {
+            let definition = index_definition(item_name, &index, &migration_driver, &sql_crate)?;
+            latest_indexes.insert(index.name, definition);
+        }
+
         let migrations = if let Some(table_data) =
             compilation_data.tables.get(&unique_id_lit.value())
         {
@@ -235,6 +609,8 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                 table_version,
                 &sql_crate,
                 &item_name.to_token_stream(),
+                &latest_constraints,
+                &latest_indexes,
             )?;
 
             if let Some(this_version) = table_data.saved_versions.get(&table_version)
@@ -263,6 +639,8 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
 
     let mut result_builder = TokensBuilder::default();
 
+    result_builder.add(FieldStorage::json_feature_check(fields.iter(), &sql_crate)?);
+
     let output_impl = sql_output_base(
         item_name,
         &fields,
@@ -274,15 +652,52 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
 
     let insert_impl = sql_insert_base(
         item_name,
-        &fields,
+        &insert_fields,
         &item_name_tokens,
         &supported_drivers,
         vec![] as Vec<syn::Ident>,
     )?;
     result_builder.add(insert_impl);
-    let update_impl = sql_update_base(item_name, &fields, &item_name_tokens, &supported_drivers)?;
+    let update_impl = sql_update_base(
+        item_name,
+        &user_fields,
+        &item_name_tokens,
+        &supported_drivers,
+    )?;
     result_builder.add(update_impl);
 
+    // `#[sql(patch)]` - columns set at runtime by `Patch`
+    if has_attributes!(item, #[sql(patch)]) {
+        result_builder.add(quote! {
+            #sql_crate::easy_sql_require_json_feature!("#[sql(patch)]");
+        });
+        result_builder.add(patch_impl(item_name, &insert_fields, &table_name)?);
+    }
+
+    // Columns the Insert derive checks to be provided
+    let insert_columns = insert_fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap().to_string());
+    result_builder.add(quote! {
+        impl #item_name {
+            #[doc(hidden)]
+            pub const __EASY_SQL_INSERT_COLUMNS: Option<&'static [&'static str]> =
+                Some(&[#(#insert_columns),*]);
+        }
+    });
+
+    // The Update derive checks that fields of the column are bindable
+    result_builder.add(quote! {
+        impl #item_name {
+            #[doc(hidden)]
+            pub const __EASY_SQL_LOCK_VERSION_COLUMN: Option<&'static str> = #lock_version_column;
+        }
+    });
+
+    let indexes = TableIndex::from_struct(&item, &table_name)?;
+    let checks = TableCheck::collect(&item, &table_name)?;
+    let uniques = TableUnique::from_struct(&item, &table_name)?;
+
     let mut primary_keys = Vec::new();
 
     for field in fields.iter() {
@@ -292,6 +707,47 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
         }
     }
 
+    // Parent keys of `#[sql(related)]` fields are checked against it by the Output derive
+    result_builder.add(quote! {
+        impl #item_name {
+            #[doc(hidden)]
+            pub const __EASY_SQL_PRIMARY_KEYS: Option<&'static [&'static str]> =
+                Some(&[#(#primary_keys),*]);
+        }
+    });
+
+    // Single column foreign keys, used by `load_related`
+    let mut related_fields: Vec<(syn::Path, Vec<&syn::Field>)> = Vec::new();
+    for field in fields.iter() {
+        for foreign_key in get_attributes!(field, #[sql(foreign_key = __unknown__)]) {
+            let foreign_key: ForeignKeyParsed = syn::parse2(foreign_key.clone())
+                .context("Expected foreign key to be a table name")?;
+            match related_fields
+                .iter_mut()
+                .find(|(table, _)| *table == foreign_key.table_struct)
+            {
+                Some((_, fields)) => fields.push(field),
+                None => related_fields.push((foreign_key.table_struct, vec![field])),
+            }
+        }
+    }
+    for (parent, parent_fields) in related_fields.iter() {
+        let [field] = parent_fields.as_slice() else {
+            continue;
+        };
+        let column = field.ident.as_ref()?.to_string();
+        let key_ty = option_inner(&field.ty).unwrap_or(&field.ty);
+        result_builder.add(quote! {
+            impl<EasySqlD: #sql_crate::Driver> #sql_crate::Related<#parent, EasySqlD> for #item_name {
+                type Key = #key_ty;
+
+                fn foreign_key_column() -> &'static str {
+                    #column
+                }
+            }
+        });
+    }
+
     for driver in supported_drivers {
         let mut foreign_keys = HashMap::new();
 
@@ -302,6 +758,10 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
         let mut field_types = Vec::new();
         let mut is_not_null = Vec::new();
         let mut is_auto_increment_list = Vec::new();
+        let mut generated_list = Vec::new();
+        let mut is_generated = Vec::new();
+        // Foreign key table and position of the field in it, for each field
+        let mut field_foreign_keys = Vec::new();
         // First token streamn represents data before the driver
         // Second token stream represents data after the driver
         let mut default_values: Vec<TokenStream> = Vec::new();
@@ -317,18 +777,21 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
             }
 
             //Foreign Key Check
+            let mut referenced = Vec::new();
             for foreign_key in get_attributes!(field, #[sql(foreign_key = __unknown__)]) {
                 let foreign_key: ForeignKeyParsed = syn::parse2(foreign_key.clone())
                     .context("Expected foreign key to be a table name")?;
 
                 let fields: &mut (Vec<String>, bool) = foreign_keys
-                    .entry(foreign_key.table_struct)
+                    .entry(foreign_key.table_struct.clone())
                     .or_insert((Default::default(), foreign_key.cascade));
                 fields.0.push(field.ident.as_ref()?.to_string());
                 if foreign_key.cascade {
                     fields.1 = true;
                 }
+                referenced.push((foreign_key.table_struct, fields.0.len() - 1));
             }
+            field_foreign_keys.push(referenced);
             //Get `Is Not Null`
             let is_field_not_null = match &field_type {
                 syn::Type::Path(type_path) => {
@@ -345,29 +808,37 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
             //Unique Check
             is_unique.push(has_attributes!(field, #[sql(unique)]));
 
-            //Binary Check and get field type
-            let binary_field = if has_attributes!(field, #[sql(bytes)]) {
-                field_types.push(quote! {
-                    {
-                        #macro_support::TypeInfo::name(
-                            &<Vec<u8> as #macro_support::Type<#macro_support::InternalDriver<#driver>>>::type_info(),
-                        )
-                        .to_owned()
-                    }
+            //Generated Column Check
+            is_generated.push(generated_fields.contains(field.ident.as_ref()?));
+            if let Some((expression, stored)) = generated_column(field)? {
+                let expression = table_expression(
+                    item_name,
+                    &expression,
+                    "generated column",
+                    &driver.to_token_stream(),
+                    &sql_crate,
+                )?;
+                generated_list.push(quote! {
+                    Some(#sql_crate::driver::GeneratedColumn {
+                        expression: #expression,
+                        stored: #stored,
+                    })
                 });
-                true
             } else {
-                field_types.push(quote! {
-                    {
-                        #macro_support::TypeInfo::name(
-                            &<#field_type as #macro_support::Type<#macro_support::InternalDriver<#driver>>>::type_info(),
-                        )
-                        .to_owned()
-                    }
-                });
+                generated_list.push(quote! {None});
+            }
 
-                false
-            };
+            //Binary/JSON Check and get field type
+            let storage = FieldStorage::of(field)?;
+            let column_ty = storage.bound_ty(&macro_support, &field_type.to_token_stream());
+            field_types.push(quote! {
+                {
+                    #macro_support::TypeInfo::name(
+                        &<#column_ty as #macro_support::Type<#macro_support::InternalDriver<#driver>>>::type_info(),
+                    )
+                    .to_owned()
+                }
+            });
 
             //Default Value Check
             let mut default_value_found = false;
@@ -381,16 +852,17 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                 syn::parse2::<syn::Expr>(default_value.clone())
                     .context("Expected default value to be an expression")?;
 
-                if binary_field {
+                if let Some(to_column) = storage.to_column_fn(&macro_support) {
                     let error_context = format!(
-                        "Converting default value `{}` to bytes for field `{}`, struct name: `{}`, table name: `{}`",
+                        "Converting default value `{}` to {} for field `{}`, struct name: `{}`, table name: `{}`",
                         default_value.to_token_stream(),
+                        storage.description(),
                         field_name,
                         item_name,
                         table_name
                     );
 
-                    //Convert provided default value to bytes
+                    //Convert provided default value to the column type
                     default_values.push(quote! {
                         {
                             //Check if default value has valid type for the current column
@@ -400,7 +872,7 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                             };
 
                             let default_v = #macro_support::Context::context(
-                                #macro_support::to_binary(#default_value),
+                                #to_column(#default_value),
                                 #error_context,
                             )?;
 
@@ -424,7 +896,13 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                 default_value_found = true;
             }
             if !default_value_found {
-                default_values.push(quote! {None});
+                if timestamp_fields.contains(field.ident.as_ref()?) {
+                    default_values.push(quote! {
+                        Some(<#driver as #sql_crate::Driver>::current_timestamp().to_string())
+                    });
+                } else {
+                    default_values.push(quote! {None});
+                }
             }
         }
 
@@ -465,6 +943,43 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
             quote! {}
         };
 
+        // Foreign key targets of every field, used by `TableMetadata`
+        let foreign_key_metas = field_foreign_keys
+            .iter()
+            .zip(field_names_str.iter())
+            .map(|(referenced, field_name)| {
+                let metas = referenced.iter().map(|(foreign_table, index)| {
+                    let cascade = foreign_keys
+                        .get(foreign_table)
+                        .is_some_and(|(_, cascade)| *cascade);
+                    let error_context = format!(
+                        "Foreign key field `{}` of `{}` has no matching primary key in `{}` (more foreign key fields than primary keys)",
+                        field_name,
+                        item_name,
+                        foreign_table.to_token_stream().to_string().replace(' ', "")
+                    );
+                    quote! {
+                        #sql_crate::ForeignKeyMeta {
+                            table: <#foreign_table as #sql_crate::Table<#driver>>::table_name(),
+                            column: #macro_support::Context::context(
+                                <#foreign_table as #sql_crate::Table<#driver>>::primary_keys()
+                                    .get(#index)
+                                    .copied(),
+                                #error_context,
+                            )?,
+                            cascade: #cascade,
+                        }
+                    }
+                });
+                quote! { vec![#(#metas),*] }
+            })
+            .collect::<Vec<_>>();
+        let is_primary_key = field_names_str
+            .iter()
+            .map(|name| primary_keys.contains(name))
+            .collect::<Vec<_>>();
+        let field_rust_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
         // Foreign keys converted
         let foreign_keys = {
             let mut foreign_keys_converted = Vec::new();
@@ -473,6 +988,7 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                     (
                         <#foreign_table as #sql_crate::Table<#driver>>::table_name(),
                         (
+                            <#foreign_table as #sql_crate::Table<#driver>>::schema(),
                             vec![#(#referenced_fields),*],
                             <#foreign_table as #sql_crate::Table<#driver>>::primary_keys(),
                             #cascade
@@ -483,29 +999,71 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
             foreign_keys_converted
         };
 
+        let create_indexes = if indexes.is_empty() {
+            quote! {}
+        } else {
+            let driver_tokens = driver.to_token_stream();
+            let indexes = indexes
+                .iter()
+                .map(|index| index_definition(item_name, index, &driver_tokens, &sql_crate))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            quote! {
+                #sql_crate::EasyExecutor::query_setup(conn, #sql_crate::driver::AlterTable{
+                    schema: <#item_name as #sql_crate::Table<#driver>>::schema(),
+                    table_name: #table_name,
+                    alters: vec![#(
+                        #sql_crate::driver::AlterTableSingle::CreateIndex{
+                            index: #indexes,
+                        }
+                    ),*],
+                }).await?;
+            }
+        };
+
+        let driver_tokens = driver.to_token_stream();
+        let mut constraints = checks
+            .iter()
+            .map(|(name, expression)| {
+                check_constraint(item_name, name, expression, &driver_tokens, &sql_crate)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        constraints.extend(
+            uniques
+                .iter()
+                .map(|unique| unique.to_driver_tokens(&sql_crate)),
+        );
+
         let create_table = quote! {
-            <#driver as #sql_crate::Driver>::create_table(
-                    conn,
-                    #table_name,
-                    vec![
-                        #(
-                        #sql_crate::driver::TableField{
-                            name: #field_names_str,
-                            data_type: #field_types,
-                            is_unique: #is_unique,
-                            is_not_null: #is_not_null,
-                            default: #default_values,
-                            is_auto_increment: #is_auto_increment_list,
+            let create_result: #macro_support::Result<()> = async {
+                <#driver as #sql_crate::Driver>::create_table(
+                        conn,
+                        <#item_name as #sql_crate::Table<#driver>>::schema(),
+                        #table_name,
+                        vec![
+                            #(
+                            #sql_crate::driver::TableField{
+                                name: #field_names_str,
+                                data_type: #field_types,
+                                is_unique: #is_unique,
+                                is_not_null: #is_not_null,
+                                default: #default_values,
+                                is_auto_increment: #is_auto_increment_list,
+                                generated: #generated_list,
+                            },
+                            )*
+                        ],
+                        vec![#(#primary_keys),*],
+                        {
+                            vec![#(#foreign_keys),*]
+                            .into_iter()
+                            .collect()
                         },
-                        )*
-                    ],
-                    vec![#(#primary_keys),*],
-                    {
-                        vec![#(#foreign_keys),*]
-                        .into_iter()
-                        .collect()
-                    },
-                ).await?;
+                        vec![#(#constraints),*],
+                    ).await?;
+                #create_indexes
+                Ok(())
+            }.await;
+            create_result.map_err(#macro_support::setup_error::<#item_name, #driver>)?;
         };
 
         let setup_body = if skip_migrations {
@@ -525,7 +1083,13 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                 let current_version_number = #sql_crate::EasySqlTables_get_version!(#driver, *conn,#unique_id);
 
                 if let Some(current_version_number) = current_version_number{
-                    #migrations
+                    let migration_result: #macro_support::Result<()> = async {
+                        #migrations
+                        Ok(())
+                    }.await;
+                    migration_result.map_err(|err| {
+                        #macro_support::migration_error(err, #table_name, current_version_number, #table_version_i64)
+                    })?;
                 }else{
                     // Create table and create version in EasySqlTables
                     #primary_key_check
@@ -549,14 +1113,59 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
                 }
             }
 
+            impl #sql_crate::TableMetadata<#driver> for #item_name {
+                fn table_meta() -> #macro_support::Result<&'static #sql_crate::TableMeta> {
+                    static TABLE_META: ::std::sync::OnceLock<#sql_crate::TableMeta> =
+                        ::std::sync::OnceLock::new();
+                    if let Some(table_meta) = TABLE_META.get() {
+                        return Ok(table_meta);
+                    }
+
+                    let table_meta = #sql_crate::TableMeta {
+                        name: #table_name,
+                        schema: <#item_name as #sql_crate::Table<#driver>>::schema(),
+                        primary_keys: vec![#(#primary_keys),*],
+                        columns: vec![
+                            #(
+                            #sql_crate::ColumnMeta {
+                                name: #field_names_str,
+                                rust_type: ::std::any::type_name::<#field_rust_types>(),
+                                sql_type: #field_types,
+                                nullable: !#is_not_null,
+                                unique: #is_unique,
+                                primary_key: #is_primary_key,
+                                auto_increment: #is_auto_increment_list,
+                                default: #default_values,
+                                generated: #is_generated,
+                                foreign_keys: #foreign_key_metas,
+                            },
+                            )*
+                        ],
+                    };
+                    Ok(TABLE_META.get_or_init(|| table_meta))
+                }
+            }
+
         });
     }
 
+    // Without the attribute, the driver default schema is used
+    let schema_fn = match &schema {
+        Some(schema) => quote! {
+            fn schema() -> Option<&'static str> {
+                Some(#schema)
+            }
+        },
+        None => quote! {},
+    };
+
     result_builder.add(quote! {
         impl #sql_crate::markers::HasTable<#item_name> for #item_name{}
 
         impl #sql_crate::markers::NotJoinedTable for #item_name {}
 
+        impl #sql_crate::markers::WritableTable for #item_name {}
+
         impl<EasySqlD:#sql_crate::Driver> #sql_crate::Table<EasySqlD> for #item_name {
 
             fn table_name() -> &'static str {
@@ -572,6 +1181,20 @@ Tip: Use `#[sql(table_name = ...)]` or rename one of the structs",
             fn table_joins(current_query: &mut String) {
 
             }
+
+            fn updated_at_columns() -> &'static [&'static str] {
+                &[#(#updated_at_fields),*]
+            }
+
+            fn soft_delete_column() -> Option<&'static str> {
+                #soft_delete_column
+            }
+
+            fn lock_version_column() -> Option<&'static str> {
+                #lock_version_column
+            }
+
+            #schema_fn
         }
     });
 
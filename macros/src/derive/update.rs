@@ -9,8 +9,8 @@ use easy_sql_compilation_data::CompilationData;
 
 use crate::{
     derive_components::{
-        UPDATE_FIELD_KEYS, UPDATE_STRUCT_KEYS, supported_drivers, ty_to_variant,
-        validate_sql_attribute_keys,
+        FieldStorage, UPDATE_FIELD_KEYS, UPDATE_STRUCT_KEYS, is_integer, supported_drivers,
+        ty_to_variant, validate_sql_attribute_keys,
     },
     sql_crate,
 };
@@ -53,33 +53,30 @@ pub fn sql_update_base(
     let mut validity_checks = Vec::new();
     let mut driver_test_values = Vec::new();
     let mut where_clauses_types = Vec::new();
+    let mut lock_version_branches = Vec::new();
+    let mut lock_version_checks = Vec::new();
 
     for field in fields.iter() {
         let field_name = field.ident.as_ref().unwrap();
         let field_name_str = field_name.to_string();
         let query_format = format!("{{delimeter}}{}{{delimeter}} = {{}}, ", field_name_str);
         let field_ty = &field.ty;
-        let bytes = has_attributes!(field, #[sql(bytes)]);
-        if bytes {
-            let bound_ty = quote! { Vec<u8> };
-            where_clauses_types.push(quote! {
-                for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
-            });
-        } else {
-            where_clauses_types.push(quote! {
-                for<'__easy_sql_x> #field_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                #field_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
-            });
-        }
+        let storage = FieldStorage::of(field)?;
+        let bound_ty = storage.bound_ty(&macro_support, &field_ty.to_token_stream());
+        where_clauses_types.push(quote! {
+            for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+            #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+        });
 
         let maybe_update =
             has_attributes!(field, #[sql(maybe_update)]) || has_attributes!(field, #[sql(maybe)]);
+        let lock_version_bindable =
+            !maybe_update && storage == FieldStorage::Native && is_integer(field_ty);
 
         let ty_variant_for_checks = ty_to_variant(
             quote! {_self},
             field_name.to_token_stream(),
-            bytes,
+            storage,
             &sql_crate,
         )?;
 
@@ -101,19 +98,11 @@ pub fn sql_update_base(
             };
 
             if nested_option {
-                if bytes {
-                    let bound_ty = quote! { Vec<u8> };
-                    where_clauses_types.push(quote! {
-                        for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                        #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
-                    });
-                } else {
-                    let option_base_ty = quote! { Option<#base_ty> };
-                    where_clauses_types.push(quote! {
-                        for<'__easy_sql_x> #option_base_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
-                        #option_base_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
-                    });
-                }
+                let bound_ty = storage.bound_ty(&macro_support, &quote! { Option<#base_ty> });
+                where_clauses_types.push(quote! {
+                    for<'__easy_sql_x> #bound_ty: #macro_support::Encode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+                    #bound_ty: #macro_support::Type<#macro_support::InternalDriver<D>>,
+                });
             }
 
             validity_checks.push(quote! {
@@ -139,18 +128,19 @@ pub fn sql_update_base(
                 }
             });
 
-            let driver_value = if bytes {
-                quote! {
-                    #macro_support::to_binary(&#macro_support::never_any::<Option<#base_ty>>())?
-                }
+            let driver_value = if storage != FieldStorage::Native {
+                storage.to_column(
+                    &macro_support,
+                    quote! { &#macro_support::never_any::<Option<#base_ty>>() },
+                )
             } else {
                 quote! { #macro_support::never_any::<Option<#base_ty>>() }
             };
             driver_test_values.push(driver_value);
 
             if nested_option {
-                let binding_expr = if bytes {
-                    quote! { #macro_support::to_binary(&value)? }
+                let binding_expr = if storage != FieldStorage::Native {
+                    storage.to_column(&macro_support, quote! { &value })
                 } else {
                     quote! { value }
                 };
@@ -168,11 +158,7 @@ pub fn sql_update_base(
                     }
                 });
 
-                let binding_expr_ref = if bytes {
-                    quote! { #macro_support::to_binary(value)? }
-                } else {
-                    quote! { value }
-                };
+                let binding_expr_ref = storage.to_column(&macro_support, quote! { value });
                 update_statements_ref.push(quote! {
                     if let Some(value) = &self.#field_name {
                         current_query.push_str(&format!(
@@ -187,8 +173,8 @@ pub fn sql_update_base(
                     }
                 });
             } else {
-                let binding_expr = if bytes {
-                    quote! { #macro_support::to_binary(&update_value)? }
+                let binding_expr = if storage != FieldStorage::Native {
+                    storage.to_column(&macro_support, quote! { &update_value })
                 } else {
                     quote! { update_value }
                 };
@@ -207,11 +193,8 @@ pub fn sql_update_base(
                     }
                 });
 
-                let binding_expr_ref = if bytes {
-                    quote! { #macro_support::to_binary(&self.#field_name)? }
-                } else {
-                    quote! { &self.#field_name }
-                };
+                let binding_expr_ref =
+                    storage.to_column(&macro_support, quote! { &self.#field_name });
                 update_statements_ref.push(quote! {
                     if self.#field_name.is_some() {
                         current_query.push_str(&format!(
@@ -230,10 +213,10 @@ pub fn sql_update_base(
             let ty_variant = ty_to_variant(
                 quote! {self},
                 field_name.to_token_stream(),
-                bytes,
+                storage,
                 &sql_crate,
             )?;
-            let debug_value = if bytes {
+            let debug_value = if storage != FieldStorage::Native {
                 quote! { &self.#field_name }
             } else {
                 quote! { #ty_variant }
@@ -275,11 +258,7 @@ pub fn sql_update_base(
                 *parameter_n += 1;
             });
 
-            let binding_expr_ref = if bytes {
-                quote! { #macro_support::to_binary(&self.#field_name)? }
-            } else {
-                quote! { &self.#field_name }
-            };
+            let binding_expr_ref = storage.to_column(&macro_support, quote! { &self.#field_name });
             update_statements_ref.push(quote! {
                 current_query.push_str(&format!(
                     #query_format,
@@ -291,9 +270,98 @@ pub fn sql_update_base(
                     .with_context(|| format!(#debug_format_str_ref, #debug_value))?;
                 *parameter_n += 1;
             });
+
+            if lock_version_bindable {
+                lock_version_branches.push(quote! {
+                    if column == #field_name_str {
+                        args_list
+                            .add(self.#field_name)
+                            .map_err(#macro_support::Error::from_boxed)
+                            .context(#debug_format_str)?;
+                        return Ok((args_list, true));
+                    }
+                });
+            }
+        }
+
+        // Lock version column is bound by `Update::lock_version` (and incremented below), other
+        // fields of the column would never be written
+        if !lock_version_bindable {
+            let message = format!(
+                "Field `{}` of `{}` is the #[sql(lock_version)] column of `{}`, it has to be a plain integer (without #[sql(maybe_update)], #[sql(bytes)] or #[sql(json)])",
+                field_name_str,
+                item_name,
+                table.to_string().replace(' ', "")
+            );
+            lock_version_checks.push(quote! {
+                const _: () = {
+                    use #macro_support::LockVersionColumnFallback as _;
+                    assert!(
+                        !#macro_support::is_lock_version_column(
+                            <#table>::__EASY_SQL_LOCK_VERSION_COLUMN,
+                            #field_name_str,
+                        ),
+                        #message
+                    );
+                };
+            });
+        }
+
+        // Lock version column is bound by `Update::lock_version` (and incremented below)
+        for statements in [&mut update_statements, &mut update_statements_ref] {
+            if let Some(statement) = statements.last_mut() {
+                let inner = std::mem::take(statement);
+                *statement = quote! {
+                    if __easy_sql_lock_version_column != Some(#field_name_str) {
+                        #inner
+                    }
+                };
+            }
         }
     }
 
+    // `#[sql(updated_at)]` columns not set by the struct itself
+    let field_names_str = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap().to_string());
+    let updated_at_statements = quote! {
+        const __EASY_SQL_UPDATED_COLUMNS: &[&str] = &[#(#field_names_str),*];
+        for column in <#table as #sql_crate::Table<D>>::updated_at_columns() {
+            if !__EASY_SQL_UPDATED_COLUMNS.contains(column) {
+                current_query.push_str(&format!(
+                    "{delimeter}{column}{delimeter} = {}, ",
+                    <D as #sql_crate::Driver>::current_timestamp()
+                ));
+            }
+        }
+    };
+
+    // `#[sql(lock_version)]` column, incremented by every update
+    let lock_version_statements = quote! {
+        if let Some(column) = __easy_sql_lock_version_column {
+            current_query.push_str(&format!(
+                "{delimeter}{column}{delimeter} = {delimeter}{column}{delimeter} + 1, "
+            ));
+        }
+    };
+
+    let lock_version_fn = if lock_version_branches.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn lock_version(
+                &self,
+                mut args_list: #macro_support::DriverArguments<'a, D>,
+                column: &str,
+            ) -> #macro_support::Result<(#macro_support::DriverArguments<'a, D>, bool)> {
+                use #macro_support::{Arguments as _, Context as _};
+
+                #(#lock_version_branches)*
+                Ok((args_list, false))
+            }
+        }
+    };
+
     let driver_tests = drivers.iter().map(|driver| {
         quote! {
             let _=|mut args_list:#macro_support::DriverArguments<'a, #driver>|{
@@ -307,8 +375,10 @@ pub fn sql_update_base(
     });
 
     Ok(quote! {
+        #(#lock_version_checks)*
+
         impl<'a,D:#sql_crate::Driver> #sql_crate::Update<'a,#table, D> for #item_name
-        where #(#where_clauses_types)* {
+        where #table: #sql_crate::Table<D>, #(#where_clauses_types)* {
 
             fn updates(
                 self,
@@ -329,19 +399,25 @@ pub fn sql_update_base(
                 #(#driver_tests)*
 
                 let delimeter = <D as #sql_crate::Driver>::identifier_delimiter();
+                let __easy_sql_lock_version_column =
+                    <#table as #sql_crate::Table<D>>::lock_version_column();
                 let current_query_start_len = current_query.len();
 
                 #(#update_statements)*
+                #updated_at_statements
+                #lock_version_statements
                 if current_query.len() >= current_query_start_len + 2 {
                     current_query.pop();
                     current_query.pop();
                 }
                 Ok(args_list)
             }
+
+            #lock_version_fn
         }
 
         impl<'a,D:#sql_crate::Driver> #sql_crate::Update<'a,#table, D> for &'a #item_name
-        where #(#where_clauses_types)* {
+        where #table: #sql_crate::Table<D>, #(#where_clauses_types)* {
 
             fn updates(
                 self,
@@ -353,15 +429,21 @@ pub fn sql_update_base(
                 // Validity check needs to be done only once
 
                 let delimeter = <D as #sql_crate::Driver>::identifier_delimiter();
+                let __easy_sql_lock_version_column =
+                    <#table as #sql_crate::Table<D>>::lock_version_column();
 
 
                 #(#update_statements_ref)*
+                #updated_at_statements
+                #lock_version_statements
 
                 current_query.pop();
                 current_query.pop();
 
                 Ok(args_list)
             }
+
+            #lock_version_fn
         }
 
     })
@@ -402,5 +484,12 @@ pub fn update(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::Token
     let compilation_data = CompilationData::load_in_macro()?;
     let supported_drivers = supported_drivers(&item, &compilation_data, true)?;
 
-    sql_update_base(item_name, fields, &table, &supported_drivers).map(|tokens| tokens.into())
+    let json_feature_check = FieldStorage::json_feature_check(fields.iter(), &sql_crate())?;
+    let update_impl = sql_update_base(item_name, fields, &table, &supported_drivers)?;
+
+    Ok(quote! {
+        #json_feature_check
+        #update_impl
+    }
+    .into())
 }
@@ -0,0 +1,324 @@
+use std::collections::BTreeSet;
+
+use ::{
+    anyhow::{self, Context},
+    proc_macro2::TokenStream,
+    quote::{ToTokens, quote},
+    syn::{self, LitStr},
+};
+use convert_case::{Case, Casing};
+use easy_macros::{TokensBuilder, always_context, get_attributes, parse_macro_input};
+#[cfg(feature = "migrations")]
+use easy_macros::{has_attributes, token_stream_to_consistent_string};
+use easy_sql_compilation_data::CompilationData;
+
+use crate::{
+    derive::sql_output_base,
+    derive_components::{
+        VIEW_FIELD_KEYS, VIEW_STRUCT_KEYS, supported_drivers, validate_sql_attribute_keys,
+    },
+    macros_components::{CollectedData, ProvidedDrivers, SelectQuery, joined_field::JoinedField},
+    sql_crate,
+};
+
+/// Renders `#[sql(query = ...)]` select query into SQL `String` for `driver`
+///
+/// Columns are validated against the selected table, literals are inlined
+#[always_context]
+fn view_query(
+    select: SelectQuery,
+    driver: &TokenStream,
+    sql_crate: &TokenStream,
+) -> anyhow::Result<TokenStream> {
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    if select.output.output_args.is_some() {
+        anyhow::bail!("Output arguments are not supported inside of view queries");
+    }
+    if select.scalar.is_some() {
+        anyhow::bail!("Scalar outputs are not supported inside of view queries");
+    }
+    if select.order_by.is_some() || select.limit.is_some() {
+        anyhow::bail!("ORDER BY and LIMIT are not supported inside of view queries");
+    }
+
+    let mut indices = BTreeSet::new();
+    for expr in select.where_clause.iter().chain(select.having.iter()) {
+        expr.collect_indices_impl(&mut indices);
+    }
+    if !indices.is_empty() {
+        anyhow::bail!("Outside variables are not allowed inside of view queries");
+    }
+
+    let output_type = select.output.output_type;
+    let output_type_tokens = output_type.to_token_stream();
+    let table_type = select.table_type;
+    let table_type_tokens = table_type.to_token_stream();
+
+    let mut checks = Vec::new();
+    let mut format_params = Vec::new();
+    let mut format_str = String::new();
+    let mut binds = Vec::new();
+    let mut before_param_n = quote! {};
+    let mut before_format = Vec::new();
+    let mut current_param_n = 0usize;
+    let mut types_driver_support_needed = Vec::new();
+
+    let provided_driver = ProvidedDrivers::SingleWithChecks {
+        driver: driver.clone(),
+        checks: vec![driver.clone()],
+    };
+    let mut data = CollectedData::new(
+        &mut format_str,
+        &mut format_params,
+        &mut binds,
+        &mut checks,
+        sql_crate,
+        &provided_driver,
+        &mut current_param_n,
+        &mut before_param_n,
+        &mut before_format,
+        Some(&output_type_tokens),
+        Some(&table_type_tokens),
+        &mut types_driver_support_needed,
+    );
+
+    // Custom select mode inlines literals (views can't have bound parameters)
+    match (select.where_clause, select.include_deleted) {
+        (Some(where_expr), true) => {
+            let sql_template = where_expr.into_query_string(&mut data, false, true);
+            data.format_str.push_str(&format!(" WHERE {sql_template}"));
+        }
+        (Some(where_expr), false) => {
+            let sql_template = where_expr.into_query_string(&mut data, false, true);
+            data.format_str.push_str(&format!(
+                " WHERE {{_easy_sql_not_deleted_and}}{sql_template}{{_easy_sql_not_deleted_end}}"
+            ));
+        }
+        (None, true) => {}
+        (None, false) => data.format_str.push_str("{_easy_sql_not_deleted_where}"),
+    }
+    if let Some(group_by_list) = select.group_by {
+        let clause_args = group_by_list
+            .into_iter()
+            .map(|column| column.into_query_string(&mut data, true))
+            .collect::<Vec<_>>()
+            .join(", ");
+        data.format_str
+            .push_str(&format!(" GROUP BY {clause_args}"));
+    }
+    if let Some(having_expr) = select.having {
+        let sql_template = having_expr.into_query_string(&mut data, false, true);
+        data.format_str.push_str(&format!(" HAVING {sql_template}"));
+    }
+
+    if !binds.is_empty() || !before_format.is_empty() {
+        anyhow::bail!("Outside variables are not allowed inside of view queries");
+    }
+
+    let query_base_str = if select.distinct {
+        "SELECT DISTINCT "
+    } else {
+        "SELECT "
+    };
+    let soft_delete_filter = if select.include_deleted {
+        quote! {}
+    } else {
+        quote! {
+            let (_easy_sql_not_deleted_and, _easy_sql_not_deleted_end, _easy_sql_not_deleted_where) =
+                #macro_support::soft_delete_filter(
+                    <#table_type as #sql_crate::Table<#driver>>::soft_delete_column(),
+                    _easy_sql_d,
+                );
+        }
+    };
+
+    Ok(quote! {
+        {
+            // Compile-time validation of the output type and columns used in the query
+            let _ = |___t___: #table_type| {
+                let _ = || {
+                    fn __easy_sql_assert_normal<T: #sql_crate::markers::NormalSelect>() {}
+                    __easy_sql_assert_normal::<<#output_type as #macro_support::OutputData<#table_type>>::SelectProvider>();
+                };
+                #(#checks)*
+            };
+
+            #[allow(unused_variables)]
+            let delimeter = <#driver as #sql_crate::Driver>::identifier_delimiter();
+            let _easy_sql_d = delimeter;
+            #soft_delete_filter
+
+            let mut query = String::from(#query_base_str);
+            <#output_type as #sql_crate::Output<#table_type, #driver>>::select(&mut query);
+            query.push_str(&format!(
+                " FROM {}",
+                #macro_support::qualified_table_name::<#table_type, #driver>()
+            ));
+            <#table_type as #sql_crate::Table<#driver>>::table_joins(&mut query);
+            query.push_str(&format!(#format_str, #(#format_params),*));
+            query
+        }
+    })
+}
+
+#[always_context]
+pub fn view(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::TokenStream> {
+    let item = parse_macro_input!(item as syn::ItemStruct);
+
+    if let Some(error_tokens) =
+        validate_sql_attribute_keys(&item, "View", VIEW_STRUCT_KEYS, VIEW_FIELD_KEYS)
+    {
+        return Ok(error_tokens.into());
+    }
+
+    let item_name = &item.ident;
+    let item_name_tokens = item.ident.to_token_stream();
+
+    let sql_crate = sql_crate();
+    let macro_support = quote! { #sql_crate::macro_support };
+
+    let fields = match &item.fields {
+        syn::Fields::Named(fields_named) => fields_named.named.clone(),
+        syn::Fields::Unnamed(_) => {
+            anyhow::bail!("Unnamed struct fields are not supported")
+        }
+        syn::Fields::Unit => anyhow::bail!("Unit struct is not supported"),
+    };
+    let column_names = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    let mut query_attrs = get_attributes!(item, #[sql(query = __unknown__)]).into_iter();
+    let query_tokens = query_attrs
+        .next()
+        .context("#[sql(query = SELECT ... FROM ...)] attribute is required")?;
+    if query_attrs.next().is_some() {
+        anyhow::bail!("Only one query attribute is allowed");
+    }
+    let select: SelectQuery = syn::parse2(query_tokens.clone())
+        .with_context(|| format!("Invalid view query: `{query_tokens}`"))?;
+
+    let mut view_name = item_name.to_string().to_case(Case::Snake);
+    //Use name provided by the user if it exists
+    if let Some(attr_data) = get_attributes!(item, #[sql(table_name = __unknown__)])
+        .into_iter()
+        .next()
+    {
+        let lit_str: LitStr = syn::parse2(attr_data.clone())
+            .context("Invalid view name provided, expected string with  quotes")?;
+        view_name = lit_str.value();
+    }
+
+    let compilation_data = CompilationData::load_in_macro()?;
+
+    // Version of the view definition, saved in EasySqlTables to recreate the view only on change
+    #[cfg(feature = "migrations")]
+    let version = if has_attributes!(item, #[sql(no_version)]) {
+        None
+    } else {
+        let view_data = compilation_data.views.get(&view_name).with_context(|| {
+            format!(
+                "View `{}` not found in the compilation data (try to save the file)",
+                view_name
+            )
+        })?;
+        if view_data.definition != token_stream_to_consistent_string(query_tokens.clone()) {
+            anyhow::bail!(
+                "View `{}` definition in easy_sql.ron is outdated (try to save the file)",
+                view_name
+            );
+        }
+        Some((format!("view:{view_name}"), view_data.version))
+    };
+    #[cfg(not(feature = "migrations"))]
+    let version: Option<(String, i64)> = None;
+
+    let supported_drivers = supported_drivers(&item, &compilation_data, false)?;
+
+    let mut result_builder = TokensBuilder::default();
+
+    let output_impl = sql_output_base(
+        item_name,
+        &fields,
+        Vec::<JoinedField>::new(),
+        &item_name_tokens,
+        &supported_drivers,
+    )?;
+    result_builder.add(output_impl);
+
+    for driver in supported_drivers {
+        let driver_tokens = driver.to_token_stream();
+        let view_query = view_query(select.clone(), &driver_tokens, &sql_crate)?;
+
+        let create_view = quote! {
+            <#driver as #sql_crate::Driver>::create_view(
+                conn,
+                <#item_name as #sql_crate::Table<#driver>>::schema(),
+                #view_name,
+                vec![#(#column_names),*],
+                #view_query,
+            ).await?;
+        };
+
+        let setup_body = if let Some((view_id, version)) = &version {
+            quote! {
+                let current_version = #sql_crate::EasySqlTables_get_version!(#driver, *conn, #view_id);
+
+                if current_version != Some(#version) {
+                    #create_view
+                    if current_version.is_some() {
+                        #sql_crate::EasySqlTables_update_version!(#driver, *conn, #view_id, #version);
+                    } else {
+                        #sql_crate::EasySqlTables_create!(#driver, *conn, #view_id.to_string(), #version);
+                    }
+                }
+
+                Ok(())
+            }
+        } else {
+            // Without version tracking, the view is recreated on every setup
+            quote! {
+                #create_view
+                Ok(())
+            }
+        };
+
+        result_builder.add(quote! {
+            impl #sql_crate::DatabaseSetup<#driver> for #item_name {
+
+                async fn setup(
+                    conn: &mut (impl #sql_crate::EasyExecutor<#driver> + Send + Sync),
+                ) -> #macro_support::Result<()> {
+                    #setup_body
+                }
+            }
+        });
+    }
+
+    // No `WritableTable` implementation, views are read only
+    result_builder.add(quote! {
+        impl #sql_crate::markers::HasTable<#item_name> for #item_name{}
+
+        impl #sql_crate::markers::NotJoinedTable for #item_name {}
+
+        impl<EasySqlD:#sql_crate::Driver> #sql_crate::Table<EasySqlD> for #item_name {
+
+            fn table_name() -> &'static str {
+                #view_name
+            }
+
+            fn primary_keys() -> Vec<&'static str>{
+                Vec::new()
+            }
+
+            #[inline(always)]
+            fn table_joins(current_query: &mut String) {
+
+            }
+        }
+    });
+
+    Ok(result_builder.finalize().into())
+}
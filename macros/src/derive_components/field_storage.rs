@@ -0,0 +1,98 @@
+use easy_macros::{always_context, has_attributes};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// How the field value is stored inside of its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldStorage {
+    /// Field type is encoded by the driver directly
+    Native,
+    /// `#[sql(bytes)]` - bincode encoded binary blob
+    Bytes,
+    /// `#[sql(json)]` - serde_json encoded JSON value
+    Json,
+}
+
+#[always_context]
+impl FieldStorage {
+    pub fn of(field: &syn::Field) -> anyhow::Result<Self> {
+        let bytes = has_attributes!(field, #[sql(bytes)]);
+        let json = has_attributes!(field, #[sql(json)]);
+
+        match (bytes, json) {
+            (true, true) => anyhow::bail!(
+                "Field `{}` can't use both #[sql(bytes)] and #[sql(json)]",
+                field.ident.as_ref()?
+            ),
+            (true, false) => Ok(FieldStorage::Bytes),
+            (false, true) => Ok(FieldStorage::Json),
+            (false, false) => Ok(FieldStorage::Native),
+        }
+    }
+
+    /// Type the field is converted to before encoding (and decoded as), `None` for native fields
+    pub fn column_ty(self, macro_support: &TokenStream) -> Option<TokenStream> {
+        match self {
+            FieldStorage::Native => None,
+            FieldStorage::Bytes => Some(quote! { Vec<u8> }),
+            FieldStorage::Json => Some(quote! { #macro_support::JsonValue }),
+        }
+    }
+
+    /// Type used in `Encode`/`Decode`/`Type` bounds
+    pub fn bound_ty(self, macro_support: &TokenStream, field_ty: &TokenStream) -> TokenStream {
+        self.column_ty(macro_support)
+            .unwrap_or_else(|| field_ty.clone())
+    }
+
+    /// Function converting field value into the column type, `None` for native fields
+    pub fn to_column_fn(self, macro_support: &TokenStream) -> Option<TokenStream> {
+        match self {
+            FieldStorage::Native => None,
+            FieldStorage::Bytes => Some(quote! { #macro_support::to_binary }),
+            FieldStorage::Json => Some(quote! { #macro_support::to_json }),
+        }
+    }
+
+    /// Converts `value` into the column type, native values are returned unchanged
+    pub fn to_column(self, macro_support: &TokenStream, value: TokenStream) -> TokenStream {
+        match self.to_column_fn(macro_support) {
+            Some(to_column) => quote! { #to_column(#value)? },
+            None => value,
+        }
+    }
+
+    /// Function converting decoded column value back into the field type, `None` for native fields
+    pub fn from_column_fn(self, macro_support: &TokenStream) -> Option<TokenStream> {
+        match self {
+            FieldStorage::Native => None,
+            FieldStorage::Bytes => Some(quote! { #macro_support::from_binary_vec }),
+            FieldStorage::Json => Some(quote! { #macro_support::from_json }),
+        }
+    }
+
+    /// `#[sql(json)]` columns exist only with the `json` feature of the main crate (unknown to
+    /// this crate), the returned tokens fail the build with a clear message without it
+    pub fn json_feature_check<'a>(
+        fields: impl IntoIterator<Item = &'a syn::Field>,
+        sql_crate: &TokenStream,
+    ) -> anyhow::Result<TokenStream> {
+        for field in fields {
+            if FieldStorage::of(field)? == FieldStorage::Json {
+                return Ok(quote! {
+                    #sql_crate::easy_sql_require_json_feature!("#[sql(json)]");
+                });
+            }
+        }
+        Ok(quote! {})
+    }
+
+    /// Used in error contexts
+    pub fn description(self) -> &'static str {
+        match self {
+            FieldStorage::Native => "native",
+            FieldStorage::Bytes => "binary",
+            FieldStorage::Json => "JSON",
+        }
+    }
+}
@@ -1,6 +1,10 @@
+mod field_storage;
+pub use field_storage::*;
 mod supported_drivers;
 pub use supported_drivers::*;
 mod ty_to_variant;
 pub use ty_to_variant::*;
 mod validate_sql_attribute_keys;
 pub use validate_sql_attribute_keys::*;
+mod wrapper_types;
+pub use wrapper_types::*;
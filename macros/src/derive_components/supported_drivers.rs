@@ -1,6 +1,7 @@
 use anyhow::Context;
 use easy_macros::{always_context, get_attributes};
 use easy_sql_compilation_data::CompilationData;
+use proc_macro2::TokenStream;
 use syn::{ItemStruct, Path, punctuated::Punctuated};
 
 #[always_context]
@@ -9,10 +10,20 @@ pub fn supported_drivers(
     compilation_data: &CompilationData,
     optional: bool,
 ) -> anyhow::Result<Vec<Path>> {
-    if let Some(attr_data) = get_attributes!(item, #[sql(drivers = __unknown__)])
+    let drivers_attr = get_attributes!(item, #[sql(drivers = __unknown__)])
         .into_iter()
-        .next()
-    {
+        .next();
+    supported_drivers_from_attribute(drivers_attr, compilation_data, optional)
+}
+
+/// `drivers_attr` - value of the `#[sql(drivers = ...)]` attribute, if provided
+#[always_context]
+pub fn supported_drivers_from_attribute(
+    drivers_attr: Option<TokenStream>,
+    compilation_data: &CompilationData,
+    optional: bool,
+) -> anyhow::Result<Vec<Path>> {
+    if let Some(attr_data) = drivers_attr {
         struct DriversParsed {
             drivers: Punctuated<Path, syn::Token![,]>,
         }
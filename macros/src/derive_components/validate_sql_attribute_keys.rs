@@ -1,34 +1,54 @@
 use easy_macros::always_context;
 use proc_macro2::TokenStream;
-use syn::ItemStruct;
+use syn::{ItemEnum, ItemStruct};
 
 pub const DATABASE_SETUP_STRUCT_KEYS: &[&str] = &["drivers"];
 pub const DATABASE_SETUP_FIELD_KEYS: &[&str] = &[];
 pub const OUTPUT_STRUCT_KEYS: &[&str] = &["table", "drivers"];
-pub const OUTPUT_FIELD_KEYS: &[&str] = &["field", "select", "bytes"];
+pub const OUTPUT_FIELD_KEYS: &[&str] = &["field", "select", "bytes", "json", "flatten", "related"];
 pub const INSERT_STRUCT_KEYS: &[&str] = &["table", "default", "drivers"];
-pub const INSERT_FIELD_KEYS: &[&str] = &["bytes"];
+pub const INSERT_FIELD_KEYS: &[&str] =
+    &["bytes", "json", "skip", "default_if_none", "value", "field"];
 pub const UPDATE_STRUCT_KEYS: &[&str] = &["table", "drivers"];
-pub const UPDATE_FIELD_KEYS: &[&str] = &["bytes", "maybe_update", "maybe"];
+pub const UPDATE_FIELD_KEYS: &[&str] = &["bytes", "json", "maybe_update", "maybe"];
 pub const TABLE_STRUCT_KEYS: &[&str] = &[
     "table_name",
+    "schema",
     "drivers",
     "version",
     "no_version",
     "version_test",
     "unique_id",
+    "index",
+    "check",
+    "unique",
+    "soft_delete",
+    "patch",
 ];
 pub const TABLE_FIELD_KEYS: &[&str] = &[
     "primary_key",
     "auto_increment",
     "foreign_key",
     "unique",
+    "index",
+    "check",
     "bytes",
+    "json",
     "default",
     "maybe_update",
     "maybe",
     "select",
+    "created_at",
+    "updated_at",
+    "generated",
+    "lock_version",
 ];
+pub const VIEW_STRUCT_KEYS: &[&str] = &["query", "table_name", "drivers", "no_version"];
+pub const VIEW_FIELD_KEYS: &[&str] = &["bytes", "json"];
+pub const SQL_TYPE_STRUCT_KEYS: &[&str] = &["via", "from", "into"];
+pub const SQL_TYPE_FIELD_KEYS: &[&str] = &[];
+pub const SQL_ENUM_KEYS: &[&str] = &["repr", "native", "type_name", "drivers"];
+pub const SQL_ENUM_VARIANT_KEYS: &[&str] = &["rename"];
 
 fn canonical_easy_sql_derive_name(derive_name: &str) -> Option<&'static str> {
     match derive_name {
@@ -37,6 +57,8 @@ fn canonical_easy_sql_derive_name(derive_name: &str) -> Option<&'static str> {
         "Insert" | "InsertDebug" => Some("Insert"),
         "Update" | "UpdateDebug" => Some("Update"),
         "Table" | "TableDebug" => Some("Table"),
+        "View" | "ViewDebug" => Some("View"),
+        "SqlType" => Some("SqlType"),
         _ => None,
     }
 }
@@ -50,6 +72,8 @@ fn supported_keys_for_derive(
         "Insert" => Some((INSERT_STRUCT_KEYS, INSERT_FIELD_KEYS)),
         "Update" => Some((UPDATE_STRUCT_KEYS, UPDATE_FIELD_KEYS)),
         "Table" => Some((TABLE_STRUCT_KEYS, TABLE_FIELD_KEYS)),
+        "View" => Some((VIEW_STRUCT_KEYS, VIEW_FIELD_KEYS)),
+        "SqlType" => Some((SQL_TYPE_STRUCT_KEYS, SQL_TYPE_FIELD_KEYS)),
         _ => None,
     }
 }
@@ -302,3 +326,28 @@ pub fn validate_sql_attribute_keys(
 
     combined_error.map(|error| error.to_compile_error())
 }
+
+#[always_context]
+pub fn validate_sql_enum_attribute_keys(item: &ItemEnum, derive_name: &str) -> Option<TokenStream> {
+    let mut combined_error = None;
+
+    scan_sql_attrs_for_unknown_keys(
+        &item.attrs,
+        derive_name,
+        "enum",
+        SQL_ENUM_KEYS,
+        &mut combined_error,
+    );
+
+    for variant in item.variants.iter() {
+        scan_sql_attrs_for_unknown_keys(
+            &variant.attrs,
+            derive_name,
+            "variant",
+            SQL_ENUM_VARIANT_KEYS,
+            &mut combined_error,
+        );
+    }
+
+    combined_error.map(|error| error.to_compile_error())
+}
@@ -0,0 +1,34 @@
+/// `T` of `Option<T>`
+pub fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    single_type_argument(ty, "Option")
+}
+
+/// `T` of `Vec<T>`
+pub fn vec_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    single_type_argument(ty, "Vec")
+}
+
+/// Built-in integer type (`i32`, `u64`, ...)
+pub fn is_integer(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(type_path) if type_path.qself.is_none()
+    && type_path.path.get_ident().is_some_and(|ident| {
+        matches!(
+            ident.to_string().as_str(),
+            "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize" | "usize"
+        )
+    }))
+}
+
+fn single_type_argument<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    if let syn::Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && let Some(last) = type_path.path.segments.last()
+        && last.ident == wrapper
+        && let syn::PathArguments::AngleBracketed(args) = &last.arguments
+        && args.args.len() == 1
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Some(inner);
+    }
+    None
+}
@@ -10,6 +10,8 @@ use proc_macro::TokenStream;
 
 /// Prefix for aliases generated by #[sql(select = ...)] to avoid conflicts with table columns
 const CUSTOM_SELECT_ALIAS_PREFIX: &str = "__easy_sql_custom_select_";
+/// Prefix for aliases of columns selected by #[sql(flatten)] fields
+const FLATTEN_ALIAS_PREFIX: &str = "__easy_sql_flatten_";
 
 fn sql_crate() -> proc_macro2::TokenStream {
     if let Some(found) = find_crate_list(&[("easy-sql", quote! {})]) {
@@ -146,6 +148,37 @@ pub fn table_debug(item: TokenStream) -> anyhow::Result<TokenStream> {
     panic!("{}", output);
 }
 
+#[always_context]
+#[proc_macro_derive(View, attributes(sql))]
+#[anyhow_result]
+pub fn view(item: TokenStream) -> anyhow::Result<TokenStream> {
+    derive::view(item)
+}
+
+#[always_context]
+#[proc_macro_derive(ViewDebug, attributes(sql))]
+#[anyhow_result]
+#[no_context]
+pub fn view_debug(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let output = derive::view(item)?;
+
+    panic!("{}", output);
+}
+
+#[always_context]
+#[proc_macro_derive(SqlEnum, attributes(sql))]
+#[anyhow_result]
+pub fn sql_enum(item: TokenStream) -> anyhow::Result<TokenStream> {
+    derive::sql_enum(item)
+}
+
+#[always_context]
+#[proc_macro_derive(SqlType, attributes(sql))]
+#[anyhow_result]
+pub fn sql_type(item: TokenStream) -> anyhow::Result<TokenStream> {
+    derive::sql_type(item)
+}
+
 #[proc_macro]
 #[always_context]
 #[anyhow_result]
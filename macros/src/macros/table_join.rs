@@ -267,28 +267,28 @@ pub fn table_join(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::T
         .map(|join| match join {
             Join::Inner { table, on } => {
                 data.format_params
-                    .push(driver.table_name(&sql_crate, table));
+                    .push(driver.qualified_table_name(&sql_crate, table));
                 let on = on.clone().into_query_string(&mut data, false, false);
 
                 format!(" INNER JOIN {{}} ON {}", on)
             }
             Join::Left { table, on } => {
                 data.format_params
-                    .push(driver.table_name(&sql_crate, table));
+                    .push(driver.qualified_table_name(&sql_crate, table));
                 let on = on.clone().into_query_string(&mut data, false, false);
 
                 format!(" LEFT JOIN {{}} ON {}", on)
             }
             Join::Right { table, on } => {
                 data.format_params
-                    .push(driver.table_name(&sql_crate, table));
+                    .push(driver.qualified_table_name(&sql_crate, table));
                 let on = on.clone().into_query_string(&mut data, false, false);
 
                 format!(" RIGHT JOIN {{}} ON {}", on)
             }
             Join::Cross { table } => {
                 data.format_params
-                    .push(driver.table_name(&sql_crate, table));
+                    .push(driver.qualified_table_name(&sql_crate, table));
                 " CROSS JOIN {}".to_string()
             }
         })
@@ -302,6 +302,10 @@ pub fn table_join(item: proc_macro::TokenStream) -> anyhow::Result<proc_macro::T
                 <#main_table_struct as #sql_crate::Table<D>>::table_name()
             }
 
+            fn schema() -> Option<&'static str> {
+                <#main_table_struct as #sql_crate::Table<D>>::schema()
+            }
+
             fn primary_keys() -> Vec<&'static str>{
                 vec![]
             }
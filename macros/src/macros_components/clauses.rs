@@ -1,14 +1,31 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::macros_components::{Expr, Limit, OrderBy, column::Column};
+use crate::macros_components::{Expr, Limit, NotChain, OrderBy, column::Column};
 
 use super::{CollectedData, SetClause};
 
-fn sql_expr_clause(expr: Expr, clause_name: &'static str, data: &mut CollectedData) {
+/// WHERE / HAVING condition, optional variables (`{?var}`) are allowed here
+fn predicate_query_string(expr: Expr, data: &mut CollectedData) -> String {
+    // Optional predicates are left out of operator chains, lone one becomes a chain of itself
+    let expr = match expr {
+        Expr::OperatorChain(..) => expr,
+        expr if expr.optional_condition().is_some() => {
+            Expr::OperatorChain(NotChain { not_count: 0 }, Box::new(expr), Vec::new())
+        }
+        expr => expr,
+    };
+
+    data.optional_predicates = true;
     let sql_template = expr.into_query_string(
         data, false, false, // for_custom_select
     );
+    data.optional_predicates = false;
+    sql_template
+}
+
+fn sql_expr_clause(expr: Expr, clause_name: &'static str, data: &mut CollectedData) {
+    let sql_template = predicate_query_string(expr, data);
     data.format_str
         .push_str(&format!(" {clause_name} {sql_template}"));
 }
@@ -17,6 +34,32 @@ pub fn where_clause(where_expr: Expr, data: &mut CollectedData) {
     sql_expr_clause(where_expr, "WHERE", data)
 }
 
+/// WHERE clause skipping soft deleted rows (unless `include_deleted`), needs variables from
+/// `soft_delete_filter`
+///
+/// Without `where_expr` the clause is only added for tables with a soft delete column
+pub fn soft_delete_where_clause(
+    where_expr: Option<Expr>,
+    include_deleted: bool,
+    data: &mut CollectedData,
+) {
+    if include_deleted {
+        if let Some(where_expr) = where_expr {
+            where_clause(where_expr, data)
+        }
+        return;
+    }
+    match where_expr {
+        Some(where_expr) => {
+            let sql_template = predicate_query_string(where_expr, data);
+            data.format_str.push_str(&format!(
+                " WHERE {{_easy_sql_not_deleted_and}}{sql_template}{{_easy_sql_not_deleted_end}}"
+            ));
+        }
+        None => data.format_str.push_str("{_easy_sql_not_deleted_where}"),
+    }
+}
+
 pub fn having_clause(having_expr: Expr, data: &mut CollectedData) {
     sql_expr_clause(having_expr, "HAVING", data)
 }
@@ -64,7 +107,9 @@ pub fn set_clause(clause: SetClause, data: &mut CollectedData) -> TokenStream {
             let result = quote! {
                 // Use Update trait's updates method to add SET arguments
                 let mut current_arg_n = #current_param_n;
-                _easy_sql_args = #query_update_data.context("Update::updates failed")?;
+                let _easy_sql_lock_version_condition;
+                (_easy_sql_args, _easy_sql_lock_version_condition) =
+                    #query_update_data.context("Update::updates failed")?;
             };
             *data.before_param_n = quote! { current_arg_n + #before_param_n};
             *data.current_param_n = 0;
@@ -88,8 +133,9 @@ pub fn set_clause(clause: SetClause, data: &mut CollectedData) -> TokenStream {
             // Generate compile-time SQL template for SET clause
             let set_sql_template = set_sql_parts.join(", ");
 
+            // `#[sql(updated_at)]` columns, see `generate_update`
             data.format_str
-                .push_str(&format!(" SET {}", set_sql_template));
+                .push_str(&format!(" SET {set_sql_template}{{_easy_sql_updated_at}}"));
 
             quote! {}
         }
@@ -15,6 +15,9 @@ pub struct CollectedData<'a> {
     pub output_ty: Option<&'a TokenStream>,
     pub main_table_type: Option<&'a TokenStream>,
     pub types_driver_support_needed: &'a mut Vec<proc_macro2::TokenStream>,
+    /// Set while building WHERE and HAVING clauses, the only places optional variables
+    /// (`{?var}`) are allowed in
+    pub optional_predicates: bool,
 }
 
 impl<'a> CollectedData<'a> {
@@ -46,6 +49,7 @@ impl<'a> CollectedData<'a> {
             output_ty,
             main_table_type,
             types_driver_support_needed,
+            optional_predicates: false,
         }
     }
 
@@ -70,6 +74,7 @@ impl<'a> CollectedData<'a> {
             output_ty: self.output_ty,
             main_table_type: self.main_table_type,
             types_driver_support_needed: self.types_driver_support_needed,
+            optional_predicates: self.optional_predicates,
         }
     }
 
@@ -93,6 +98,7 @@ impl<'a> CollectedData<'a> {
             output_ty: self.output_ty,
             main_table_type: self.main_table_type,
             types_driver_support_needed: self.types_driver_support_needed,
+            optional_predicates: self.optional_predicates,
         }
     }
 }
@@ -26,6 +26,149 @@ fn add_operator_support_check<T>(
     }
 }
 
+fn add_operator_check(data: &mut CollectedData, op: &Operator) {
+    let op_trait_name = match op {
+        Operator::Mod | Operator::Concat => format!("{:?}Operator", op),
+        op => format!("{:?}", op),
+    };
+    add_operator_support_check(data, op_trait_name);
+}
+
+/// `a && b && ...` of the conditions, `None` if none of them are conditional
+pub(super) fn all_of(
+    conditions: impl IntoIterator<Item = Option<proc_macro2::TokenStream>>,
+) -> Option<proc_macro2::TokenStream> {
+    let conditions = conditions.into_iter().flatten().collect::<Vec<_>>();
+    if conditions.is_empty() {
+        None
+    } else {
+        Some(quote! {(#(#conditions)&&*)})
+    }
+}
+
+/// Operator chain containing optional variables (`{?var}`), built at runtime
+///
+/// Chain is split into OR groups of AND operands, every operand with a `None` optional variable is
+/// left out of its group, groups without operands are left out together with their OR (`1 = 1` is
+/// used when nothing is left). Operands are joined again at runtime keeping AND over OR
+/// precedence. Binds of left out operands are skipped and parameters after them are shifted back
+/// at runtime, the same way as after `IN {vec}` lists.
+fn optional_operator_chain(
+    data: &mut CollectedData,
+    operands: Vec<(NotChain, Option<Operator>, Expr)>,
+    for_custom_select: bool,
+) -> String {
+    let sql_crate = data.sql_crate;
+
+    // (OR group index, operands between AND / OR operators)
+    let mut predicates: Vec<(usize, Vec<(NotChain, Option<Operator>, Expr)>)> = Vec::new();
+    let mut or_group = 0usize;
+    for (not_chain, op, expr) in operands {
+        let starts_predicate = op.as_ref().is_none_or(Operator::is_logical);
+        if let Some(op) = op.as_ref().filter(|op| op.is_logical()) {
+            add_operator_check(data, op);
+            if matches!(op, Operator::Or) {
+                or_group += 1;
+            }
+        }
+        match predicates.last_mut() {
+            Some((_, predicate)) if !starts_predicate => predicate.push((not_chain, op, expr)),
+            _ => predicates.push((or_group, vec![(not_chain, None, expr)])),
+        }
+    }
+
+    let mut pushes = Vec::new();
+    for (or_group, predicate) in predicates {
+        let condition = all_of(
+            predicate
+                .iter()
+                .map(|(_, _, expr)| expr.optional_condition()),
+        );
+
+        let binds_start = data.binds.len();
+        let param_start = *data.current_param_n;
+        let before_param_n_start = data.before_param_n.clone();
+
+        let mut predicate_format_params = Vec::new();
+        let mut predicate_sql = String::new();
+        {
+            let mut data = data.with_format_params(&mut predicate_format_params);
+            for (not_chain, op, expr) in predicate {
+                if let Some(op) = op {
+                    add_operator_check(&mut data, &op);
+                    predicate_sql.push_str(op.into_query_string());
+                }
+                predicate_sql.push_str(&not_chain.into_query_string());
+                predicate_sql.push_str(&expr.into_query_string(
+                    &mut data,
+                    false,
+                    for_custom_select,
+                ));
+            }
+        }
+
+        let push = quote! {
+            __easy_sql_predicates.push((
+                #or_group,
+                format!(#predicate_sql, #(#predicate_format_params),*),
+            ));
+        };
+
+        let Some(condition) = condition else {
+            pushes.push(push);
+            continue;
+        };
+
+        let predicate_binds = data.binds.drain(binds_start..).collect::<Vec<_>>();
+        data.binds.push(quote! {
+            #[allow(unused_parens)]
+            if #condition {
+                #(#predicate_binds)*
+            }
+        });
+
+        // Parameters used by the predicate, 0 when it's left out
+        let param_n_name =
+            format_ident!("__easy_sql_before_param_n_{}", data.before_format.len());
+        data.before_format.push(quote! {
+            #[allow(unused_variables)]
+            let #param_n_name: usize;
+        });
+        let static_param_n = *data.current_param_n - param_start;
+        let before_param_n_end = &*data.before_param_n;
+        let runtime_param_n =
+            if before_param_n_end.to_string() == before_param_n_start.to_string() {
+                quote! {}
+            } else {
+                quote! {+ ((#before_param_n_end 0) - (#before_param_n_start 0))}
+            };
+        pushes.push(quote! {
+            if #condition {
+                #push
+                #param_n_name = #static_param_n #runtime_param_n;
+            } else {
+                #param_n_name = 0;
+            }
+        });
+
+        *data.current_param_n = param_start;
+        *data.before_param_n = quote! {#param_n_name + #before_param_n_start};
+    }
+
+    data.format_params.push(quote! {
+        {
+            #[allow(unused_parens, unused_assignments, clippy::needless_borrow)]
+            {
+                let mut __easy_sql_predicates = Vec::new();
+                #(#pushes)*
+                #sql_crate::macro_support::join_optional_predicates(__easy_sql_predicates)
+            }
+        }
+    });
+
+    "{}".to_string()
+}
+
 #[derive(Debug, Clone)]
 pub enum Expr {
     Value(Box<Value>),
@@ -85,48 +228,26 @@ impl Expr {
                 format!("({})", inner_sql)
             }
             Expr::OperatorChain(not_chain, first, rest) => {
-                let mut result = format!(
-                    "{}{}",
-                    not_chain.into_query_string(),
-                    first.into_query_string(
-                        data,
-                        false,
-                        for_custom_select,
-                    )
+                let mut operands = vec![(not_chain, None, *first)];
+                operands.extend(
+                    rest.into_iter()
+                        .map(|(not_chain, op, expr)| (not_chain, Some(op), expr)),
                 );
-                for (not_chain, op, expr) in rest.into_iter() {
-                    let op_trait_name = match &op {
-                        Operator::Mod|Operator::Concat => format!("{:?}Operator",op),
-                        op=>format!("{:?}",op)
-                    };
-                    add_operator_support_check(
-                        data,
-                        op_trait_name,
-                    );
-                    let op_str = match op {
-                        Operator::And => " AND ",
-                        Operator::Or => " OR ",
-                        Operator::Add => " + ",
-                        Operator::Sub => " - ",
-                        Operator::Mul => " * ",
-                        Operator::Div => " / ",
-                        Operator::Mod => " % ",
-                        Operator::Concat => " || ",
-                        Operator::JsonExtract => " -> ",
-                        Operator::JsonExtractText => " ->> ",
-                        Operator::BitAnd => " & ",
-                        Operator::BitOr => " | ",
-                        Operator::BitShiftLeft => " << ",
-                        Operator::BitShiftRight => " >> ",
-                        Operator::Equal => " = ",
-                        Operator::NotEqual => " != ",
-                        Operator::GreaterThan => " > ",
-                        Operator::GreaterThanOrEqual => " >= ",
-                        Operator::LessThan => " < ",
-                        Operator::LessThanOrEqual => " <= ",
-                        Operator::Like => " LIKE ",
-                    };
-                    result.push_str(op_str);
+
+                if data.optional_predicates
+                    && operands
+                        .iter()
+                        .any(|(_, _, expr)| expr.optional_condition().is_some())
+                {
+                    return optional_operator_chain(data, operands, for_custom_select);
+                }
+
+                let mut result = String::new();
+                for (not_chain, op, expr) in operands.into_iter() {
+                    if let Some(op) = op {
+                        add_operator_check(data, &op);
+                        result.push_str(op.into_query_string());
+                    }
                     result.push_str(&not_chain.into_query_string());
                     result.push_str(&expr.into_query_string(
                         data,
@@ -186,10 +307,9 @@ impl Expr {
                             }
                         });
 
-                        let format_param_n = data.format_params.len();
-
+                        // Unique per query, format params of optional predicates are collected separately
                         let before_param_n_name =
-                            format_ident!("__easy_sql_before_param_n_{}", format_param_n);
+                            format_ident!("__easy_sql_before_param_n_{}", data.before_format.len());
                         let before_param_n = &mut data.before_param_n;
 
                         data.before_format.push(quote! {
@@ -249,6 +369,45 @@ impl Expr {
         }
     }
 
+    /// Runtime condition under which this expression stays in the query, `None` when it doesn't
+    /// contain optional variables (`{?var}`)
+    ///
+    /// Operator chains stay while any of their AND / OR operands does, everything else needs all
+    /// of its optional variables to be `Some`
+    pub fn optional_condition(&self) -> Option<proc_macro2::TokenStream> {
+        match self {
+            Expr::Value(v) | Expr::IsNull(v) | Expr::IsNotNull(v) => v.optional_condition(),
+            Expr::Parenthesized(inner) => inner.optional_condition(),
+            Expr::OperatorChain(_, first, chain) => {
+                let mut predicates = vec![vec![first.optional_condition()]];
+                for (_, op, expr) in chain.iter() {
+                    let condition = expr.optional_condition();
+                    match predicates.last_mut() {
+                        Some(predicate) if !op.is_logical() => predicate.push(condition),
+                        _ => predicates.push(vec![condition]),
+                    }
+                }
+                let conditions = predicates
+                    .into_iter()
+                    .map(all_of)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(quote! {(#(#conditions)||*)})
+            }
+            Expr::In(v, value_in) => {
+                let mut conditions = vec![v.optional_condition()];
+                if let ValueIn::Multiple(exprs) = &**value_in {
+                    conditions.extend(exprs.iter().map(Expr::optional_condition));
+                }
+                all_of(conditions)
+            }
+            Expr::Between(v, low, high) => all_of([
+                v.optional_condition(),
+                low.optional_condition(),
+                high.optional_condition(),
+            ]),
+        }
+    }
+
     /// Check if this expression contains any outside variables ({arg0}, {arg1}, etc.)
     /// Returns a set of argument indices found in the expression.
     pub fn collect_indices_impl(&self, indices: &mut std::collections::BTreeSet<usize>) {
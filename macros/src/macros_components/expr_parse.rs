@@ -188,6 +188,11 @@ impl Parse for Value {
             } else if lookahead.peek(syn::token::Brace) {
                 let inside_braces;
                 syn::braced!(inside_braces in input);
+                if inside_braces.peek(syn::Token![?]) {
+                    inside_braces.parse::<syn::Token![?]>()?;
+                    let expr: syn::Expr = inside_braces.parse()?;
+                    return Ok(Value::OptionalVariable(expr));
+                }
                 let expr: syn::Expr = inside_braces.parse()?;
                 Ok(Value::OutsideVariable(expr))
             } else if lookahead.peek(syn::Ident) {
@@ -226,6 +231,11 @@ impl Parse for ValueIn {
         } else if lookahead.peek(syn::token::Brace) {
             let inside_braces;
             syn::braced!(inside_braces in input);
+            if inside_braces.peek(syn::Token![?]) {
+                return Err(inside_braces.error(
+                    "Optional variables ({?var}) can't be used as IN lists, use {var} instead",
+                ));
+            }
             let expr: syn::Expr = inside_braces.parse()?;
             Ok(ValueIn::SingleVar(expr))
         } else {
@@ -295,3 +295,6 @@ sql_keyword!(update);
 sql_keyword!(delete);
 sql_keyword!(returning);
 sql_keyword!(exists);
+sql_keyword!(with);
+sql_keyword!(deleted);
+sql_keyword!(hard);
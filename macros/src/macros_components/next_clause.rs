@@ -5,6 +5,7 @@ use super::keyword;
 /// Checks if the next token starts a new clause in SQL.
 pub fn next_clause_token(lookahead: &Lookahead1) -> bool {
     lookahead.peek(keyword::distinct)
+        || lookahead.peek(keyword::from)
         || lookahead.peek(keyword::where_)
         || lookahead.peek(keyword::having)
         || lookahead.peek(keyword::group)
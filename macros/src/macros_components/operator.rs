@@ -155,6 +155,39 @@ impl Parse for NotChain {
     }
 }
 
+impl Operator {
+    pub fn into_query_string(&self) -> &'static str {
+        match self {
+            Operator::And => " AND ",
+            Operator::Or => " OR ",
+            Operator::Add => " + ",
+            Operator::Sub => " - ",
+            Operator::Mul => " * ",
+            Operator::Div => " / ",
+            Operator::Mod => " % ",
+            Operator::Concat => " || ",
+            Operator::JsonExtract => " -> ",
+            Operator::JsonExtractText => " ->> ",
+            Operator::BitAnd => " & ",
+            Operator::BitOr => " | ",
+            Operator::BitShiftLeft => " << ",
+            Operator::BitShiftRight => " >> ",
+            Operator::Equal => " = ",
+            Operator::NotEqual => " != ",
+            Operator::GreaterThan => " > ",
+            Operator::GreaterThanOrEqual => " >= ",
+            Operator::LessThan => " < ",
+            Operator::LessThanOrEqual => " <= ",
+            Operator::Like => " LIKE ",
+        }
+    }
+
+    /// AND / OR, operands of these are removed by optional variables (`{?var}`)
+    pub fn is_logical(&self) -> bool {
+        matches!(self, Operator::And | Operator::Or)
+    }
+}
+
 impl NotChain {
     pub fn into_query_string(self) -> String {
         let mut current_query = String::new();
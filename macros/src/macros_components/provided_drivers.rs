@@ -125,6 +125,7 @@ impl ProvidedDrivers {
         sql_crate: &TokenStream,
         table_type: &syn::Type,
         to_insert: syn::Expr,
+        reserved_parameters: &TokenStream,
     ) -> TokenStream {
         match self {
             ProvidedDrivers::Single(driver) | ProvidedDrivers::SingleWithChecks { driver, .. } => {
@@ -132,7 +133,7 @@ impl ProvidedDrivers {
                     #sql_crate::macro_support::query_insert_data_selected_driver::<#table_type, #driver, _>(
                         #[allow(unused_braces)]
                         {#to_insert},
-                        _easy_sql_args,
+                        #reserved_parameters,
                     )
                 }
             }
@@ -141,7 +142,7 @@ impl ProvidedDrivers {
                     #sql_crate::macro_support::query_insert_data::<#table_type, _, _>(
                         #[allow(unused_braces)]
                         {#to_insert},
-                        _easy_sql_args,
+                        #reserved_parameters,
                         #conn,
                     )
                 }
@@ -199,6 +200,59 @@ impl ProvidedDrivers {
         }
     }
 
+    pub fn soft_delete_column(
+        &self,
+        sql_crate: &TokenStream,
+        table_type: &syn::Type,
+    ) -> TokenStream {
+        match self {
+            ProvidedDrivers::Single(driver) | ProvidedDrivers::SingleWithChecks { driver, .. } => {
+                quote_spanned! {table_type.span()=>
+                    <#table_type as #sql_crate::Table<#driver>>::soft_delete_column()
+                }
+            }
+            ProvidedDrivers::MultipleWithConn { drivers: _, conn } => {
+                quote_spanned! {table_type.span()=>
+                    #sql_crate::macro_support::driver_soft_delete_column::<#table_type, _>(#conn)
+                }
+            }
+        }
+    }
+
+    pub fn updated_at_columns(
+        &self,
+        sql_crate: &TokenStream,
+        table_type: &syn::Type,
+    ) -> TokenStream {
+        match self {
+            ProvidedDrivers::Single(driver) | ProvidedDrivers::SingleWithChecks { driver, .. } => {
+                quote_spanned! {table_type.span()=>
+                    <#table_type as #sql_crate::Table<#driver>>::updated_at_columns()
+                }
+            }
+            ProvidedDrivers::MultipleWithConn { drivers: _, conn } => {
+                quote_spanned! {table_type.span()=>
+                    #sql_crate::macro_support::driver_updated_at_columns::<#table_type, _>(#conn)
+                }
+            }
+        }
+    }
+
+    pub fn current_timestamp(&self, sql_crate: &TokenStream) -> TokenStream {
+        match self {
+            ProvidedDrivers::Single(driver) | ProvidedDrivers::SingleWithChecks { driver, .. } => {
+                quote_spanned! {driver.span()=>
+                    <#driver as #sql_crate::Driver>::current_timestamp()
+                }
+            }
+            ProvidedDrivers::MultipleWithConn { drivers: _, conn } => {
+                quote_spanned! {conn.span()=>
+                    #sql_crate::macro_support::driver_current_timestamp(#conn)
+                }
+            }
+        }
+    }
+
     pub fn parameter_placeholder_base(&self, sql_crate: &TokenStream) -> TokenStream {
         match self {
             ProvidedDrivers::Single(_) | ProvidedDrivers::SingleWithChecks { .. } => {
@@ -283,6 +337,26 @@ impl ProvidedDrivers {
             }
         }
     }
+
+    /// Schema qualified table name (`String`), used when referencing the table itself
+    pub fn qualified_table_name<T: ToTokens + Spanned>(
+        &self,
+        sql_crate: &TokenStream,
+        table_type: &T,
+    ) -> TokenStream {
+        match self {
+            ProvidedDrivers::Single(driver) | ProvidedDrivers::SingleWithChecks { driver, .. } => {
+                quote_spanned! {table_type.span()=>
+                    #sql_crate::macro_support::qualified_table_name::<#table_type, #driver>()
+                }
+            }
+            ProvidedDrivers::MultipleWithConn { drivers: _, conn } => {
+                quote_spanned! {table_type.span()=>
+                    #sql_crate::macro_support::driver_qualified_table_name::<#table_type,_>(#conn)
+                }
+            }
+        }
+    }
 }
 
 pub enum ProvidedDriversIterator<'a> {
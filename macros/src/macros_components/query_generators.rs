@@ -3,11 +3,28 @@ use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, format_ident, quote};
 
 use super::{
-    CollectedData, DeleteQuery, ExistsQuery, InsertQuery, ProvidedDrivers, ReturningData,
-    SelectQuery, UpdateQuery, group_by_clause, having_clause, limit_clause, order_by_clause,
-    set_clause, where_clause,
+    CollectedData, DeleteQuery, ExistsQuery, Expr, InsertQuery, ProvidedDrivers, ReturningData,
+    ScalarOutput, ScalarWrapper, SelectQuery, SetClause, UpdateQuery, group_by_clause,
+    having_clause, limit_clause, order_by_clause, set_clause, soft_delete_where_clause,
 };
 
+/// Variables used by `soft_delete_where_clause`, nothing when soft deleted rows are included
+fn soft_delete_filter(
+    driver: &ProvidedDrivers,
+    sql_crate: &TokenStream,
+    table_type: &syn::Type,
+    include_deleted: bool,
+) -> TokenStream {
+    if include_deleted {
+        return quote! {};
+    }
+    let soft_delete_column = driver.soft_delete_column(sql_crate, table_type);
+    quote! {
+        let (_easy_sql_not_deleted_and, _easy_sql_not_deleted_end, _easy_sql_not_deleted_where) =
+            #sql_crate::macro_support::soft_delete_filter(#soft_delete_column, _easy_sql_d);
+    }
+}
+
 struct ReturningArgData {
     arg_defs: Vec<TokenStream>,
     arg_tokens: Vec<TokenStream>,
@@ -64,6 +81,107 @@ impl ReturningData {
     }
 }
 
+struct ScalarOutputData {
+    /// Local `Output` type decoding one row by column position
+    output_impl: TokenStream,
+    /// User output type with the row type replaced by the local `Output` type
+    output_type: syn::Type,
+    /// Adds selected expressions to `query`
+    select: TokenStream,
+    /// Converts `output` (of `output_type`) into the user output type
+    unwrap: TokenStream,
+}
+
+impl ScalarOutput {
+    fn build(self, data: &mut CollectedData) -> ScalarOutputData {
+        let sql_crate = data.sql_crate;
+        let macro_support = quote! {#sql_crate::macro_support};
+        let table_type = data.main_table_type;
+
+        let mut select = Vec::new();
+        for (idx, expr) in self.exprs.into_iter().enumerate() {
+            let mut expr_format_params = Vec::new();
+            let mut data = data.with_format_params(&mut expr_format_params);
+            let expr_sql_template = expr.into_query_string(&mut data, false, false);
+            let separator = if idx > 0 { ", " } else { "" };
+            select.push(quote! {
+                query.push_str(#separator);
+                query.push_str(&format!(#expr_sql_template, #(#expr_format_params),*));
+            });
+        }
+
+        let row_type = self.row_type;
+        let column_types = match &row_type {
+            syn::Type::Tuple(tuple) => tuple.elems.iter().cloned().collect::<Vec<_>>(),
+            _ => vec![row_type.clone()],
+        };
+        let decoded = column_types
+            .iter()
+            .enumerate()
+            .map(|(idx, _)| {
+                let context_str = format!("Decoding column {idx} of the scalar output");
+                quote! {
+                    <#macro_support::DriverRow<D> as #macro_support::SqlxRow>::try_get(&data, #idx)
+                        .with_context(#macro_support::context!(#context_str))?
+                }
+            })
+            .collect::<Vec<_>>();
+        let row_value = if matches!(row_type, syn::Type::Tuple(_)) {
+            quote! { (#(#decoded,)*) }
+        } else {
+            quote! { #(#decoded)* }
+        };
+
+        let output_impl = quote! {
+            struct __EasySqlScalarOutput(#row_type);
+
+            impl<D: #sql_crate::Driver> #sql_crate::Output<#table_type, D> for __EasySqlScalarOutput
+            where
+                #macro_support::DriverRow<D>: #macro_support::ToConvert<D>,
+                usize: #macro_support::ColumnIndex<#macro_support::DriverRow<D>>,
+                #(
+                    for<'__easy_sql_x> #column_types: #macro_support::Decode<'__easy_sql_x, #macro_support::InternalDriver<D>>,
+                    #column_types: #macro_support::Type<#macro_support::InternalDriver<D>>,
+                )*
+            {
+                type DataToConvert = #macro_support::DriverRow<D>;
+                type UsedForChecks = ();
+
+                // Selected expressions are added by the query itself
+                fn select(_current_query: &mut String) {}
+
+                fn convert(data: #macro_support::DriverRow<D>) -> #macro_support::Result<Self> {
+                    use #macro_support::Context;
+
+                    Ok(Self(#row_value))
+                }
+            }
+        };
+
+        let (output_type, unwrap): (syn::Type, _) = match self.wrapper {
+            ScalarWrapper::Single => (
+                syn::parse_quote! { __EasySqlScalarOutput },
+                quote! { output.0 },
+            ),
+            ScalarWrapper::Vec => (
+                syn::parse_quote! { Vec<__EasySqlScalarOutput> },
+                quote! { output.into_iter().map(|row| row.0).collect::<Vec<_>>() },
+            ),
+            ScalarWrapper::Option => (
+                syn::parse_quote! { Option<__EasySqlScalarOutput> },
+                quote! { output.map(|row| row.0) },
+            ),
+        };
+
+        ScalarOutputData {
+            output_impl,
+            output_type,
+            select: quote! { #(#select)* },
+            unwrap,
+        }
+    }
+}
+
 #[always_context]
 pub fn generate_select(
     select: SelectQuery,
@@ -109,21 +227,36 @@ pub fn generate_select(
         &mut param_counter,
         &mut before_param_n,
         &mut before_format,
-        Some(&output_type_ts),
+        // Scalar outputs have no named columns, columns are validated against the table
+        select.scalar.is_none().then_some(&output_type_ts),
         Some(&table_type_tokens),
         &mut types_driver_support_needed,
     );
 
-    let output_arg_data = output.build_arg_data(&mut data);
+    let scalar = select.scalar.map(|scalar| scalar.build(&mut data));
+    let output_arg_data = if scalar.is_some() {
+        ReturningArgData {
+            arg_defs: Vec::new(),
+            arg_tokens: Vec::new(),
+        }
+    } else {
+        output.build_arg_data(&mut data)
+    };
     let output_arg_defs = output_arg_data.arg_defs;
     let output_arg_tokens = output_arg_data.arg_tokens;
     let output_args = output.output_args;
-    let output_type = output.output_type;
+    let user_output_type = output.output_type;
+    let output_type = match &scalar {
+        Some(scalar) => scalar.output_type.clone(),
+        None => user_output_type.clone(),
+    };
+    let (scalar_output_impl, scalar_select, scalar_unwrap) = match scalar {
+        Some(scalar) => (scalar.output_impl, scalar.select, Some(scalar.unwrap)),
+        None => (quote! {}, quote! {}, None),
+    };
 
     // Generate runtime code for WHERE clause
-    if let Some(where_expr) = select.where_clause {
-        where_clause(where_expr, &mut data)
-    }
+    soft_delete_where_clause(select.where_clause, select.include_deleted, &mut data);
 
     // Build GROUP BY clause code if present
     if let Some(group_by_list) = select.group_by {
@@ -171,6 +304,19 @@ pub fn generate_select(
         "sql query! macro input: {}"
     };
 
+    // Scalar outputs are converted from the local `Output` type into the user type
+    let scalar_map = match &scalar_unwrap {
+        Some(unwrap) => quote! { .map(|output| #unwrap) },
+        None => quote! {},
+    };
+    let converted_output = match &scalar_unwrap {
+        Some(unwrap) => quote! {
+            let output = converted;
+            Ok(#unwrap)
+        },
+        None => quote! { Ok(converted) },
+    };
+
     let final_to_execute = if let Some(connection) = connection {
         quote! {
             let built_query = builder.build();
@@ -179,23 +325,18 @@ pub fn generate_select(
             #macro_support::query_execute::<#table_type, #output_type, _>(#connection, built_query)
                 .await
                 .with_context(|| format!(#debug_format_str, #macro_input))
+                #scalar_map
         }
     } else {
         let fetch_internals = |executor: TokenStream| {
             quote! {
                     use #sql_crate::EasyExecutor as _;
-                self.builder.build().fetch(conn.#executor()).map(|r| {
-                                match r {
-                                    Ok(r) => {
-                                        let converted =
-                                            <#output_type as #sql_crate::Output<#table_type, #lazy_mode_driver>>::convert(r)
-                                                .context("Output::convert failed")?;
-
-                                        Ok(converted)
-                                    }
-                                    Err(err) => Err(#macro_support::Error::from(err)),
-                                }
-                                .with_context(|| format!(#debug_format_str, #macro_input))
+                self.builder.build().fetch(conn.#executor()).map(|r| -> #macro_support::Result<_> {
+                                let converted =
+                                    #macro_support::query_lazy_row::<#table_type, #output_type, #lazy_mode_driver>(r)
+                                        .with_context(|| format!(#debug_format_str, #macro_input))?;
+
+                                #converted_output
                             })
             }
         };
@@ -212,7 +353,7 @@ pub fn generate_select(
                     &'_easy_sql_e mut self,
                     mut conn: E,
                 ) -> impl #macro_support::Stream<
-                    Item = #macro_support::Result<#output_type>,
+                    Item = #macro_support::Result<#user_output_type>,
                 > + '_easy_sql_e
                 where
                     E: #sql_crate::EasyExecutorInto<#lazy_mode_driver> + '_easy_sql_e,
@@ -233,9 +374,11 @@ pub fn generate_select(
     } else {
         driver.query_add_selected(sql_crate, &output_type, &table_type)
     };
-    let main_table_name = driver.table_name(sql_crate, &table_type);
+    let main_table_name = driver.qualified_table_name(sql_crate, &table_type);
     let table_joins = driver.table_joins(sql_crate, &table_type);
     let parameter_placeholder_base = driver.parameter_placeholder_base(sql_crate);
+    let soft_delete_filter =
+        soft_delete_filter(&driver, sql_crate, &table_type, select.include_deleted);
 
     let async_block = if lazy_mode_driver.is_some() {
         quote! {}
@@ -248,6 +391,8 @@ pub fn generate_select(
             #async_block {
                 use {#macro_support::ToConvert,#macro_support::{Context,Arguments}};
 
+                #scalar_output_impl
+
                 // Safety checks closure
                 let _ = |___t___: #table_type| {
                     #(#checks)*
@@ -255,6 +400,7 @@ pub fn generate_select(
 
                 let mut _easy_sql_args = #driver_arguments;
                 let _easy_sql_d = #identifier_delimiter;
+                #soft_delete_filter
                 #(#before_format)*
                 let mut query = String::from(#query_base_str);
                 #parameter_placeholder_base
@@ -263,6 +409,7 @@ pub fn generate_select(
 
                 // Add output columns
                 #query_add_selected
+                #scalar_select
 
                 query.push_str(&format!(" FROM {}", #main_table_name));
                 // Handle potential table joins
@@ -353,18 +500,12 @@ pub fn generate_insert(
             let fetch_internals = |executor: TokenStream| {
                 quote! {
                         use #sql_crate::EasyExecutor as _;
-                    self.builder.build().fetch(conn.#executor()).map(|r| {
-                                    match r {
-                                        Ok(r) => {
-                                            let converted =
-                                                <#returning_type as #sql_crate::Output<#table_type, #driver>>::convert(r)
-                                                    .context("Output::convert failed")?;
-
-                                            Ok(converted)
-                                        }
-                                        Err(err) => Err(#macro_support::Error::from(err)),
-                                    }
-                                    .with_context(|| format!(#debug_format_str, #macro_input))
+                    self.builder.build().fetch(conn.#executor()).map(|r| -> #macro_support::Result<_> {
+                                    let converted =
+                                        #macro_support::query_lazy_row::<#table_type, #returning_type, #driver>(r)
+                                            .with_context(|| format!(#debug_format_str, #macro_input))?;
+
+                                    Ok(converted)
                                 })
                 }
             };
@@ -389,6 +530,9 @@ pub fn generate_insert(
             (
                 returning_select,
                 quote! {
+                    let builder = _easy_sql_builders
+                        .pop()
+                        .context("No statements were generated for the query")?;
                     #macro_support::Result::<LazyQueryResult>::Ok(LazyQueryResult { builder })
                 },
                 quote! {
@@ -443,8 +587,8 @@ pub fn generate_insert(
                     #query_add_selected
                 },
                 quote! {
-                    let built_query = builder.build();
-                    #macro_support::query_execute::<#table_type,#returning_type,_>(#connection,built_query).await.with_context(|| format!(#debug_format_str, #macro_input))
+                    let built_queries = _easy_sql_builders.iter_mut().map(|builder| builder.build()).collect::<Vec<_>>();
+                    #macro_support::query_execute_chunks::<#table_type,#returning_type,_>(#connection,built_queries).await.with_context(|| format!(#debug_format_str, #macro_input))
                 },
                 quote! {},
                 returning_arg_defs,
@@ -462,8 +606,8 @@ pub fn generate_insert(
         (
             quote! {},
             quote! {
-                let built_query = builder.build();
-                #macro_support::query_execute_no_output(#connection,built_query).await.with_context(|| format!(#debug_format_str, #macro_input))
+                let built_queries = _easy_sql_builders.iter_mut().map(|builder| builder.build()).collect::<Vec<_>>();
+                #macro_support::query_execute_chunks_no_output::<#table_type,_>(#connection,built_queries).await.with_context(|| format!(#debug_format_str, #macro_input))
             },
             quote! {},
             Vec::new(),
@@ -473,13 +617,20 @@ pub fn generate_insert(
         )
     };
 
-    let driver_arguments = driver.arguments(sql_crate);
     let identifier_delimiter = driver.identifier_delimiter(sql_crate);
-    let main_table_name = driver.table_name(sql_crate, &table_type);
+    let main_table_name = driver.qualified_table_name(sql_crate, &table_type);
     let parameter_placeholder_base = driver.parameter_placeholder_base(sql_crate);
     let parameter_placeholder_fn = driver.parameter_placeholder_fn(sql_crate, Span::call_site());
 
-    let query_insert_data = driver.query_insert_data(sql_crate, &table_type, values);
+    // query_lazy! can only hold a single statement, values are never split there
+    let reserved_parameters = if lazy_mode_driver.is_some() {
+        quote! {None}
+    } else {
+        let returning_parameters = returning_arg_binds.len();
+        quote! {Some(#returning_parameters)}
+    };
+    let query_insert_data =
+        driver.query_insert_data(sql_crate, &table_type, values, &reserved_parameters);
 
     let async_block = if lazy_mode_driver.is_some() {
         quote! {}
@@ -495,47 +646,52 @@ pub fn generate_insert(
                 use #macro_support::{Arguments,Context};
                 use #macro_support::ToConvert;
 
-                    let mut _easy_sql_args = #driver_arguments;
-                    let mut query = String::from("INSERT INTO ");
-                    let mut current_arg_n = 0;
                     let _easy_sql_d = #identifier_delimiter;
                     #parameter_placeholder_base
 
+                    let _ = || {
+                        fn __easy_sql_assert_writable<T: #sql_crate::markers::WritableTable>() {}
+                        __easy_sql_assert_writable::<#table_type>();
+                    };
                     #returning_checks
 
-                    query.push_str(#main_table_name);
-                    query.push_str(" (");
+                    // Values are split into multiple statements when they exceed driver bind parameter limit
+                    let (columns, chunks) = #query_insert_data.with_context(|| format!(#debug_format_str, #macro_input))?;
+                    let mut _easy_sql_builders = Vec::with_capacity(chunks.len());
 
-                    let (columns, new_args, count) = #query_insert_data.with_context(|| format!(#debug_format_str, #macro_input))?;
-                    for (i, col) in columns.iter().enumerate() {
-                        if i > 0 {
-                            query.push_str(", ");
-                        }
-                        query.push_str(&format!("{_easy_sql_d}{col}{_easy_sql_d}"));
-                    }
+                    // Only used by `RETURNING` arguments
+                    let _ = #parameter_placeholder_fn;
 
-                    query.push_str(") VALUES");
-                    _easy_sql_args = new_args;
+                    for chunk in chunks {
+                        #[allow(unused_mut)]
+                        let mut _easy_sql_args = chunk.args;
+                        let mut query = String::from("INSERT INTO ");
+                        #[allow(unused_variables)]
+                        let current_arg_n = chunk.parameters;
 
-                    for _ in 0..count {
+                        query.push_str(&#main_table_name);
                         query.push_str(" (");
-                        for i in 0..columns.len() {
-                            query.push_str(&#parameter_placeholder_fn(current_arg_n + i));
-                            query.push(',');
+
+                        for (i, col) in columns.iter().enumerate() {
+                            if i > 0 {
+                                query.push_str(", ");
+                            }
+                            query.push_str(&format!("{_easy_sql_d}{col}{_easy_sql_d}"));
                         }
-                        current_arg_n += columns.len();
+
+                        query.push_str(") VALUES");
+
+                        query.push_str(&chunk.values_sql);
                         query.pop(); // Remove last comma
-                        query.push_str("),");
-                    }
-                    query.pop(); // Remove last comma
 
-                    #(#returning_before_format)*
-                    #(#returning_arg_defs)*
-                    #returning_select
+                        #(#returning_before_format)*
+                        #(#returning_arg_defs)*
+                        #returning_select
 
-                    #(#returning_arg_binds)*
+                        #(#returning_arg_binds)*
 
-                    let mut builder = #macro_support::QueryBuilder::with_arguments(query, _easy_sql_args);
+                        _easy_sql_builders.push(#macro_support::QueryBuilder::with_arguments(query, _easy_sql_args));
+                    }
 
                     #execute_ending
 
@@ -585,26 +741,74 @@ pub fn generate_update(
         &mut types_driver_support_needed,
     );
 
+    // `Update` data can check `#[sql(lock_version)]`, its condition is appended to the WHERE
+    // clause (so the user condition is parenthesized)
+    let lock_version = matches!(set_clause_data, SetClause::FromType(_));
+    let has_where_clause = update.where_clause.is_some();
+    let where_clause = if lock_version {
+        update
+            .where_clause
+            .map(|where_expr| Expr::Parenthesized(Box::new(where_expr)))
+    } else {
+        update.where_clause
+    };
+
+    // Inline SET updates `#[sql(updated_at)]` columns it doesn't set (`Update` types do it in
+    // `Update::updates`)
+    let updated_at_set = if let SetClause::Expr(set_expr) = &set_clause_data {
+        let set_columns = set_expr.updates.iter().map(|(ident, _)| ident.to_string());
+        let updated_at_columns = driver.updated_at_columns(sql_crate, &table_type);
+        let current_timestamp = driver.current_timestamp(sql_crate);
+        quote! {
+            let _easy_sql_updated_at = #macro_support::updated_at_set(
+                #updated_at_columns,
+                &[#(#set_columns),*],
+                #current_timestamp,
+                _easy_sql_d,
+            );
+        }
+    } else {
+        quote! {}
+    };
+
     // Process SET clause first
     let set_code = set_clause(set_clause_data, &mut data);
 
     // Process WHERE clause with compile-time SQL generation
-    let where_code = if let Some(where_expr) = update.where_clause {
-        if !data.before_param_n.is_empty() {
-            let mut clause_format_str = String::new();
-            let mut clause_format_params = Vec::new();
-            let mut data =
-                data.with_format_str_and_params(&mut clause_format_str, &mut clause_format_params);
-            where_clause(where_expr, &mut data);
-
+    let where_code = if !data.before_param_n.is_empty() {
+        let mut clause_format_str = String::new();
+        let mut clause_format_params = Vec::new();
+        let mut data =
+            data.with_format_str_and_params(&mut clause_format_str, &mut clause_format_params);
+        soft_delete_where_clause(where_clause, update.include_deleted, &mut data);
+
+        if clause_format_str.is_empty() {
+            quote! {}
+        } else {
             quote! {
                 query.push_str(&format!(#clause_format_str,
                     #(#clause_format_params),*
                 ));
             }
+        }
+    } else {
+        soft_delete_where_clause(where_clause, update.include_deleted, &mut data);
+        quote! {}
+    };
+
+    let lock_version_code = if lock_version {
+        let where_added = if has_where_clause {
+            quote! {true}
+        } else if update.include_deleted {
+            quote! {false}
         } else {
-            where_clause(where_expr, &mut data);
-            quote! {}
+            quote! {!_easy_sql_not_deleted_where.is_empty()}
+        };
+        quote! {
+            if let Some(lock_version_condition) = &_easy_sql_lock_version_condition {
+                query.push_str(if #where_added { " AND " } else { " WHERE " });
+                query.push_str(lock_version_condition);
+            }
         }
     } else {
         quote! {}
@@ -647,34 +851,64 @@ pub fn generate_update(
                     query.push_str(" RETURNING ");
                     #query_add_selected
                 },
-                quote! {
-                    let mut builder = #macro_support::QueryBuilder::with_arguments(&query, _easy_sql_args);
-                    let built_query = builder.build();
-                    #macro_support::query_execute::<#table_type, #returning_type, _>(#connection, built_query)
+                if lock_version {
+                    quote! {
+                        let mut builder = #macro_support::QueryBuilder::with_arguments(&query, _easy_sql_args);
+                        let built_query = builder.build();
+                        #macro_support::query_execute_locked_returning::<#table_type, #returning_type, _>(
+                            #connection,
+                            built_query,
+                            _easy_sql_lock_version_condition.is_some(),
+                        )
                         .await
                         .with_context(|| format!(#debug_format_str, #macro_input))
+                    }
+                } else {
+                    quote! {
+                        let mut builder = #macro_support::QueryBuilder::with_arguments(&query, _easy_sql_args);
+                        let built_query = builder.build();
+                        #macro_support::query_execute::<#table_type, #returning_type, _>(#connection, built_query)
+                            .await
+                            .with_context(|| format!(#debug_format_str, #macro_input))
+                    }
                 },
                 returning_arg_defs,
             )
         } else {
             let fetch_internals = |executor: TokenStream| {
+                let rows = quote! {
+                    self.builder.build().fetch(conn.#executor()).map(|r| -> #macro_support::Result<_> {
+                                    let converted =
+                                        #macro_support::query_lazy_row::<#table_type, #returning_type, #lazy_mode_driver>(r)
+                                            .with_context(|| format!(#debug_format_str, #macro_input))?;
+
+                                    Ok(converted)
+                                })
+                };
+                // `#[sql(lock_version)]` conflicts end the stream without rows
+                let rows = if lock_version {
+                    quote! {
+                        #macro_support::locked_rows_stream::<#table_type, #lazy_mode_driver, _>(
+                            #rows,
+                            self.lock_version_checked,
+                        )
+                    }
+                } else {
+                    rows
+                };
                 quote! {
                         use #sql_crate::EasyExecutor as _;
-                    self.builder.build().fetch(conn.#executor()).map(|r| {
-                                    match r {
-                                        Ok(r) => {
-                                            let converted =
-                                                <#returning_type as #sql_crate::Output<#table_type, #lazy_mode_driver>>::convert(r)
-                                                    .context("Output::convert failed")?;
-
-                                            Ok(converted)
-                                        }
-                                        Err(err) => Err(#macro_support::Error::from(err)),
-                                    }
-                                    .with_context(|| format!(#debug_format_str, #macro_input))
-                                })
+                    #rows
                 }
             };
+            let (lock_version_field, lock_version_init) = if lock_version {
+                (
+                    quote! { lock_version_checked: bool, },
+                    quote! { lock_version_checked: _easy_sql_lock_version_condition.is_some(), },
+                )
+            } else {
+                (quote! {}, quote! {})
+            };
 
             let fetch_internals_normal = fetch_internals(quote! {into_executor});
 
@@ -712,6 +946,7 @@ pub fn generate_update(
 
                     struct LazyQueryResult<'_easy_sql_a> {
                         builder: #macro_support::QueryBuilder<'_easy_sql_a, #macro_support::InternalDriver<#lazy_mode_driver>>,
+                        #lock_version_field
                     }
 
                     impl<'_easy_sql_q> LazyQueryResult<'_easy_sql_q> {
@@ -729,7 +964,10 @@ pub fn generate_update(
                         }
                     }
 
-                    #macro_support::Result::<LazyQueryResult>::Ok(LazyQueryResult { builder })
+                    #macro_support::Result::<LazyQueryResult>::Ok(LazyQueryResult {
+                        builder,
+                        #lock_version_init
+                    })
                 },
                 returning_arg_defs,
             )
@@ -742,11 +980,24 @@ pub fn generate_update(
                 "UPDATE queries in query_lazy! macro must have a RETURNING clause, use normal query! macro otherwise"
             );
         };
+        let execute = if lock_version {
+            quote! {
+                #macro_support::query_execute_locked::<#table_type, _>(
+                    #connection,
+                    query,
+                    _easy_sql_lock_version_condition.is_some(),
+                )
+            }
+        } else {
+            quote! {
+                #macro_support::query_execute_no_output::<#table_type, _>(#connection, query)
+            }
+        };
         (
             quote! {},
             quote! {
                 let query = #macro_support::query_with(&query, _easy_sql_args);
-                #macro_support::query_execute_no_output(#connection, query)
+                #execute
                     .await
                     .with_context(|| format!(#debug_format_str, #macro_input))
             },
@@ -757,11 +1008,15 @@ pub fn generate_update(
     let driver_arguments = driver.arguments(sql_crate);
     let parameter_placeholder_base = driver.parameter_placeholder_base(sql_crate);
     let identifier_delimiter = driver.identifier_delimiter(sql_crate);
-    let main_table_name = driver.table_name(sql_crate, &table_type);
+    let main_table_name = driver.qualified_table_name(sql_crate, &table_type);
+    let soft_delete_filter =
+        soft_delete_filter(&driver, sql_crate, &table_type, update.include_deleted);
     checks.push(quote! {
         let _ = || {
             fn __easy_sql_assert_not_joined<T: #sql_crate::markers::NotJoinedTable>() {}
             __easy_sql_assert_not_joined::<#table_type>();
+            fn __easy_sql_assert_writable<T: #sql_crate::markers::WritableTable>() {}
+            __easy_sql_assert_writable::<#table_type>();
         };
     });
 
@@ -785,6 +1040,8 @@ pub fn generate_update(
                 let mut _easy_sql_args = #driver_arguments;
                 let _easy_sql_d = #identifier_delimiter;
                 #parameter_placeholder_base
+                #soft_delete_filter
+                #updated_at_set
                 #(#before_format)*
                 let mut query = format!("UPDATE {}",#main_table_name);
 
@@ -796,6 +1053,7 @@ pub fn generate_update(
 
                 // Build WHERE clause string
                 #where_code
+                #lock_version_code
 
                 // Add ALL parameter bindings
                 #(#all_binds)*
@@ -848,10 +1106,8 @@ pub fn generate_delete(
         &mut types_driver_support_needed,
     );
 
-    // Generate runtime code for WHERE clause
-    if let Some(where_expr) = delete.where_clause {
-        where_clause(where_expr, &mut data)
-    }
+    // Generate runtime code for WHERE clause, already soft deleted rows keep their timestamp
+    soft_delete_where_clause(delete.where_clause, delete.hard, &mut data);
 
     let lazy_mode_driver = if connection.is_none() {
         driver.single_driver()
@@ -893,7 +1149,7 @@ pub fn generate_delete(
                 quote! {
                     let mut builder = #macro_support::QueryBuilder::with_arguments(&query, _easy_sql_args);
                     let built_query = builder.build();
-                    #macro_support::query_execute(#connection, built_query)
+                    #macro_support::query_execute::<#table_type, _, _>(#connection, built_query)
                         .await
                         .with_context(|| format!(#debug_format_str, #macro_input))
                 },
@@ -903,18 +1159,12 @@ pub fn generate_delete(
             let fetch_internals = |executor: TokenStream| {
                 quote! {
                         use #sql_crate::EasyExecutor as _;
-                    self.builder.build().fetch(conn.#executor()).map(|r| {
-                                    match r {
-                                        Ok(r) => {
-                                            let converted =
-                                                <#returning_type as #sql_crate::Output<#table_type, #lazy_mode_driver>>::convert(r)
-                                                    .context("Output::convert failed")?;
-
-                                            Ok(converted)
-                                        }
-                                        Err(err) => Err(#macro_support::Error::from(err)),
-                                    }
-                                    .with_context(|| format!(#debug_format_str, #macro_input))
+                    self.builder.build().fetch(conn.#executor()).map(|r| -> #macro_support::Result<_> {
+                                    let converted =
+                                        #macro_support::query_lazy_row::<#table_type, #returning_type, #lazy_mode_driver>(r)
+                                            .with_context(|| format!(#debug_format_str, #macro_input))?;
+
+                                    Ok(converted)
                                 })
                 }
             };
@@ -990,7 +1240,7 @@ pub fn generate_delete(
             quote! {
                 let mut builder = #macro_support::QueryBuilder::with_arguments(&query, _easy_sql_args);
                 let built_query = builder.build();
-                #macro_support::query_execute_no_output(#connection, built_query)
+                #macro_support::query_execute_no_output::<#table_type, _>(#connection, built_query)
                     .await
                     .with_context(|| format!(#debug_format_str, #macro_input))
             },
@@ -1000,14 +1250,36 @@ pub fn generate_delete(
 
     let driver_arguments = driver.arguments(sql_crate);
     let identifier_delimiter = driver.identifier_delimiter(sql_crate);
-    let table_name = driver.table_name(sql_crate, &table_type);
+    let table_name = driver.qualified_table_name(sql_crate, &table_type);
     checks.push(quote! {
         let _ = || {
             fn __easy_sql_assert_not_joined<T: #sql_crate::markers::NotJoinedTable>() {}
             __easy_sql_assert_not_joined::<#table_type>();
+            fn __easy_sql_assert_writable<T: #sql_crate::markers::WritableTable>() {}
+            __easy_sql_assert_writable::<#table_type>();
         };
     });
     let parameter_placeholder_base = driver.parameter_placeholder_base(sql_crate);
+    let soft_delete_filter = soft_delete_filter(&driver, sql_crate, &table_type, delete.hard);
+    // Soft delete tables are updated instead, unless `HARD DELETE` is used
+    let delete_start = if delete.hard {
+        quote! {
+            format!("DELETE FROM {}", #table_name)
+        }
+    } else {
+        let soft_delete_column = driver.soft_delete_column(sql_crate, &table_type);
+        let current_timestamp = driver.current_timestamp(sql_crate);
+        quote! {
+            match #soft_delete_column {
+                Some(column) => format!(
+                    "UPDATE {} SET {_easy_sql_d}{column}{_easy_sql_d} = {}",
+                    #table_name,
+                    #current_timestamp
+                ),
+                None => format!("DELETE FROM {}", #table_name),
+            }
+        }
+    };
 
     let async_block = if lazy_mode_driver.is_some() {
         quote! {}
@@ -1029,9 +1301,10 @@ pub fn generate_delete(
                 let mut _easy_sql_args = #driver_arguments;
                 let _easy_sql_d = #identifier_delimiter;
                 #parameter_placeholder_base
+                #soft_delete_filter
                 #(#before_format)*
 
-                let mut query = format!("DELETE FROM {}", #table_name);
+                let mut query = #delete_start;
 
                 query.push_str(&format!(#format_str, #(#format_params),*));
 
@@ -1089,9 +1362,7 @@ pub fn generate_exists(
     );
 
     // Generate runtime code for WHERE clause
-    if let Some(where_expr) = exists.where_clause {
-        where_clause(where_expr, &mut data)
-    }
+    soft_delete_where_clause(exists.where_clause, exists.include_deleted, &mut data);
 
     // Build GROUP BY clause code if present
     if let Some(group_by_list) = exists.group_by {
@@ -1117,9 +1388,11 @@ pub fn generate_exists(
 
     let driver_arguments = driver.arguments(sql_crate);
     let identifier_delimiter = driver.identifier_delimiter(sql_crate);
-    let table_name = driver.table_name(sql_crate, &table_type);
+    let table_name = driver.qualified_table_name(sql_crate, &table_type);
     let table_joins = driver.table_joins(sql_crate, &table_type);
     let parameter_placeholder_base = driver.parameter_placeholder_base(sql_crate);
+    let soft_delete_filter =
+        soft_delete_filter(&driver, sql_crate, &table_type, exists.include_deleted);
 
     Ok(quote! {
         {
@@ -1134,6 +1407,7 @@ pub fn generate_exists(
                 let mut _easy_sql_args = #driver_arguments;
                 let _easy_sql_d = #identifier_delimiter;
                 #parameter_placeholder_base
+                #soft_delete_filter
                 #(#before_format)*
                 let mut query = format!("SELECT EXISTS(SELECT 1 FROM {}",
                     #table_name
@@ -1154,7 +1428,7 @@ pub fn generate_exists(
                 let mut builder = #macro_support::QueryBuilder::with_arguments(query, _easy_sql_args);
                 let built_query = builder.build();
 
-                #macro_support::query_exists_execute(#connection, built_query)
+                #macro_support::query_exists_execute::<#table_type, _>(#connection, built_query)
                     .await
                     .with_context(|| format!("sql query! macro input: {}", #macro_input))
             }
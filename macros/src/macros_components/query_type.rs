@@ -14,11 +14,13 @@ pub enum QueryType {
     Exists(ExistsQuery),
 }
 
-/// SELECT OutputType FROM TableType [WHERE ...] [ORDER BY ...] [LIMIT ...]
+/// SELECT OutputType [= expr] FROM TableType [WITH DELETED] [WHERE ...] [ORDER BY ...] [LIMIT ...]
 #[derive(Debug, Clone)]
 pub struct SelectQuery {
     pub output: ReturningData,
+    pub scalar: Option<ScalarOutput>,
     pub table_type: syn::Type,
+    pub include_deleted: bool,
     pub where_clause: Option<Expr>,
     pub order_by: Option<Vec<OrderBy>>,
     pub group_by: Option<Vec<Column>>,
@@ -35,10 +37,11 @@ pub struct InsertQuery {
     pub returning: Option<ReturningData>,
 }
 
-/// UPDATE TableType SET field = value [WHERE ...] [RETURNING OutputType]
+/// UPDATE TableType [WITH DELETED] SET field = value [WHERE ...] [RETURNING OutputType]
 #[derive(Debug, Clone)]
 pub struct UpdateQuery {
     pub table_type: syn::Type,
+    pub include_deleted: bool,
     pub set_clause: SetClause,
     pub where_clause: Option<Expr>,
     pub returning: Option<ReturningData>,
@@ -49,10 +52,12 @@ pub enum SetClause {
     Expr(SetExpr),
 }
 
-/// DELETE FROM TableType [WHERE ...] [RETURNING OutputType]
+/// [HARD] DELETE FROM TableType [WHERE ...] [RETURNING OutputType]
 #[derive(Debug, Clone)]
 pub struct DeleteQuery {
     pub table_type: syn::Type,
+    /// Removes rows even from soft delete tables
+    pub hard: bool,
     pub where_clause: Option<Expr>,
     pub returning: Option<ReturningData>,
 }
@@ -93,10 +98,84 @@ impl Parse for ReturningData {
     }
 }
 
-/// EXISTS TableType [WHERE ...] [GROUP BY ...] [HAVING ...] [ORDER BY ...] [LIMIT ...]
+/// How rows of a scalar output are collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarWrapper {
+    /// Exactly one row
+    Single,
+    Vec,
+    /// Zero or one row
+    Option,
+}
+
+/// `SELECT i64 = expr` / `SELECT Vec<(A, B)> = (expr, expr)`, columns are decoded by position
+#[derive(Debug, Clone)]
+pub struct ScalarOutput {
+    pub wrapper: ScalarWrapper,
+    /// Single value or tuple decoded from one row
+    pub row_type: syn::Type,
+    pub exprs: Vec<Expr>,
+}
+
+impl ScalarOutput {
+    /// Parses expressions after `=`, `output_type` is the whole type written by the user
+    fn parse_exprs(input: syn::parse::ParseStream, output_type: &syn::Type) -> syn::Result<Self> {
+        let (wrapper, row_type) = scalar_wrapper(output_type);
+
+        let exprs = match &row_type {
+            syn::Type::Tuple(tuple) if !tuple.elems.is_empty() => {
+                let inside_paren;
+                syn::parenthesized!(inside_paren in input);
+                let exprs = inside_paren
+                    .parse_terminated(Expr::parse, syn::Token![,])?
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                if exprs.len() != tuple.elems.len() {
+                    return Err(syn::Error::new_spanned(
+                        tuple,
+                        format!(
+                            "Tuple output has {} elements, but {} expressions were provided",
+                            tuple.elems.len(),
+                            exprs.len()
+                        ),
+                    ));
+                }
+                exprs
+            }
+            _ => vec![input.parse()?],
+        };
+
+        Ok(ScalarOutput {
+            wrapper,
+            row_type,
+            exprs,
+        })
+    }
+}
+
+/// Splits `Vec<T>` / `Option<T>` into the wrapper and the row type
+fn scalar_wrapper(ty: &syn::Type) -> (ScalarWrapper, syn::Type) {
+    if let syn::Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && let Some(last) = type_path.path.segments.last()
+        && let syn::PathArguments::AngleBracketed(args) = &last.arguments
+        && args.args.len() == 1
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        if last.ident == "Vec" {
+            return (ScalarWrapper::Vec, inner.clone());
+        } else if last.ident == "Option" {
+            return (ScalarWrapper::Option, inner.clone());
+        }
+    }
+    (ScalarWrapper::Single, ty.clone())
+}
+
+/// EXISTS TableType [WITH DELETED] [WHERE ...] [GROUP BY ...] [HAVING ...] [ORDER BY ...] [LIMIT ...]
 #[derive(Debug, Clone)]
 pub struct ExistsQuery {
     pub table_type: syn::Type,
+    pub include_deleted: bool,
     pub where_clause: Option<Expr>,
     pub group_by: Option<Vec<Column>>,
     pub having: Option<Expr>,
@@ -104,6 +183,17 @@ pub struct ExistsQuery {
     pub limit: Option<Limit>,
 }
 
+/// Optional `WITH DELETED` after the table type, disables soft delete filtering
+fn parse_include_deleted(input: syn::parse::ParseStream) -> syn::Result<bool> {
+    if input.peek(keyword::with) {
+        input.parse::<keyword::with>()?;
+        input.parse::<keyword::deleted>()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
 #[always_context]
 impl Parse for QueryType {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -118,7 +208,7 @@ impl Parse for QueryType {
         } else if lookahead.peek(keyword::update) {
             let update_query = input.parse::<UpdateQuery>()?;
             Ok(QueryType::Update(update_query))
-        } else if lookahead.peek(keyword::delete) {
+        } else if lookahead.peek(keyword::delete) || lookahead.peek(keyword::hard) {
             let delete_query = input.parse::<DeleteQuery>()?;
             Ok(QueryType::Delete(delete_query))
         } else if lookahead.peek(keyword::exists) {
@@ -143,8 +233,22 @@ impl Parse for SelectQuery {
 
         let output = input.parse::<ReturningData>()?;
 
+        let scalar = if input.peek(syn::Token![=]) {
+            let eq = input.parse::<syn::Token![=]>()?;
+            if output.output_args.is_some() {
+                return Err(syn::Error::new_spanned(
+                    eq,
+                    "Output arguments can't be used together with scalar outputs",
+                ));
+            }
+            Some(ScalarOutput::parse_exprs(input, &output.output_type)?)
+        } else {
+            None
+        };
+
         input.parse::<keyword::from>()?;
         let table_type = input.parse::<syn::Type>()?;
+        let include_deleted = parse_include_deleted(input)?;
 
         let mut where_clause = None;
         let mut order_by = None;
@@ -199,7 +303,9 @@ impl Parse for SelectQuery {
 
         Ok(SelectQuery {
             output,
+            scalar,
             table_type,
+            include_deleted,
             where_clause,
             order_by,
             group_by,
@@ -247,6 +353,7 @@ impl Parse for UpdateQuery {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         input.parse::<keyword::update>()?;
         let table_type = input.parse::<syn::Type>()?;
+        let include_deleted = parse_include_deleted(input)?;
         input.parse::<keyword::set>()?;
 
         // Check if SET expression is in braces or inline
@@ -280,6 +387,7 @@ impl Parse for UpdateQuery {
 
         Ok(UpdateQuery {
             table_type,
+            include_deleted,
             set_clause,
             where_clause,
             returning,
@@ -290,6 +398,10 @@ impl Parse for UpdateQuery {
 #[always_context]
 impl Parse for DeleteQuery {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let hard = input.peek(keyword::hard);
+        if hard {
+            input.parse::<keyword::hard>()?;
+        }
         input.parse::<keyword::delete>()?;
         input.parse::<keyword::from>()?;
         let table_type = input.parse::<syn::Type>()?;
@@ -313,6 +425,7 @@ impl Parse for DeleteQuery {
 
         Ok(DeleteQuery {
             table_type,
+            hard,
             where_clause,
             returning,
         })
@@ -324,6 +437,7 @@ impl Parse for ExistsQuery {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         input.parse::<keyword::exists>()?;
         let table_type = input.parse::<syn::Type>()?;
+        let include_deleted = parse_include_deleted(input)?;
 
         let mut where_clause = None;
         let mut group_by = None;
@@ -378,6 +492,7 @@ impl Parse for ExistsQuery {
 
         Ok(ExistsQuery {
             table_type,
+            include_deleted,
             where_clause,
             group_by,
             having,
@@ -1,7 +1,7 @@
 use super::{
     CollectedData, builtin_functions,
     column::Column,
-    expr::Expr,
+    expr::{Expr, all_of},
 };
 use ::{
     proc_macro2::{self},
@@ -17,6 +17,8 @@ pub enum Value {
     Column(Column),
     Lit(syn::Lit),
     OutsideVariable(syn::Expr),
+    /// `{?var}` with `var: Option<T>`, AND / OR operand containing it is removed when `None`
+    OptionalVariable(syn::Expr),
     Cast {
         expr: Box<Expr>,
         ty: syn::Type,
@@ -151,6 +153,36 @@ impl Value {
                 *data.current_param_n += 1;
                 "{}".to_string()
             }
+            Value::OptionalVariable(expr_val) => {
+                if for_custom_select || !data.optional_predicates {
+                    data.checks.push(quote::quote_spanned! {expr_val.span()=>
+                        {
+                            compile_error!("Optional variables ({?var}) are only allowed inside of WHERE and HAVING clauses of query! and query_lazy!");
+                        }
+                    });
+                    return "{}".to_string();
+                }
+
+                let debug_str = format!(
+                    "Failed to bind `{}` to query parameter",
+                    expr_val.to_token_stream()
+                );
+                // Only reached when the predicate wasn't left out, see `Expr::optional_condition`
+                data.binds.push(quote::quote_spanned! {expr_val.span()=>
+                    if let Some(__easy_sql_optional_value) = (#expr_val).as_ref() {
+                        _easy_sql_args.add(__easy_sql_optional_value).map_err(anyhow::Error::from_boxed).context(#debug_str)?;
+                    }
+                });
+                data.format_params.push(data.driver.parameter_placeholder(
+                    sql_crate,
+                    expr_val.span(),
+                    &data.before_param_n,
+                    *data.current_param_n,
+                ));
+
+                *data.current_param_n += 1;
+                "{}".to_string()
+            }
             Value::Cast { expr, ty } => {
                 for driver_ty in data.driver.iter_for_checks() {
                     data.checks.push(quote_spanned! {ty.span()=>
@@ -227,6 +259,20 @@ impl Value {
         }
     }
 
+    /// See [`Expr::optional_condition`]
+    pub(super) fn optional_condition(&self) -> Option<proc_macro2::TokenStream> {
+        match self {
+            Value::OptionalVariable(expr) => Some(quote_spanned! {expr.span()=>
+                (#expr).is_some()
+            }),
+            Value::FunctionCall {
+                args: Some(args), ..
+            } => all_of(args.iter().map(Expr::optional_condition)),
+            Value::Cast { expr, .. } => expr.optional_condition(),
+            _ => None,
+        }
+    }
+
     pub(super) fn collect_indices_impl(&self, indices: &mut std::collections::BTreeSet<usize>) {
         match self {
             Value::OutsideVariable(expr) => {